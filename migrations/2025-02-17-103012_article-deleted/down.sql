@@ -0,0 +1,3 @@
+ALTER TABLE article
+    DROP COLUMN deleted;
+
@@ -0,0 +1,3 @@
+ALTER TABLE article
+    ADD COLUMN deleted bool NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,9 @@
+CREATE TABLE article_redirect (
+    id serial PRIMARY KEY,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    title text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (article_id, title)
+);
+
+CREATE INDEX article_redirect_title ON article_redirect (title);
@@ -0,0 +1,2 @@
+ALTER TABLE edit
+    DROP COLUMN snapshot;
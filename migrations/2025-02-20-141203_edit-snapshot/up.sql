@@ -0,0 +1,2 @@
+ALTER TABLE edit
+    ADD COLUMN snapshot text;
@@ -0,0 +1,2 @@
+-- Must be identical to the expression used in `DbArticle::search`, otherwise the index is not used
+CREATE INDEX article_search ON article USING gin ((setweight(to_tsvector('english', replace(title, '_', ' ')), 'A') || setweight(to_tsvector('english', text), 'B')));
@@ -0,0 +1,9 @@
+CREATE TABLE article_watch (
+    id serial PRIMARY KEY,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    last_read timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (article_id, person_id)
+);
+
+CREATE INDEX article_watch_person ON article_watch (person_id);
@@ -0,0 +1,7 @@
+ALTER TABLE comment
+    ADD COLUMN read_by_parent_creator boolean NOT NULL DEFAULT FALSE;
+
+ALTER TABLE article_watch
+    ADD COLUMN last_read timestamptz NOT NULL DEFAULT now();
+
+DROP TABLE notification;
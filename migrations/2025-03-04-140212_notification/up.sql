@@ -0,0 +1,72 @@
+CREATE TABLE notification (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    conflict_id int REFERENCES conflict ON UPDATE CASCADE ON DELETE CASCADE,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    edit_id int REFERENCES edit ON UPDATE CASCADE ON DELETE CASCADE,
+    read boolean NOT NULL DEFAULT false,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX notification_person_read ON notification (person_id, read);
+
+-- existing edit conflicts
+INSERT INTO notification (person_id, article_id, conflict_id, published)
+SELECT
+    creator_id,
+    article_id,
+    id,
+    published
+FROM
+    conflict;
+
+-- unread comment replies
+INSERT INTO notification (person_id, article_id, comment_id, published)
+SELECT
+    parent.creator_id,
+    c.article_id,
+    c.id,
+    c.published
+FROM
+    comment c
+    JOIN comment parent ON c.parent_id = parent.id
+    JOIN local_user ON local_user.person_id = parent.creator_id
+WHERE
+    c.creator_id != parent.creator_id
+    AND NOT c.deleted
+    AND NOT c.read_by_parent_creator;
+
+-- unread edits on watched articles
+INSERT INTO notification (person_id, article_id, edit_id, published)
+SELECT
+    article_watch.person_id,
+    edit.article_id,
+    edit.id,
+    edit.published
+FROM
+    edit
+    JOIN article_watch ON article_watch.article_id = edit.article_id
+WHERE
+    edit.published > article_watch.last_read
+    AND edit.creator_id != article_watch.person_id
+    AND NOT edit.pending;
+
+-- articles waiting for approval, for each admin
+INSERT INTO notification (person_id, article_id, published)
+SELECT
+    local_user.person_id,
+    article.id,
+    article.published
+FROM
+    article
+    CROSS JOIN local_user
+WHERE
+    local_user.admin
+    AND NOT article.approved;
+
+ALTER TABLE comment
+    DROP COLUMN read_by_parent_creator;
+
+ALTER TABLE article_watch
+    DROP COLUMN last_read;
@@ -0,0 +1,15 @@
+DROP TABLE password_reset_request;
+
+DROP TABLE email_verification;
+
+ALTER TABLE notification
+    DROP COLUMN emailed;
+
+ALTER TABLE local_user
+    DROP COLUMN email_notifications;
+
+ALTER TABLE local_user
+    DROP COLUMN email_verified;
+
+ALTER TABLE local_user
+    DROP COLUMN email;
@@ -0,0 +1,25 @@
+ALTER TABLE local_user
+    ADD COLUMN email text UNIQUE;
+
+ALTER TABLE local_user
+    ADD COLUMN email_verified bool NOT NULL DEFAULT FALSE;
+
+ALTER TABLE local_user
+    ADD COLUMN email_notifications bool NOT NULL DEFAULT FALSE;
+
+ALTER TABLE notification
+    ADD COLUMN emailed bool NOT NULL DEFAULT FALSE;
+
+CREATE TABLE email_verification (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    token text NOT NULL UNIQUE,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE password_reset_request (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    token text NOT NULL UNIQUE,
+    published timestamptz NOT NULL DEFAULT now()
+);
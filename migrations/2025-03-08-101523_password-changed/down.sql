@@ -0,0 +1,2 @@
+ALTER TABLE local_user
+    DROP COLUMN password_changed;
@@ -0,0 +1,2 @@
+ALTER TABLE local_user
+    ADD COLUMN password_changed timestamptz;
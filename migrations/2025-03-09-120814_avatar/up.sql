@@ -0,0 +1,3 @@
+ALTER TABLE person
+    ADD COLUMN avatar varchar(255),
+    ADD COLUMN banner varchar(255);
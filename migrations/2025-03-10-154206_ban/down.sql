@@ -0,0 +1,5 @@
+DROP TABLE mod_log;
+
+ALTER TABLE person
+    DROP COLUMN banned,
+    DROP COLUMN ban_expires;
@@ -0,0 +1,14 @@
+ALTER TABLE person
+    ADD COLUMN banned bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN ban_expires timestamptz;
+
+CREATE TABLE mod_log (
+    id serial PRIMARY KEY,
+    mod_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    action text NOT NULL,
+    target_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    target_article_id int REFERENCES article ON UPDATE CASCADE ON DELETE SET NULL,
+    reason text,
+    expires timestamptz,
+    published timestamptz NOT NULL DEFAULT now()
+);
@@ -0,0 +1,6 @@
+CREATE TABLE federation_list (
+    id serial PRIMARY KEY,
+    domain text NOT NULL UNIQUE,
+    blocked bool NOT NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
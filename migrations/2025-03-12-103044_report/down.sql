@@ -0,0 +1,4 @@
+ALTER TABLE notification
+    DROP COLUMN report_id;
+
+DROP TABLE report;
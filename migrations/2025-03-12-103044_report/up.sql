@@ -0,0 +1,15 @@
+CREATE TABLE report (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    edit_id int REFERENCES edit ON UPDATE CASCADE ON DELETE CASCADE,
+    comment_id int REFERENCES COMMENT ON UPDATE CASCADE ON DELETE CASCADE,
+    reason text NOT NULL,
+    ap_id varchar(255) NOT NULL UNIQUE,
+    local bool NOT NULL,
+    resolved bool NOT NULL DEFAULT FALSE,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+ALTER TABLE notification
+    ADD COLUMN report_id int REFERENCES report ON UPDATE CASCADE ON DELETE CASCADE;
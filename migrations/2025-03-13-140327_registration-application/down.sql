@@ -0,0 +1,10 @@
+DROP TABLE captcha_answer;
+
+DELETE FROM notification
+WHERE article_id IS NULL;
+
+ALTER TABLE notification
+    DROP COLUMN registration_application_id,
+    ALTER COLUMN article_id SET NOT NULL;
+
+DROP TABLE registration_application;
@@ -0,0 +1,19 @@
+CREATE TABLE registration_application (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL UNIQUE,
+    answer text NOT NULL,
+    accepted bool NOT NULL DEFAULT FALSE,
+    admin_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    deny_reason text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+ALTER TABLE notification
+    ALTER COLUMN article_id DROP NOT NULL,
+    ADD COLUMN registration_application_id int REFERENCES registration_application ON UPDATE CASCADE ON DELETE CASCADE;
+
+CREATE TABLE captcha_answer (
+    uuid uuid PRIMARY KEY DEFAULT gen_random_uuid (),
+    answer text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now()
+);
@@ -0,0 +1,14 @@
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    token_hash text NOT NULL UNIQUE,
+    scope_read bool NOT NULL,
+    scope_edit bool NOT NULL,
+    scope_comment bool NOT NULL,
+    scope_admin bool NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    last_used timestamptz
+);
+
+CREATE INDEX api_token_person ON api_token (person_id);
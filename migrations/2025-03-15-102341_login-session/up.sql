@@ -0,0 +1,9 @@
+CREATE TABLE login_session (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    user_agent text,
+    published timestamptz NOT NULL DEFAULT now(),
+    last_used timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX login_session_person ON login_session (person_id);
@@ -0,0 +1,10 @@
+CREATE TABLE oauth_account (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    provider text NOT NULL,
+    subject text NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (provider, subject)
+);
+
+CREATE INDEX oauth_account_person ON oauth_account (person_id);
@@ -42,9 +42,9 @@ killall ibis || true
 CARGO_TARGET_DIR=target/frontend trunk build
 
 # launch a couple of local instances to test federation, then wait for processes to finish
-IBIS__BIND=127.0.0.1:8090 IBIS__FEDERATION__DOMAIN=ibis-alpha:8090 IBIS__DATABASE_URL=$ALPHA_DB_URL cargo run &
+IBIS__BIND=127.0.0.1:8090 IBIS__FEDERATION__DOMAIN=ibis-alpha:8090 IBIS__DATABASE_URL=$ALPHA_DB_URL IBIS__MEDIA__ALLOW_PRIVATE_ADDRESSES=true cargo run &
 PID_ALPHA=($!)
-IBIS__BIND=127.0.0.1:8091 IBIS__FEDERATION__DOMAIN=ibis-beta:8091 IBIS__DATABASE_URL=$BETA_DB_URL cargo run &
+IBIS__BIND=127.0.0.1:8091 IBIS__FEDERATION__DOMAIN=ibis-beta:8091 IBIS__DATABASE_URL=$BETA_DB_URL IBIS__MEDIA__ALLOW_PRIVATE_ADDRESSES=true cargo run &
 PID_BETA=($!)
 
 wait $PID_ALPHA
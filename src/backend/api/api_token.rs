@@ -0,0 +1,54 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        utils::{error::BackendResult, validate::validate_not_empty},
+    },
+    common::{
+        api_token::{
+            CreateApiTokenParams,
+            CreateApiTokenResponse,
+            DbApiToken,
+            DeleteApiTokenParams,
+        },
+        user::LocalUserView,
+        SuccessResponse,
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{Extension, Form, Json};
+use axum_macros::debug_handler;
+
+/// Create a new api token for the current user. The token is only returned once.
+#[debug_handler]
+pub(in crate::backend::api) async fn create_api_token(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateApiTokenParams>,
+) -> BackendResult<Json<CreateApiTokenResponse>> {
+    validate_not_empty(&params.name)?;
+    if params.scope_admin && !user.local_user.admin {
+        return Err(anyhow!("Only admins can create api tokens with admin scope").into());
+    }
+    let (api_token, token) = DbApiToken::create(user.person.id, params, &context)?;
+    Ok(Json(CreateApiTokenResponse { api_token, token }))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn list_api_tokens(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<DbApiToken>>> {
+    Ok(Json(DbApiToken::list(user.person.id, &context)?))
+}
+
+/// Revoke an api token, so that it can't be used anymore.
+#[debug_handler]
+pub(in crate::backend::api) async fn delete_api_token(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteApiTokenParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbApiToken::delete(params.id, user.person.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
@@ -1,17 +1,23 @@
-use super::check_is_admin;
 use crate::{
     backend::{
         database::{
             article::DbArticleForm,
             conflict::{DbConflict, DbConflictForm},
             edit::DbEditForm,
+            mod_log::DbModLogForm,
             IbisContext,
         },
-        federation::activities::{create_article::CreateArticle, submit_article_update},
+        federation::activities::{
+            create_article::CreateArticle,
+            delete_article::DeleteArticle,
+            submit_article_update,
+            undo_delete_article::UndoDeleteArticle,
+            update_local_article::UpdateLocalArticle,
+        },
         utils::{
             error::BackendResult,
-            generate_article_version,
-            validate::{validate_article_title, validate_not_empty},
+            media::store_article_image,
+            validate::{validate_article_title, validate_not_banned, validate_not_empty},
         },
     },
     common::{
@@ -22,6 +28,7 @@ use crate::{
             DbArticle,
             DbArticleView,
             DbEdit,
+            DeleteArticleParams,
             DeleteConflictParams,
             EditArticleParams,
             EditVersion,
@@ -29,20 +36,27 @@ use crate::{
             GetArticleParams,
             GetConflictParams,
             ListArticlesParams,
+            MoveArticleParams,
             ProtectArticleParams,
+            RevertArticleParams,
             SearchArticleParams,
+            SearchArticleResult,
+            UploadImageResponse,
+            WatchArticleParams,
         },
         comment::DbComment,
         instance::DbInstance,
+        mod_log::{DbModLog, ModAction},
         user::LocalUserView,
-        utils::{extract_domain, http_protocol_str},
         validation::can_edit_article,
         ResolveObjectParams,
+        SuccessResponse,
+        MAIN_PAGE_NAME,
     },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use anyhow::anyhow;
-use axum::{extract::Query, Extension, Form, Json};
+use axum::{body::Bytes, extract::Query, Extension, Form, Json};
 use axum_macros::debug_handler;
 use chrono::Utc;
 use diffy::create_patch;
@@ -56,14 +70,14 @@ pub(in crate::backend::api) async fn create_article(
 ) -> BackendResult<Json<DbArticleView>> {
     params.title = validate_article_title(&params.title)?;
     validate_not_empty(&params.text)?;
+    validate_not_banned(&user.person)?;
+
+    if DbArticle::local_title_exists(&params.title, &context)? {
+        return Err(anyhow!("Article with this title already exists").into());
+    }
 
     let local_instance = DbInstance::read_local(&context)?;
-    let ap_id = ObjectId::parse(&format!(
-        "{}://{}/article/{}",
-        http_protocol_str(),
-        extract_domain(&local_instance.ap_id),
-        params.title
-    ))?;
+    let ap_id = DbArticle::generate_ap_id(&params.title, &context)?;
     let form = DbArticleForm {
         title: params.title,
         text: String::new(),
@@ -108,6 +122,7 @@ pub(in crate::backend::api) async fn edit_article(
     Form(mut params): Form<EditArticleParams>,
 ) -> BackendResult<Json<Option<ApiConflict>>> {
     validate_not_empty(&params.new_text)?;
+    validate_not_banned(&user.person)?;
     // resolve conflict if any
     if let Some(resolve_conflict_id) = params.resolve_conflict_id {
         DbConflict::delete(resolve_conflict_id, user.person.id, &context)?;
@@ -147,8 +162,8 @@ pub(in crate::backend::api) async fn edit_article(
     } else {
         // There have been other changes since this edit was initiated. Get the common ancestor
         // version and generate a diff to find out what exactly has changed.
-        let edits = DbEdit::list_for_article(original_article.article.id, &context)?;
-        let ancestor = generate_article_version(&edits, &params.previous_version_id)?;
+        let mut edits = DbEdit::list_for_article(original_article.article.id, &context)?;
+        let ancestor = DbEdit::generate_version(&mut edits, &params.previous_version_id, &context)?;
         let patch = create_patch(&ancestor, &new_text);
 
         let previous_version = DbEdit::read(&params.previous_version_id, &context)?;
@@ -165,27 +180,69 @@ pub(in crate::backend::api) async fn edit_article(
     }
 }
 
+/// Reset the article text to an earlier version. This creates a new edit with the old text, so
+/// it works the same for local and remote articles.
+#[debug_handler]
+pub(in crate::backend::api) async fn revert_article(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<RevertArticleParams>,
+) -> BackendResult<Json<DbArticleView>> {
+    validate_not_banned(&user.person)?;
+    let original_article = DbArticle::read_view(params.article_id, &context)?;
+    can_edit_article(&original_article.article, user.local_user.admin)?;
+    let mut edits = DbEdit::list_for_article(original_article.article.id, &context)?;
+    let reverted_edit = edits
+        .iter()
+        .find(|e| e.hash == params.version)
+        .cloned()
+        .ok_or(anyhow!("Version does not belong to this article"))?;
+    if reverted_edit.pending {
+        return Err(anyhow!("Can't revert to a pending edit").into());
+    }
+    let new_text = DbEdit::generate_version(&mut edits, &params.version, &context)?;
+    if new_text == original_article.article.text {
+        return Err(anyhow!("Article already has the same text as this version").into());
+    }
+    let summary = format!(
+        "Revert to version from {}: {}",
+        reverted_edit.published.format("%Y-%m-%d %H:%M"),
+        reverted_edit.summary
+    );
+
+    submit_article_update(
+        new_text,
+        summary,
+        original_article.latest_version,
+        &original_article.article,
+        user.person.id,
+        &context,
+    )
+    .await?;
+    Ok(Json(DbArticle::read_view(params.article_id, &context)?))
+}
+
 /// Retrieve an article by ID. It must already be stored in the local database.
 #[debug_handler]
 pub(in crate::backend::api) async fn get_article(
     Query(query): Query<GetArticleParams>,
+    user: Option<Extension<LocalUserView>>,
     context: Data<IbisContext>,
 ) -> BackendResult<Json<DbArticleView>> {
-    match (query.title, query.id) {
-        (Some(title), None) => Ok(Json(DbArticle::read_view_title(
-            &title,
-            query.domain,
-            &context,
-        )?)),
+    let mut article = match (query.title, query.id) {
+        (Some(title), None) => DbArticle::read_view_title(&title, query.domain, &context)?,
         (None, Some(id)) => {
             if query.domain.is_some() {
                 return Err(anyhow!("Cant combine id and instance_domain").into());
             }
-            let article = DbArticle::read_view(id, &context)?;
-            Ok(Json(article))
+            DbArticle::read_view(id, &context)?
         }
-        _ => Err(anyhow!("Must pass exactly one of title, id").into()),
+        _ => return Err(anyhow!("Must pass exactly one of title, id").into()),
+    };
+    if let Some(user) = user {
+        article.watched = DbArticle::is_watched(article.article.id, user.person.id, &context)?;
     }
+    Ok(Json(article))
 }
 
 #[debug_handler]
@@ -193,11 +250,38 @@ pub(in crate::backend::api) async fn list_articles(
     Query(query): Query<ListArticlesParams>,
     context: Data<IbisContext>,
 ) -> BackendResult<Json<Vec<DbArticle>>> {
-    Ok(Json(DbArticle::read_all(
-        query.only_local,
-        query.instance_id,
-        &context,
-    )?))
+    Ok(Json(DbArticle::read_all(&query, &context)?))
+}
+
+/// Change the title of a local article. The article id stays the same, and the old title keeps
+/// working as redirect. Remote articles need to be renamed on their origin instance.
+#[debug_handler]
+pub(in crate::backend::api) async fn move_article(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<MoveArticleParams>,
+) -> BackendResult<Json<DbArticleView>> {
+    let article = DbArticle::read(params.article_id, &context)?;
+    if !article.local {
+        return Err(anyhow!("Only local articles can be moved").into());
+    }
+    if article.title == MAIN_PAGE_NAME {
+        return Err(anyhow!("Main page can't be moved").into());
+    }
+    can_edit_article(&article, user.local_user.admin)?;
+    params.new_title = validate_article_title(&params.new_title)?;
+    if params.new_title == article.title {
+        return Err(anyhow!("New title is the same as old title").into());
+    }
+    // titles which are only used as redirect of another article can be taken over
+    if DbArticle::local_title_exists(&params.new_title, &context)? {
+        return Err(anyhow!("Article with this title already exists").into());
+    }
+
+    let updated = DbArticle::update_title(&article, &params.new_title, &context)?;
+    UpdateLocalArticle::send(updated, vec![], &context).await?;
+
+    Ok(Json(DbArticle::read_view(article.id, &context)?))
 }
 
 /// Fork a remote article to local instance. This is useful if there are disagreements about
@@ -210,15 +294,17 @@ pub(in crate::backend::api) async fn fork_article(
 ) -> BackendResult<Json<DbArticleView>> {
     // TODO: lots of code duplicated from create_article(), can move it into helper
     let original_article = DbArticle::read_view(params.article_id, &context)?;
+    if original_article.article.deleted {
+        return Err(anyhow!("Article is deleted").into());
+    }
     params.new_title = validate_article_title(&params.new_title)?;
 
+    if DbArticle::local_title_exists(&params.new_title, &context)? {
+        return Err(anyhow!("Article with this title already exists").into());
+    }
+
     let local_instance = DbInstance::read_local(&context)?;
-    let ap_id = ObjectId::parse(&format!(
-        "{}://{}/article/{}",
-        http_protocol_str(),
-        extract_domain(&local_instance.ap_id),
-        &params.new_title
-    ))?;
+    let ap_id = DbArticle::generate_ap_id(&params.new_title, &context)?;
     let form = DbArticleForm {
         title: params.new_title,
         text: original_article.article.text.clone(),
@@ -271,19 +357,20 @@ pub(super) async fn resolve_article(
         instance,
         comments,
         latest_version,
+        watched: false,
     }))
 }
 
-/// Search articles for matching title or body text.
+/// Search articles for matching title or body text, ordered by relevance.
 #[debug_handler]
 pub(super) async fn search_article(
     Query(query): Query<SearchArticleParams>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<DbArticle>>> {
-    if query.query.is_empty() {
+) -> BackendResult<Json<Vec<SearchArticleResult>>> {
+    if query.query.trim().is_empty() {
         return Err(anyhow!("Query is empty").into());
     }
-    let article = DbArticle::search(&query.query, &context)?;
+    let article = DbArticle::search(&query, &context)?;
     Ok(Json(article))
 }
 
@@ -293,8 +380,44 @@ pub(in crate::backend::api) async fn protect_article(
     context: Data<IbisContext>,
     Form(params): Form<ProtectArticleParams>,
 ) -> BackendResult<Json<DbArticle>> {
-    check_is_admin(&user)?;
     let article = DbArticle::update_protected(params.article_id, params.protected, &context)?;
+    let action = if params.protected {
+        ModAction::ProtectArticle
+    } else {
+        ModAction::UnprotectArticle
+    };
+    DbModLog::create(
+        DbModLogForm::article(user.person.id, action, article.id),
+        &context,
+    )?;
+    Ok(Json(article))
+}
+
+/// Mark an article as deleted, or restore it. Deleted articles are hidden from listings and
+/// search. If the article is local, the change is federated to followers.
+#[debug_handler]
+pub(in crate::backend::api) async fn delete_article(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteArticleParams>,
+) -> BackendResult<Json<DbArticle>> {
+    let article = DbArticle::update_deleted(params.article_id, params.deleted, &context)?;
+    let action = if params.deleted {
+        ModAction::DeleteArticle
+    } else {
+        ModAction::RestoreArticle
+    };
+    DbModLog::create(
+        DbModLogForm::article(user.person.id, action, article.id),
+        &context,
+    )?;
+    if article.local {
+        if params.deleted {
+            DeleteArticle::send(&article, &context).await?;
+        } else {
+            UndoDeleteArticle::send(&article, &context).await?;
+        }
+    }
     Ok(Json(article))
 }
 
@@ -304,7 +427,16 @@ pub async fn approve_article(
     context: Data<IbisContext>,
     Form(params): Form<ApproveArticleParams>,
 ) -> BackendResult<Json<()>> {
-    check_is_admin(&user)?;
+    // log entry needs to be written first, otherwise the article reference is invalid
+    let action = if params.approve {
+        ModAction::ApproveArticle
+    } else {
+        ModAction::RejectArticle
+    };
+    DbModLog::create(
+        DbModLogForm::article(user.person.id, action, params.article_id),
+        &context,
+    )?;
     if params.approve {
         DbArticle::update_approved(params.article_id, true, &context)?;
     } else {
@@ -336,3 +468,27 @@ pub async fn delete_conflict(
     DbConflict::delete(params.conflict_id, user.person.id, &context)?;
     Ok(Json(()))
 }
+
+/// Start or stop watching an article. Watched articles generate a notification for each new
+/// edit, no matter if it was made locally or received over federation.
+#[debug_handler]
+pub(in crate::backend::api) async fn watch_article(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<WatchArticleParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    // ensure that article exists
+    DbArticle::read(params.article_id, &context)?;
+    DbArticle::watch(params.article_id, user.person.id, params.watch, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Upload an image which can be embedded in articles. The request body contains the image file.
+#[debug_handler]
+pub(in crate::backend::api) async fn upload_image(
+    context: Data<IbisContext>,
+    body: Bytes,
+) -> BackendResult<Json<UploadImageResponse>> {
+    let path = store_article_image(body.to_vec(), &context).await?;
+    Ok(Json(UploadImageResponse { path }))
+}
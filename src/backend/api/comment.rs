@@ -2,6 +2,7 @@ use crate::{
     backend::{
         database::{
             comment::{DbCommentInsertForm, DbCommentUpdateForm},
+            notifications::DbNotification,
             IbisContext,
         },
         federation::activities::comment::{
@@ -11,7 +12,7 @@ use crate::{
         },
         utils::{
             error::BackendResult,
-            validate::{validate_comment_max_depth, validate_not_empty},
+            validate::{validate_comment_max_depth, validate_not_banned, validate_not_empty},
         },
     },
     common::{
@@ -40,6 +41,7 @@ pub(in crate::backend::api) async fn create_comment(
     Form(params): Form<CreateCommentParams>,
 ) -> BackendResult<Json<DbCommentView>> {
     validate_not_empty(&params.content)?;
+    validate_not_banned(&user.person)?;
     let mut depth = 0;
     if let Some(parent_id) = params.parent_id {
         let parent = DbComment::read(parent_id, &context)?;
@@ -86,6 +88,7 @@ pub(in crate::backend::api) async fn edit_comment(
     context: Data<IbisContext>,
     Form(params): Form<EditCommentParams>,
 ) -> BackendResult<Json<DbCommentView>> {
+    validate_not_banned(&user.person)?;
     if let Some(content) = &params.content {
         validate_not_empty(content)?;
     }
@@ -118,12 +121,14 @@ pub(in crate::backend::api) async fn edit_comment(
     Ok(Json(comment))
 }
 
+/// Deprecated, use `/user/notifications/mark_as_read` instead.
+// TODO: remove in 0.3
 #[debug_handler]
 pub(in crate::backend::api) async fn mark_as_read(
-    user: Extension<LocalUserView>,
+    Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
     Form(params): Form<MarkAsReadParams>,
 ) -> BackendResult<Json<SuccessResponse>> {
-    DbComment::mark_as_read(params.id, user.person.id, &context)?;
+    DbNotification::mark_reply_as_read(params.id, user.person.id, &context)?;
     Ok(Json(SuccessResponse::default()))
 }
@@ -2,16 +2,20 @@ use super::empty_to_none;
 use crate::{
     backend::{
         database::{instance::DbInstanceUpdateForm, IbisContext},
-        federation::activities::follow::Follow,
+        federation::activities::{follow::Follow, update_instance::UpdateInstance},
         utils::error::BackendResult,
     },
     common::{
         instance::{
+            AddFederationListParams,
+            DbFederationListEntry,
             DbInstance,
             FollowInstanceParams,
             GetInstanceParams,
             InstanceView,
             InstanceView2,
+            ListInstancesParams,
+            RemoveFederationListParams,
             UpdateInstanceParams,
         },
         user::LocalUserView,
@@ -20,8 +24,10 @@ use crate::{
     },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use anyhow::anyhow;
 use axum::{extract::Query, Extension, Form, Json};
 use axum_macros::debug_handler;
+use url::Url;
 
 /// Retrieve details about an instance. If no id is provided, return local instance.
 #[debug_handler]
@@ -43,7 +49,9 @@ pub(in crate::backend::api) async fn update_instance(
         name: params.name,
         topic: params.topic,
     };
-    Ok(Json(DbInstance::update(form, &context)?))
+    let instance = DbInstance::update(form, &context)?;
+    UpdateInstance::send(instance.clone(), &context).await?;
+    Ok(Json(instance))
 }
 
 /// Make the local instance follow a given remote instance, to receive activities about new and
@@ -83,8 +91,62 @@ pub(in crate::backend::api) async fn list_instances(
 
 #[debug_handler]
 pub(in crate::backend::api) async fn list_instance_views(
+    Query(params): Query<ListInstancesParams>,
     context: Data<IbisContext>,
 ) -> BackendResult<Json<Vec<InstanceView>>> {
-    let instances = DbInstance::list_views(&context)?;
+    let instances = DbInstance::list_views(&params, &context)?;
     Ok(Json(instances))
 }
+
+/// List domains which are explicitly allowed or blocked for federation.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_federation_list(
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<DbFederationListEntry>>> {
+    Ok(Json(DbFederationListEntry::list(&context)?))
+}
+
+/// Add a domain to the allowlist or blocklist. This takes effect immediately, without restart.
+#[debug_handler]
+pub(in crate::backend::api) async fn add_to_federation_list(
+    context: Data<IbisContext>,
+    Form(params): Form<AddFederationListParams>,
+) -> BackendResult<Json<DbFederationListEntry>> {
+    let domain = parse_domain(&params.domain)?;
+    if domain == context.config.federation.domain {
+        return Err(anyhow!("Invalid domain").into());
+    }
+    if params.purge && !params.blocked {
+        return Err(anyhow!("Only blocked domains can be purged").into());
+    }
+    let entry = DbFederationListEntry::add(&domain, params.blocked, &context)?;
+    if params.purge {
+        DbInstance::purge(&domain, &context)?;
+    }
+    Ok(Json(entry))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn remove_from_federation_list(
+    context: Data<IbisContext>,
+    Form(params): Form<RemoveFederationListParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbFederationListEntry::remove(&params.domain, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Normalize a domain entered by the admin, which must be a plain host with optional port.
+fn parse_domain(input: &str) -> BackendResult<String> {
+    let domain = input.trim().to_lowercase();
+    let url = Url::parse(&format!("http://{domain}")).map_err(|_| anyhow!("Invalid domain"))?;
+    let host = url.host_str().unwrap_or_default();
+    let parsed = match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+    // anything else like path, credentials or wildcard characters changes the parsed url
+    if host.is_empty() || parsed != domain || url.path() != "/" {
+        return Err(anyhow!("Invalid domain").into());
+    }
+    Ok(domain)
+}
@@ -2,90 +2,213 @@ use super::database::edit::ViewEditParams;
 use crate::{
     backend::{
         api::{
+            api_token::{create_api_token, delete_api_token, list_api_tokens},
             article::{
                 create_article,
+                delete_article,
                 edit_article,
                 fork_article,
                 get_article,
                 get_conflict,
                 list_articles,
+                move_article,
                 protect_article,
                 resolve_article,
+                revert_article,
                 search_article,
+                upload_image,
+                watch_article,
             },
-            comment::{create_comment, edit_comment},
+            comment::{create_comment, edit_comment, mark_as_read},
             instance::{follow_instance, get_instance, resolve_instance},
+            mod_log::{ban_user, list_mod_log},
+            oauth::{oauth_authorize, oauth_login},
+            report::{create_report, dismiss_report, resolve_report},
             user::{get_user, login_user, logout_user, register_user},
         },
         database::IbisContext,
-        utils::error::BackendResult,
+        utils::{error::BackendResult, media::MAX_IMAGE_SIZE},
     },
     common::{
         article::{DbEdit, EditView, GetEditList},
         instance::SiteView,
-        user::LocalUserView,
+        user::{LocalUserView, OAuthProvider},
     },
 };
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
 use article::{approve_article, delete_conflict};
 use axum::{
-    extract::Query,
-    routing::{delete, get, patch, post},
+    extract::{DefaultBodyLimit, Query},
+    middleware::from_fn_with_state,
+    routing::{get, patch, post},
     Extension,
     Json,
     Router,
 };
 use axum_macros::debug_handler;
-use comment::mark_as_read;
-use instance::{list_instance_views, list_instances, update_instance};
-use user::{count_notifications, list_notifications, update_user_profile};
+use instance::{
+    add_to_federation_list,
+    list_federation_list,
+    list_instance_views,
+    list_instances,
+    remove_from_federation_list,
+    update_instance,
+};
+use permission::{require_role, Role};
+use user::{
+    admin_delete_user,
+    admin_reset_password,
+    admin_rotate_jwt_secret,
+    approve_registration,
+    captcha_image,
+    change_password,
+    count_notifications,
+    delete_account,
+    delete_notification,
+    disable_totp,
+    enable_totp,
+    generate_totp,
+    get_captcha,
+    list_notifications,
+    list_sessions,
+    mark_all_notifications_as_read,
+    mark_notification_as_read,
+    request_password_reset,
+    reset_password,
+    revoke_session,
+    update_email,
+    update_user_profile,
+    upload_avatar,
+    upload_banner,
+    verify_email,
+};
 
+mod api_token;
 mod article;
 mod comment;
 mod instance;
+mod mod_log;
+mod oauth;
+mod permission;
+mod report;
 pub(super) mod user;
 
 pub fn api_routes() -> Router<()> {
     Router::new()
-        .route(
-            "/article",
-            get(get_article).post(create_article).patch(edit_article),
-        )
+        .merge(public_routes())
+        .merge(user_routes().route_layer(from_fn_with_state(Role::User, require_role)))
+        .merge(admin_routes().route_layer(from_fn_with_state(Role::Admin, require_role)))
+}
+
+/// Routes which can be used without login.
+fn public_routes() -> Router<()> {
+    Router::new()
+        .route("/article", get(get_article))
         .route("/article/list", get(list_articles))
-        .route("/article/fork", post(fork_article))
         .route("/article/resolve", get(resolve_article))
-        .route("/article/protect", post(protect_article))
-        .route("/article/approve", post(approve_article))
         .route("/edit/list", get(edit_list))
-        .route("/conflict", get(get_conflict))
-        .route("/conflict", delete(delete_conflict))
-        .route("/comment", post(create_comment))
-        .route("/comment", patch(edit_comment))
-        .route("/comment/mark_as_read", post(mark_as_read))
         .route("/instance", get(get_instance))
-        .route("/instance", patch(update_instance))
-        .route("/instance/follow", post(follow_instance))
         .route("/instance/resolve", get(resolve_instance))
         // TODO: deprecated, remove in 0.3
         .route("/instance/list", get(list_instances))
         .route("/instance/list_views", get(list_instance_views))
         .route("/search", get(search_article))
+        .route("/mod_log", get(list_mod_log))
         .route("/user", get(get_user))
-        .route("/user/notifications/list", get(list_notifications))
         .route("/user/notifications/count", get(count_notifications))
         .route("/account/register", post(register_user))
         .route("/account/login", post(login_user))
         .route("/account/logout", post(logout_user))
-        .route("/account/update", post(update_user_profile))
+        .route("/account/captcha", get(get_captcha))
+        .route("/account/oauth/authorize", get(oauth_authorize))
+        .route("/account/oauth/login", post(oauth_login))
+        .route("/account/captcha/:uuid", get(captcha_image))
+        .route("/account/verify_email", post(verify_email))
+        .route(
+            "/account/password_reset/request",
+            post(request_password_reset),
+        )
+        .route("/account/password_reset/confirm", post(reset_password))
         .route("/site", get(site_view))
 }
 
-pub fn check_is_admin(user: &LocalUserView) -> BackendResult<()> {
-    if !user.local_user.admin {
-        return Err(anyhow!("Only admin can perform this action").into());
-    }
-    Ok(())
+/// Routes which require login.
+fn user_routes() -> Router<()> {
+    Router::new()
+        .route("/article", post(create_article).patch(edit_article))
+        .route("/article/fork", post(fork_article))
+        .route("/article/move", post(move_article))
+        .route("/article/revert", post(revert_article))
+        .route("/article/watch", post(watch_article))
+        .route(
+            "/article/image",
+            post(upload_image).layer(DefaultBodyLimit::max(MAX_IMAGE_SIZE)),
+        )
+        .route("/conflict", get(get_conflict).delete(delete_conflict))
+        .route("/comment", post(create_comment).patch(edit_comment))
+        // TODO: deprecated, remove in 0.3
+        .route("/comment/mark_as_read", post(mark_as_read))
+        .route("/instance/follow", post(follow_instance))
+        .route("/report", post(create_report))
+        .route("/user/notifications/list", get(list_notifications))
+        .route(
+            "/user/notifications/mark_as_read",
+            post(mark_notification_as_read),
+        )
+        .route(
+            "/user/notifications/mark_all_as_read",
+            post(mark_all_notifications_as_read),
+        )
+        .route("/user/notifications/delete", post(delete_notification))
+        .route("/account/update", post(update_user_profile))
+        .route("/account/email", post(update_email))
+        .route("/account/change_password", post(change_password))
+        .route("/account/delete", post(delete_account))
+        .route("/account/session/list", get(list_sessions))
+        .route("/account/session/revoke", post(revoke_session))
+        .route("/account/totp/generate", post(generate_totp))
+        .route("/account/totp/enable", post(enable_totp))
+        .route("/account/totp/disable", post(disable_totp))
+        .route(
+            "/account/api_token",
+            get(list_api_tokens)
+                .post(create_api_token)
+                .delete(delete_api_token),
+        )
+        .route(
+            "/account/avatar",
+            post(upload_avatar).layer(DefaultBodyLimit::max(MAX_IMAGE_SIZE)),
+        )
+        .route(
+            "/account/banner",
+            post(upload_banner).layer(DefaultBodyLimit::max(MAX_IMAGE_SIZE)),
+        )
+}
+
+/// Routes which are only available to admins.
+fn admin_routes() -> Router<()> {
+    Router::new()
+        .route("/article/protect", post(protect_article))
+        .route("/article/approve", post(approve_article))
+        .route("/article/delete", post(delete_article))
+        .route("/instance", patch(update_instance))
+        .route(
+            "/instance/federation_list",
+            get(list_federation_list)
+                .post(add_to_federation_list)
+                .delete(remove_from_federation_list),
+        )
+        .route("/instance/jwt_secret/rotate", post(admin_rotate_jwt_secret))
+        .route("/user/reset_password", post(admin_reset_password))
+        .route("/user/delete", post(admin_delete_user))
+        .route("/user/ban", post(ban_user))
+        .route(
+            "/registration_application/approve",
+            post(approve_registration),
+        )
+        .route("/report/resolve", post(resolve_report))
+        .route("/report/dismiss", post(dismiss_report))
 }
 
 #[debug_handler]
@@ -96,6 +219,15 @@ pub(in crate::backend::api) async fn site_view(
     Ok(Json(SiteView {
         my_profile: user.map(|u| u.0),
         config: context.config.options.clone(),
+        oauth_providers: context
+            .config
+            .oauth_providers
+            .iter()
+            .map(|p| OAuthProvider {
+                name: p.name.clone(),
+                display_name: p.display_name.clone(),
+            })
+            .collect(),
     }))
 }
 
@@ -113,7 +245,12 @@ pub async fn edit_list(
     } else {
         return Err(anyhow!("Must provide article_id or person_id").into());
     };
-    Ok(Json(DbEdit::view(params, &user.map(|u| u.0), &context)?))
+    Ok(Json(DbEdit::view(
+        params,
+        &query,
+        &user.map(|u| u.0),
+        &context,
+    )?))
 }
 
 /// Trims the string param, and converts to None if it is empty
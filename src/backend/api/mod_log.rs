@@ -0,0 +1,54 @@
+use super::empty_to_none;
+use crate::{
+    backend::{
+        database::{mod_log::DbModLogForm, IbisContext},
+        utils::error::BackendResult,
+    },
+    common::{
+        mod_log::{BanUserParams, DbModLog, ListModLogParams, ModAction, ModLogView},
+        user::{DbPerson, LocalUserView},
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{extract::Query, Extension, Form, Json};
+use axum_macros::debug_handler;
+
+/// Ban or unban a local or remote user. Bans only apply to the local instance, they are not
+/// federated.
+#[debug_handler]
+pub(in crate::backend::api) async fn ban_user(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<BanUserParams>,
+) -> BackendResult<Json<DbPerson>> {
+    if params.person_id == user.person.id {
+        return Err(anyhow!("Cannot ban yourself").into());
+    }
+    empty_to_none(&mut params.reason);
+    let expires = params.expires.filter(|_| params.ban);
+    let person = DbPerson::update_ban(params.person_id, params.ban, expires, &context)?;
+    let form = DbModLogForm {
+        mod_id: user.person.id,
+        action: if params.ban {
+            ModAction::BanUser
+        } else {
+            ModAction::UnbanUser
+        },
+        target_person_id: Some(person.id),
+        target_article_id: None,
+        reason: params.reason,
+        expires,
+    };
+    DbModLog::create(form, &context)?;
+    Ok(Json(person))
+}
+
+/// List moderation actions taken by admins of this instance, newest first.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_mod_log(
+    Query(params): Query<ListModLogParams>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<ModLogView>>> {
+    Ok(Json(DbModLog::list(&params, &context)?))
+}
@@ -0,0 +1,138 @@
+use super::user::{create_cookie, generate_login_token};
+use crate::{
+    backend::{
+        database::{oauth_account::DbOAuthAccount, IbisContext},
+        utils::{
+            email::generate_token,
+            error::BackendResult,
+            oauth::{authorize_url, fetch_user_info, find_provider, generate_pkce},
+            validate::validate_user_name,
+        },
+    },
+    common::{
+        instance::RegistrationMode,
+        registration_application::DbRegistrationApplication,
+        user::{
+            DbPerson,
+            LocalUserView,
+            OAuthAuthorizeParams,
+            OAuthAuthorizeResponse,
+            OAuthLoginParams,
+        },
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{extract::Query, http::HeaderMap, Extension, Form, Json};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use axum_macros::debug_handler;
+use time::Duration;
+
+const OAUTH_STATE_COOKIE: &str = "oauth_state";
+
+/// Start login with an OpenID Connect provider. The state is stored in a cookie and compared
+/// after the redirect back, so that an attacker can't login the user to a different account.
+/// The PKCE verifier is stored in the same cookie.
+#[debug_handler]
+pub(in crate::backend::api) async fn oauth_authorize(
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    Query(params): Query<OAuthAuthorizeParams>,
+) -> BackendResult<(CookieJar, Json<OAuthAuthorizeResponse>)> {
+    let provider = find_provider(&params.provider, &context)?;
+    let state = generate_token();
+    let (pkce_verifier, pkce_challenge) = generate_pkce();
+    let url = authorize_url(provider, &state, &pkce_challenge, &context).await?;
+    let jar = jar.add(state_cookie(format!(
+        "{}:{state}:{pkce_verifier}",
+        provider.name
+    )));
+    Ok((
+        jar,
+        Json(OAuthAuthorizeResponse {
+            url: url.to_string(),
+        }),
+    ))
+}
+
+/// Finish login after the provider redirected back. If the user is already logged in, the
+/// provider account is linked to the current account. Otherwise login to the linked account, or
+/// create a new account with the username from the provider, depending on the registration
+/// mode. Users are authenticated by the provider, so captcha and two-factor authentication don't
+/// apply here.
+#[debug_handler]
+pub(in crate::backend::api) async fn oauth_login(
+    user: Option<Extension<LocalUserView>>,
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    headers: HeaderMap,
+    Form(params): Form<OAuthLoginParams>,
+) -> BackendResult<(CookieJar, Json<LocalUserView>)> {
+    let stored_state = jar
+        .get(OAUTH_STATE_COOKIE)
+        .map(|c| c.value().to_string())
+        .unwrap_or_default();
+    // each state can only be used once
+    let jar = jar.remove(state_cookie(String::new()));
+    let mut parts = stored_state.rsplitn(3, ':');
+    let (Some(pkce_verifier), Some(state), Some(provider)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow!("Invalid oauth state").into());
+    };
+    if state != params.state {
+        return Err(anyhow!("Invalid oauth state").into());
+    }
+    let provider = find_provider(provider, &context)?;
+    let info = fetch_user_info(provider, &params.code, pkce_verifier, &context).await?;
+    let account = DbOAuthAccount::read(&provider.name, &info.sub, &context)?;
+
+    if let Some(Extension(user)) = user {
+        if account.is_some() {
+            return Err(anyhow!("Provider account is already linked").into());
+        }
+        DbOAuthAccount::create(user.person.id, &provider.name, &info.sub, &context)?;
+        return Ok((jar, Json(user)));
+    }
+    let user = if let Some(account) = account {
+        DbPerson::read_local_from_id(account.person_id, &context)?
+    } else {
+        let registration_mode = context.config.options.registration_mode;
+        if registration_mode == RegistrationMode::Closed {
+            return Err(anyhow!("Registration is closed").into());
+        }
+        let Some(username) = info.preferred_username else {
+            return Err(anyhow!("Oauth provider did not return a username").into());
+        };
+        validate_user_name(&username)?;
+        if DbPerson::read_local_from_name(&username, &context).is_ok() {
+            return Err(anyhow!(
+                "Username is already taken, login with password to link the provider account"
+            )
+            .into());
+        }
+        // the password is unknown, it needs to be reset for login without the provider
+        let user = DbPerson::create_local(username, generate_token(), false, &context)?;
+        DbOAuthAccount::create(user.person.id, &provider.name, &info.sub, &context)?;
+        if registration_mode == RegistrationMode::RequireApplication {
+            // login is rejected below until the application is approved
+            let answer = format!("Registered with {}", provider.display_name);
+            DbRegistrationApplication::create(user.person.id, answer, &context)?;
+        }
+        user
+    };
+    DbRegistrationApplication::check_accepted(user.person.id, &context)?;
+    let token = generate_login_token(&user.person, &user.local_user, &headers, &context)?;
+    let jar = jar.add(create_cookie(token, &context));
+    Ok((jar, Json(user)))
+}
+
+fn state_cookie(value: String) -> Cookie<'static> {
+    Cookie::build((OAUTH_STATE_COOKIE, value))
+        .same_site(SameSite::Lax)
+        .path("/")
+        .http_only(true)
+        .secure(!cfg!(debug_assertions))
+        .max_age(Duration::minutes(10))
+        .build()
+}
@@ -0,0 +1,74 @@
+use crate::common::{
+    api_token::{ApiTokenScope, DbApiToken},
+    user::LocalUserView,
+};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_macros::debug_middleware;
+
+/// Permission which is required to call an api route.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Role {
+    /// Any logged in user
+    User,
+    /// Only admins of the local instance
+    Admin,
+}
+
+/// Rejects requests from users who don't have the required role for the route, and write
+/// requests from banned users. Relies on
+/// `auth_middleware` to set the `LocalUserView` extension for logged in users, and the
+/// `DbApiToken` extension for requests authenticated with an api token.
+#[debug_middleware]
+pub(super) async fn require_role(
+    State(role): State<Role>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(user) = request.extensions().get::<LocalUserView>() else {
+        // user is not set for read requests with an api token that is missing the read scope
+        if request.extensions().get::<DbApiToken>().is_some() {
+            return (StatusCode::FORBIDDEN, "Api token is missing required scope").into_response();
+        }
+        return (StatusCode::UNAUTHORIZED, "Login required").into_response();
+    };
+    if role == Role::Admin && !user.local_user.admin {
+        return (StatusCode::FORBIDDEN, "Only admin can perform this action").into_response();
+    }
+    // banned users can still delete their account
+    if user.person.is_banned()
+        && request.method() != Method::GET
+        && request.uri().path() != "/account/delete"
+    {
+        return (StatusCode::FORBIDDEN, "User is banned").into_response();
+    }
+    if let Some(api_token) = request.extensions().get::<DbApiToken>() {
+        let scope = required_scope(role, request.method(), request.uri().path());
+        if !scope.is_some_and(|s| api_token.has_scope(s)) {
+            return (StatusCode::FORBIDDEN, "Api token is missing required scope").into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Scope which an api token needs to call the route. Account settings including api tokens
+/// themselves can only be changed after login with password, so they return `None`.
+fn required_scope(role: Role, method: &Method, path: &str) -> Option<ApiTokenScope> {
+    use ApiTokenScope::*;
+    if role == Role::Admin {
+        Some(Admin)
+    } else if path.starts_with("/account/") {
+        None
+    } else if method == Method::GET {
+        Some(Read)
+    } else if path == "/comment" {
+        Some(Comment)
+    } else {
+        Some(Edit)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    backend::{
+        database::{mod_log::DbModLogForm, report::DbReportForm, IbisContext},
+        federation::activities::report::Report,
+        utils::{
+            error::BackendResult,
+            generate_activity_id,
+            validate::{validate_not_banned, validate_not_empty},
+        },
+    },
+    common::{
+        article::{DbArticle, DbEdit},
+        comment::DbComment,
+        mod_log::{DbModLog, ModAction},
+        report::{CreateReportParams, DbReport, ResolveReportParams},
+        user::LocalUserView,
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{Extension, Form, Json};
+use axum_macros::debug_handler;
+
+/// Report an article, edit or comment to admins. If the article is hosted on another instance,
+/// the report is also sent there.
+#[debug_handler]
+pub(in crate::backend::api) async fn create_report(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateReportParams>,
+) -> BackendResult<Json<DbReport>> {
+    validate_not_empty(&params.reason)?;
+    validate_not_banned(&user.person)?;
+    if params.edit_id.is_some() && params.comment_id.is_some() {
+        return Err(anyhow!("Cannot report edit and comment at the same time").into());
+    }
+    if let Some(edit_id) = params.edit_id {
+        if DbEdit::read_from_id(edit_id, &context)?.article_id != params.article_id {
+            return Err(anyhow!("Invalid article_id/edit_id combination").into());
+        }
+    }
+    if let Some(comment_id) = params.comment_id {
+        if DbComment::read(comment_id, &context)?.article_id != params.article_id {
+            return Err(anyhow!("Invalid article_id/comment_id combination").into());
+        }
+    }
+    let article = DbArticle::read(params.article_id, &context)?;
+    let form = DbReportForm {
+        creator_id: user.person.id,
+        article_id: article.id,
+        edit_id: params.edit_id,
+        comment_id: params.comment_id,
+        reason: params.reason,
+        ap_id: generate_activity_id(&context)?.to_string(),
+        local: true,
+    };
+    let report = DbReport::create(form, &context)?;
+    if !article.local {
+        Report::send(&report, &article, &user.person, &context).await?;
+    }
+    Ok(Json(report))
+}
+
+/// Mark a report as resolved, after action was taken against the reported content.
+#[debug_handler]
+pub(in crate::backend::api) async fn resolve_report(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<ResolveReportParams>,
+) -> BackendResult<Json<DbReport>> {
+    close_report(params, ModAction::ResolveReport, user, &context)
+}
+
+/// Close a report without taking any action.
+#[debug_handler]
+pub(in crate::backend::api) async fn dismiss_report(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<ResolveReportParams>,
+) -> BackendResult<Json<DbReport>> {
+    close_report(params, ModAction::DismissReport, user, &context)
+}
+
+fn close_report(
+    params: ResolveReportParams,
+    action: ModAction,
+    user: LocalUserView,
+    context: &IbisContext,
+) -> BackendResult<Json<DbReport>> {
+    if DbReport::read(params.report_id, context)?.resolved {
+        return Err(anyhow!("Report is already resolved").into());
+    }
+    let report = DbReport::resolve(params.report_id, context)?;
+    let form = DbModLogForm::article(user.person.id, action, report.article_id);
+    DbModLog::create(form, context)?;
+    Ok(Json(report))
+}
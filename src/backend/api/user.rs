@@ -1,29 +1,90 @@
 use super::empty_to_none;
 use crate::{
     backend::{
-        database::{read_jwt_secret, IbisContext},
+        database::{
+            captcha::DbCaptchaAnswer,
+            mod_log::DbModLogForm,
+            notifications::DbNotification,
+            read_jwt_secret,
+            rotate_jwt_secret,
+            IbisContext,
+        },
+        federation::activities::{delete_user::DeleteUser, update_user::UpdateUser},
         utils::{
+            captcha::{generate_captcha_answer, render_captcha},
+            email::{
+                generate_token,
+                password_reset_link,
+                send_password_reset_email,
+                send_verification_email,
+            },
             error::BackendResult,
-            validate::{validate_display_name, validate_user_name},
+            media::{store_image, ImageKind},
+            totp::{
+                build_totp,
+                check_totp_token,
+                generate_recovery_codes,
+                generate_totp_secret,
+                hash_recovery_code,
+            },
+            validate::{validate_display_name, validate_email, validate_user_name},
         },
     },
     common::{
+        api_token::DbApiToken,
+        instance::RegistrationMode,
+        mod_log::{DbModLog, ModAction},
+        newtypes::LoginSessionId,
+        registration_application::{ApproveRegistrationParams, DbRegistrationApplication},
         user::{
+            AdminDeleteUserParams,
+            AdminResetPasswordParams,
+            AdminResetPasswordResponse,
+            CaptchaResponse,
+            ChangePasswordParams,
+            DbLocalUser,
+            DbLoginSession,
             DbPerson,
+            DeleteAccountParams,
+            DisableTotpParams,
+            EnableTotpParams,
+            EnableTotpResponse,
+            GenerateTotpSecretResponse,
             GetUserParams,
             LocalUserView,
+            LoginSessionView,
             LoginUserParams,
             RegisterUserParams,
+            RequestPasswordResetParams,
+            ResetPasswordParams,
+            RevokeSessionParams,
+            UpdateEmailParams,
             UpdateUserParams,
+            VerifyEmailParams,
+            TOTP_REQUIRED,
         },
-        Notification,
+        ApiNotification,
+        DeleteNotificationParams,
+        ListNotificationsParams,
+        MarkNotificationAsReadParams,
         SuccessResponse,
         AUTH_COOKIE,
     },
 };
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
-use axum::{extract::Query, Extension, Form, Json};
+use axum::{
+    body::Bytes,
+    extract::{Path, Query},
+    http::{
+        header::{CONTENT_TYPE, USER_AGENT},
+        HeaderMap,
+        HeaderName,
+    },
+    Extension,
+    Form,
+    Json,
+};
 use axum_extra::extract::cookie::{Cookie, CookieJar, Expiration, SameSite};
 use axum_macros::debug_handler;
 use bcrypt::verify;
@@ -37,8 +98,10 @@ use jsonwebtoken::{
     Header,
     Validation,
 };
+use log::error;
 use serde::{Deserialize, Serialize};
 use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
@@ -50,15 +113,36 @@ struct Claims {
     pub iat: i64,
     /// Expiration time
     pub exp: u64,
+    /// Time of the last password change in milliseconds, so that changing the password
+    /// invalidates all tokens issued before
+    #[serde(default)]
+    pub pwd: Option<i64>,
+    /// Id of the login session, so that the token can be revoked. Tokens without session are
+    /// rejected.
+    #[serde(default)]
+    pub sid: Option<LoginSessionId>,
 }
 
-fn generate_login_token(person: &DbPerson, context: &Data<IbisContext>) -> BackendResult<String> {
+/// Create a new login session and a token for it.
+pub(super) fn generate_login_token(
+    person: &DbPerson,
+    local_user: &DbLocalUser,
+    headers: &HeaderMap,
+    context: &Data<IbisContext>,
+) -> BackendResult<String> {
+    let user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.to_string());
+    let session = DbLoginSession::create(person.id, user_agent, context)?;
     let hostname = context.domain().to_string();
     let claims = Claims {
         sub: person.username.clone(),
         iss: hostname,
         iat: Utc::now().timestamp(),
         exp: get_current_timestamp() + 60 * 60 * 24 * 365,
+        pwd: local_user.password_changed.map(|p| p.timestamp_millis()),
+        sid: Some(session.id),
     };
 
     let secret = read_jwt_secret(context)?;
@@ -67,26 +151,67 @@ fn generate_login_token(person: &DbPerson, context: &Data<IbisContext>) -> Backe
     Ok(jwt)
 }
 
-pub async fn validate(jwt: &str, context: &IbisContext) -> BackendResult<LocalUserView> {
+pub async fn validate(
+    jwt: &str,
+    context: &IbisContext,
+) -> BackendResult<(LocalUserView, LoginSessionId)> {
     let validation = Validation::default();
     let secret = read_jwt_secret(context)?;
     let key = DecodingKey::from_secret(secret.as_bytes());
     let claims = decode::<Claims>(jwt, &key, &validation)?;
-    DbPerson::read_local_from_name(&claims.claims.sub, context)
+    let user = DbPerson::read_local_from_name(&claims.claims.sub, context)?;
+    let password_changed = user.local_user.password_changed;
+    if claims.claims.pwd != password_changed.map(|p| p.timestamp_millis()) {
+        return Err(anyhow!("Login token is invalid because password was changed").into());
+    }
+    let Some(session_id) = claims.claims.sid else {
+        return Err(anyhow!("Login token has no session, login again").into());
+    };
+    DbLoginSession::validate(session_id, user.person.id, context)?;
+    Ok((user, session_id))
+}
+
+/// Authenticate with a personal api token. The caller needs to check that the token has the
+/// required scope.
+pub async fn validate_api_token(
+    token: &str,
+    context: &IbisContext,
+) -> BackendResult<(LocalUserView, DbApiToken)> {
+    let api_token = DbApiToken::read_for_auth(token, context)?;
+    let user = DbPerson::read_local_from_id(api_token.person_id, context)?;
+    Ok((user, api_token))
 }
 
 #[debug_handler]
 pub(in crate::backend::api) async fn register_user(
     context: Data<IbisContext>,
     jar: CookieJar,
+    headers: HeaderMap,
     Form(params): Form<RegisterUserParams>,
 ) -> BackendResult<(CookieJar, Json<LocalUserView>)> {
-    if !context.config.options.registration_open {
+    let options = &context.config.options;
+    if options.registration_mode == RegistrationMode::Closed {
         return Err(anyhow!("Registration is closed").into());
     }
+    if options.registration_captcha {
+        let (Some(uuid), Some(answer)) = (params.captcha_uuid, &params.captcha_answer) else {
+            return Err(anyhow!("Captcha is required").into());
+        };
+        DbCaptchaAnswer::check(uuid, answer, &context)?;
+    }
     validate_user_name(&params.username)?;
+    if options.registration_mode == RegistrationMode::RequireApplication {
+        let answer = params.application_answer.filter(|a| !a.trim().is_empty());
+        let Some(answer) = answer else {
+            return Err(anyhow!("Registration application is required").into());
+        };
+        let user = DbPerson::create_local(params.username, params.password, false, &context)?;
+        DbRegistrationApplication::create(user.person.id, answer, &context)?;
+        // dont login until the application is approved
+        return Ok((jar, Json(user)));
+    }
     let user = DbPerson::create_local(params.username, params.password, false, &context)?;
-    let token = generate_login_token(&user.person, &context)?;
+    let token = generate_login_token(&user.person, &user.local_user, &headers, &context)?;
     let jar = jar.add(create_cookie(token, &context));
     Ok((jar, Json(user)))
 }
@@ -95,6 +220,7 @@ pub(in crate::backend::api) async fn register_user(
 pub(in crate::backend::api) async fn login_user(
     context: Data<IbisContext>,
     jar: CookieJar,
+    headers: HeaderMap,
     Form(params): Form<LoginUserParams>,
 ) -> BackendResult<(CookieJar, Json<LocalUserView>)> {
     let user = DbPerson::read_local_from_name(&params.username, &context)?;
@@ -102,12 +228,24 @@ pub(in crate::backend::api) async fn login_user(
     if !valid {
         return Err(anyhow!("Invalid login").into());
     }
-    let token = generate_login_token(&user.person, &context)?;
+    DbRegistrationApplication::check_accepted(user.person.id, &context)?;
+    if user.local_user.totp_enabled {
+        let Some(totp_token) = &params.totp_token else {
+            return Err(anyhow!(TOTP_REQUIRED).into());
+        };
+        check_totp_token(
+            &user.local_user,
+            &user.person.username,
+            totp_token,
+            &context,
+        )?;
+    }
+    let token = generate_login_token(&user.person, &user.local_user, &headers, &context)?;
     let jar = jar.add(create_cookie(token, &context));
     Ok((jar, Json(user)))
 }
 
-fn create_cookie(jwt: String, context: &Data<IbisContext>) -> Cookie<'static> {
+pub(super) fn create_cookie(jwt: String, context: &Data<IbisContext>) -> Cookie<'static> {
     let mut cookie = Cookie::build((AUTH_COOKIE, jwt));
 
     // Must not set cookie domain on localhost
@@ -129,13 +267,268 @@ fn create_cookie(jwt: String, context: &Data<IbisContext>) -> Cookie<'static> {
 
 #[debug_handler]
 pub(in crate::backend::api) async fn logout_user(
+    user: Option<Extension<LocalUserView>>,
+    session_id: Option<Extension<LoginSessionId>>,
     context: Data<IbisContext>,
     jar: CookieJar,
 ) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    // revoke the session so that the token can't be used anymore, even if it was copied
+    if let (Some(user), Some(session_id)) = (user, session_id) {
+        DbLoginSession::delete(session_id.0, user.person.id, &context)?;
+    }
     let jar = jar.remove(create_cookie(String::new(), &context));
     Ok((jar, Json(SuccessResponse::default())))
 }
 
+/// List all sessions where the user is logged in.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_sessions(
+    Extension(user): Extension<LocalUserView>,
+    session_id: Option<Extension<LoginSessionId>>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<Vec<LoginSessionView>>> {
+    let current = session_id.map(|s| s.0);
+    let sessions = DbLoginSession::list(user.person.id, &context)?
+        .into_iter()
+        .map(|session| LoginSessionView {
+            current: Some(session.id) == current,
+            session,
+        })
+        .collect();
+    Ok(Json(sessions))
+}
+
+/// Logout a session, eg on a device which was lost.
+#[debug_handler]
+pub(in crate::backend::api) async fn revoke_session(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<RevokeSessionParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbLoginSession::delete(params.id, user.person.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Generate a new secret for signing login tokens. This logs out all users including the
+/// admin who is calling it.
+#[debug_handler]
+pub(in crate::backend::api) async fn admin_rotate_jwt_secret(
+    context: Data<IbisContext>,
+    jar: CookieJar,
+) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    rotate_jwt_secret(&context)?;
+    let jar = jar.remove(create_cookie(String::new(), &context));
+    Ok((jar, Json(SuccessResponse::default())))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn change_password(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    headers: HeaderMap,
+    Form(params): Form<ChangePasswordParams>,
+) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    let valid = verify(&params.old_password, &user.local_user.password_encrypted)?;
+    if !valid {
+        return Err(anyhow!("Invalid old password").into());
+    }
+    let local_user =
+        DbLocalUser::update_password(user.local_user.id.0, &params.new_password, &context)?;
+    // Existing login tokens are invalid now, so the current session needs a new one
+    let token = generate_login_token(&user.person, &local_user, &headers, &context)?;
+    let jar = jar.add(create_cookie(token, &context));
+    Ok((jar, Json(SuccessResponse::default())))
+}
+
+/// Generate a new totp secret for the user. Two-factor authentication is only enabled after
+/// confirming the secret with a valid token.
+#[debug_handler]
+pub(in crate::backend::api) async fn generate_totp(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<GenerateTotpSecretResponse>> {
+    if user.local_user.totp_enabled {
+        return Err(anyhow!("Two-factor authentication is already enabled").into());
+    }
+    let secret = generate_totp_secret();
+    let totp_url = build_totp(&secret, &user.person.username, &context)?.get_url();
+    DbLocalUser::update_totp(
+        user.local_user.id.0,
+        Some(secret.clone()),
+        false,
+        vec![],
+        &context,
+    )?;
+    Ok(Json(GenerateTotpSecretResponse { secret, totp_url }))
+}
+
+/// Enable two-factor authentication, and return recovery codes which can be used in place of a
+/// totp token if the authenticator app is lost. The codes are only stored as hashes, so they can
+/// not be retrieved later.
+#[debug_handler]
+pub(in crate::backend::api) async fn enable_totp(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<EnableTotpParams>,
+) -> BackendResult<Json<EnableTotpResponse>> {
+    if user.local_user.totp_enabled {
+        return Err(anyhow!("Two-factor authentication is already enabled").into());
+    }
+    let Some(secret) = user.local_user.totp_secret else {
+        return Err(anyhow!("Totp secret needs to be generated first").into());
+    };
+    let totp = build_totp(&secret, &user.person.username, &context)?;
+    if !totp.check_current(params.totp_token.trim())? {
+        return Err(anyhow!("Invalid two-factor authentication token").into());
+    }
+    let recovery_codes = generate_recovery_codes();
+    let hashes = recovery_codes
+        .iter()
+        .map(|c| hash_recovery_code(c))
+        .collect();
+    DbLocalUser::update_totp(user.local_user.id.0, Some(secret), true, hashes, &context)?;
+    Ok(Json(EnableTotpResponse { recovery_codes }))
+}
+
+/// Disable two-factor authentication, after confirming the password and the second factor.
+#[debug_handler]
+pub(in crate::backend::api) async fn disable_totp(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<DisableTotpParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    let valid = verify(&params.password, &user.local_user.password_encrypted)?;
+    if !valid {
+        return Err(anyhow!("Invalid password").into());
+    }
+    check_totp_token(
+        &user.local_user,
+        &user.person.username,
+        &params.totp_token,
+        &context,
+    )?;
+    DbLocalUser::update_totp(user.local_user.id.0, None, false, vec![], &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Reset the password of another user, and return a link which the admin can pass on for setting
+/// a new one. The old password stops working immediately and the user is logged out everywhere.
+#[debug_handler]
+pub(in crate::backend::api) async fn admin_reset_password(
+    context: Data<IbisContext>,
+    Form(params): Form<AdminResetPasswordParams>,
+) -> BackendResult<Json<AdminResetPasswordResponse>> {
+    let local_user = DbLocalUser::read_from_person(params.person_id, &context)?;
+    DbLocalUser::update_password(local_user.id.0, &generate_token(), &context)?;
+    let reset_link = password_reset_link(&local_user, &context)?;
+    Ok(Json(AdminResetPasswordResponse { reset_link }))
+}
+
+/// Delete the account of the current user, after confirming the password. Edits and comments are
+/// kept, but attributed to the ghost user.
+#[debug_handler]
+pub(in crate::backend::api) async fn delete_account(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    Form(params): Form<DeleteAccountParams>,
+) -> BackendResult<(CookieJar, Json<SuccessResponse>)> {
+    let valid = verify(&params.password, &user.local_user.password_encrypted)?;
+    if !valid {
+        return Err(anyhow!("Invalid password").into());
+    }
+    DeleteUser::send(&user.person, &context).await?;
+    DbPerson::delete(user.person.id, &context)?;
+    let jar = jar.remove(create_cookie(String::new(), &context));
+    Ok((jar, Json(SuccessResponse::default())))
+}
+
+/// Delete the account of a local user. Admin accounts can't be deleted this way, so that there is
+/// always at least one admin left.
+#[debug_handler]
+pub(in crate::backend::api) async fn admin_delete_user(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<AdminDeleteUserParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    // ensure that this is a local user
+    let local_user = DbLocalUser::read_from_person(params.person_id, &context)?;
+    if local_user.admin {
+        return Err(anyhow!("Admin accounts can't be deleted").into());
+    }
+    let person = DbPerson::read(params.person_id, &context)?;
+    DeleteUser::send(&person, &context).await?;
+    let form = DbModLogForm {
+        mod_id: user.person.id,
+        action: ModAction::DeleteUser,
+        target_person_id: Some(person.id),
+        target_article_id: None,
+        reason: None,
+        expires: None,
+    };
+    DbModLog::create(form, &context)?;
+    DbPerson::delete(person.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Accept or deny the registration application of a new user.
+#[debug_handler]
+pub(in crate::backend::api) async fn approve_registration(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<ApproveRegistrationParams>,
+) -> BackendResult<Json<DbRegistrationApplication>> {
+    if DbRegistrationApplication::read(params.application_id, &context)?
+        .admin_id
+        .is_some()
+    {
+        return Err(anyhow!("Registration application was already handled").into());
+    }
+    empty_to_none(&mut params.deny_reason);
+    let deny_reason = params.deny_reason.filter(|_| !params.approve);
+    let application = DbRegistrationApplication::update(
+        params.application_id,
+        params.approve,
+        user.person.id,
+        deny_reason.clone(),
+        &context,
+    )?;
+    let form = DbModLogForm {
+        mod_id: user.person.id,
+        action: if params.approve {
+            ModAction::ApproveRegistration
+        } else {
+            ModAction::DenyRegistration
+        },
+        target_person_id: Some(application.person_id),
+        target_article_id: None,
+        reason: deny_reason,
+        expires: None,
+    };
+    DbModLog::create(form, &context)?;
+    Ok(Json(application))
+}
+
+/// Generate a new captcha which needs to be solved for registration.
+#[debug_handler]
+pub(in crate::backend::api) async fn get_captcha(
+    context: Data<IbisContext>,
+) -> BackendResult<Json<CaptchaResponse>> {
+    let captcha = DbCaptchaAnswer::create(&generate_captcha_answer(), &context)?;
+    Ok(Json(CaptchaResponse { uuid: captcha.uuid }))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn captcha_image(
+    Path(uuid): Path<Uuid>,
+    context: Data<IbisContext>,
+) -> BackendResult<([(HeaderName, &'static str); 1], Vec<u8>)> {
+    let captcha = DbCaptchaAnswer::read(uuid, &context)?;
+    let image = render_captcha(&captcha.answer)?;
+    Ok(([(CONTENT_TYPE, "image/png")], image))
+}
+
 #[debug_handler]
 pub(in crate::backend::api) async fn get_user(
     params: Query<GetUserParams>,
@@ -150,22 +543,114 @@ pub(in crate::backend::api) async fn get_user(
 
 #[debug_handler]
 pub(in crate::backend::api) async fn update_user_profile(
+    Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
     Form(mut params): Form<UpdateUserParams>,
 ) -> BackendResult<Json<SuccessResponse>> {
     empty_to_none(&mut params.display_name);
     empty_to_none(&mut params.bio);
     validate_display_name(&params.display_name)?;
-    DbPerson::update_profile(&params, &context)?;
+    let person = DbPerson::update_profile(user.person.id, &params, &context)?;
+    UpdateUser::send(person, &context).await?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Upload a new profile picture. The request body contains the image file.
+#[debug_handler]
+pub(in crate::backend::api) async fn upload_avatar(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    body: Bytes,
+) -> BackendResult<Json<DbPerson>> {
+    upload_image(user, ImageKind::Avatar, body, context).await
+}
+
+/// Upload a new banner for the profile page. The request body contains the image file.
+#[debug_handler]
+pub(in crate::backend::api) async fn upload_banner(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    body: Bytes,
+) -> BackendResult<Json<DbPerson>> {
+    upload_image(user, ImageKind::Banner, body, context).await
+}
+
+async fn upload_image(
+    user: LocalUserView,
+    kind: ImageKind,
+    body: Bytes,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<DbPerson>> {
+    let url = store_image(body.to_vec(), kind, &context).await?;
+    let person = DbPerson::update_image(user.person.id, kind, url.to_string(), &context)?;
+    UpdateUser::send(person.clone(), &context).await?;
+    Ok(Json(person))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn update_email(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<UpdateEmailParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    empty_to_none(&mut params.email);
+    if let Some(email) = &params.email {
+        validate_email(email)?;
+    }
+    DbLocalUser::update_email(&user.local_user, &params, &context)?;
+    if let Some(email) = &params.email {
+        if params.email != user.local_user.email {
+            send_verification_email(&user.local_user, email, &context).await?;
+        }
+    }
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn verify_email(
+    context: Data<IbisContext>,
+    Form(params): Form<VerifyEmailParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbLocalUser::verify_email(&params.token, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn request_password_reset(
+    context: Data<IbisContext>,
+    Form(params): Form<RequestPasswordResetParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    // Always return success, so that it is impossible to find out which email addresses are
+    // registered
+    if let Some(local_user) = DbLocalUser::read_from_email(&params.email, &context)? {
+        // send in background so that response time doesn't depend on the email being registered,
+        // and dont return errors from sending which would only happen for registered emails
+        let context = context.reset_request_count();
+        tokio::spawn(async move {
+            if let Err(e) = send_password_reset_email(&local_user, &params.email, &context).await {
+                error!("Failed to send password reset email: {e}");
+            }
+        });
+    }
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn reset_password(
+    context: Data<IbisContext>,
+    Form(params): Form<ResetPasswordParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbLocalUser::reset_password(&params.token, &params.new_password, &context)?;
     Ok(Json(SuccessResponse::default()))
 }
 
 #[debug_handler]
 pub(crate) async fn list_notifications(
+    Query(params): Query<ListNotificationsParams>,
     Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
-) -> BackendResult<Json<Vec<Notification>>> {
-    Ok(Json(Notification::list(&user, &context).await?))
+) -> BackendResult<Json<Vec<ApiNotification>>> {
+    Ok(Json(DbNotification::list(&user, &params, &context)?))
 }
 
 #[debug_handler]
@@ -174,8 +659,37 @@ pub(crate) async fn count_notifications(
     context: Data<IbisContext>,
 ) -> BackendResult<Json<i64>> {
     if let Some(user) = user {
-        Ok(Json(Notification::count(&user, &context)?))
+        Ok(Json(DbNotification::count(&user, &context)?))
     } else {
         Ok(Json(0))
     }
 }
+
+#[debug_handler]
+pub(crate) async fn mark_notification_as_read(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<MarkNotificationAsReadParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbNotification::mark_as_read(params.id, user.person.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(crate) async fn mark_all_notifications_as_read(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbNotification::mark_all_as_read(user.person.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(crate) async fn delete_notification(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteNotificationParams>,
+) -> BackendResult<Json<SuccessResponse>> {
+    DbNotification::delete(params.id, user.person.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
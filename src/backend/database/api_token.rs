@@ -0,0 +1,75 @@
+use super::{schema::api_token, IbisContext};
+use crate::{
+    backend::utils::{email::generate_token, error::BackendResult},
+    common::{
+        api_token::{CreateApiTokenParams, DbApiToken, API_TOKEN_PREFIX},
+        newtypes::{ApiTokenId, PersonId},
+    },
+};
+use anyhow::anyhow;
+use chrono::Utc;
+use diesel::{delete, insert_into, update, ExpressionMethods, QueryDsl, RunQueryDsl};
+use sha2::{Digest, Sha256};
+use std::ops::DerefMut;
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token))
+}
+
+impl DbApiToken {
+    /// Generate a new token, returns the db row and the plaintext token which is only shown
+    /// to the user once.
+    pub fn create(
+        person_id: PersonId,
+        params: CreateApiTokenParams,
+        context: &IbisContext,
+    ) -> BackendResult<(Self, String)> {
+        let mut conn = context.db_pool.get()?;
+        let token = format!("{API_TOKEN_PREFIX}{}", generate_token());
+        let api_token = insert_into(api_token::table)
+            .values((
+                api_token::person_id.eq(person_id),
+                api_token::name.eq(params.name),
+                api_token::token_hash.eq(hash_token(&token)),
+                api_token::scope_read.eq(params.scope_read),
+                api_token::scope_edit.eq(params.scope_edit),
+                api_token::scope_comment.eq(params.scope_comment),
+                api_token::scope_admin.eq(params.scope_admin),
+            ))
+            .get_result(conn.deref_mut())?;
+        Ok((api_token, token))
+    }
+
+    pub fn list(person_id: PersonId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(api_token::table
+            .filter(api_token::person_id.eq(person_id))
+            .order_by(api_token::published.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Revoke the token, can only be done by its owner.
+    pub fn delete(id: ApiTokenId, person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let deleted = delete(
+            api_token::table
+                .filter(api_token::id.eq(id))
+                .filter(api_token::person_id.eq(person_id)),
+        )
+        .execute(conn.deref_mut())?;
+        if deleted == 0 {
+            return Err(anyhow!("Api token not found").into());
+        }
+        Ok(())
+    }
+
+    /// Find the token for authentication, and update the time when it was last used.
+    pub fn read_for_auth(token: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(
+            update(api_token::table.filter(api_token::token_hash.eq(hash_token(token))))
+                .set(api_token::last_used.eq(Utc::now()))
+                .get_result(conn.deref_mut())?,
+        )
+    }
+}
@@ -1,33 +1,63 @@
 use crate::{
     backend::{
         database::{
-            schema::{article, edit, instance},
+            notifications::DbNotification,
+            page_limit,
+            schema::{article, article_redirect, article_watch, edit, instance},
             IbisContext,
         },
         federation::objects::edits_collection::DbEditCollection,
         utils::error::BackendResult,
     },
     common::{
-        article::{DbArticle, DbArticleView, EditVersion},
+        article::{
+            ArticleSort,
+            DbArticle,
+            DbArticleView,
+            EditVersion,
+            ListArticlesParams,
+            SearchArticleParams,
+            SearchArticleResult,
+        },
         comment::DbComment,
         instance::DbInstance,
-        newtypes::{ArticleId, InstanceId},
+        newtypes::{ArticleId, InstanceId, PersonId},
+        utils::http_protocol_str,
     },
 };
 use activitypub_federation::fetch::{collection_id::CollectionId, object_id::ObjectId};
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
 use diesel::{
-    dsl::max,
+    delete,
+    dsl::{exists, max, sql},
     insert_into,
+    select,
+    sql_types::{Bool, Float, Text},
     AsChangeset,
     BoolExpressionMethods,
+    Connection,
     ExpressionMethods,
     Insertable,
-    PgTextExpressionMethods,
+    OptionalExtension,
+    PgConnection,
     QueryDsl,
     RunQueryDsl,
 };
 use std::ops::DerefMut;
 
+/// Weighted search vector for an article, with matches in title ranked higher than in text. Must be
+/// identical to the expression used in the `article_search` index.
+const SEARCH_VECTOR: &str = "(setweight(to_tsvector('english', replace(article.title, '_', ' ')), \
+     'A') || setweight(to_tsvector('english', article.text), 'B'))";
+
+/// Matches in the search snippet are surrounded by these control characters, so that they can
+/// be replaced with HTML tags after escaping the article text.
+const SNIPPET_START: char = '\u{2}';
+const SNIPPET_STOP: char = '\u{3}';
+const SNIPPET_OPTIONS: &str =
+    "StartSel=\u{2}, StopSel=\u{3}, MaxFragments=2, MaxWords=30, MinWords=10";
+
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = article, check_for_backend(diesel::pg::Pg))]
 pub struct DbArticleForm {
@@ -48,9 +78,47 @@ impl DbArticle {
 
     pub fn create(form: DbArticleForm, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(insert_into(article::table)
+        let article: Self = insert_into(article::table)
             .values(form)
-            .get_result(conn.deref_mut())?)
+            .get_result(conn.deref_mut())?;
+        if !article.approved {
+            DbNotification::notify_approval_required(&article, context)?;
+        }
+        Ok(article)
+    }
+
+    /// Generate the ap_id for a new local article from its title. Moved articles keep the ap_id
+    /// with their original title, so if that is already taken a numeric suffix is added.
+    pub fn generate_ap_id(title: &str, context: &IbisContext) -> BackendResult<ObjectId<Self>> {
+        let mut conn = context.db_pool.get()?;
+        let base = format!(
+            "{}://{}/article/{title}",
+            http_protocol_str(),
+            context.config.federation.domain
+        );
+        let mut ap_id = ObjectId::parse(&base)?;
+        let mut suffix = 1;
+        while select(exists(
+            article::table.filter(article::dsl::ap_id.eq(&ap_id)),
+        ))
+        .get_result::<bool>(conn.deref_mut())?
+        {
+            suffix += 1;
+            ap_id = ObjectId::parse(&format!("{base}_{suffix}"))?;
+        }
+        Ok(ap_id)
+    }
+
+    /// Check if a local article currently has the given title. Unlike
+    /// [read_view_title](Self::read_view_title) this doesn't follow redirects.
+    pub fn local_title_exists(title: &str, context: &IbisContext) -> BackendResult<bool> {
+        let mut conn = context.db_pool.get()?;
+        Ok(select(exists(
+            article::table
+                .filter(article::dsl::title.eq(title))
+                .filter(article::dsl::local.eq(true)),
+        ))
+        .get_result(conn.deref_mut())?)
     }
 
     pub fn create_or_update(form: DbArticleForm, context: &IbisContext) -> BackendResult<Self> {
@@ -70,6 +138,35 @@ impl DbArticle {
             .get_result::<Self>(conn.deref_mut())?)
     }
 
+    /// Rename an article and keep the old title as redirect, both in a single transaction so that
+    /// links to the old title can't break.
+    pub fn update_title(
+        article: &DbArticle,
+        new_title: &str,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        conn.deref_mut().transaction(|conn| {
+            let updated = diesel::update(article::dsl::article.find(article.id))
+                .set(article::dsl::title.eq(new_title))
+                .get_result::<Self>(conn)?;
+            insert_redirect(article.id, &article.title, new_title, conn)?;
+            Ok(updated)
+        })
+    }
+
+    /// Remember the previous title of a renamed article, so that links to the old title keep
+    /// working.
+    pub fn create_redirect(
+        id: ArticleId,
+        old_title: &str,
+        new_title: &str,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_redirect(id, old_title, new_title, conn.deref_mut())
+    }
+
     pub fn update_protected(
         id: ArticleId,
         locked: bool,
@@ -87,8 +184,23 @@ impl DbArticle {
         context: &IbisContext,
     ) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(diesel::update(article::dsl::article.find(id))
+        let article = diesel::update(article::dsl::article.find(id))
             .set(article::dsl::approved.eq(approved))
+            .get_result::<Self>(conn.deref_mut())?;
+        if article.approved {
+            DbNotification::delete_approval_required(article.id, context)?;
+        }
+        Ok(article)
+    }
+
+    pub fn update_deleted(
+        id: ArticleId,
+        deleted: bool,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(article::dsl::article.find(id))
+            .set(article::dsl::deleted.eq(deleted))
             .get_result::<Self>(conn.deref_mut())?)
     }
 
@@ -118,6 +230,7 @@ impl DbArticle {
             instance,
             comments,
             latest_version,
+            watched: false,
         })
     }
 
@@ -132,12 +245,33 @@ impl DbArticle {
                 .inner_join(instance::table)
                 .filter(article::dsl::title.eq(title))
                 .into_boxed();
-            let query = if let Some(domain) = domain {
+            let query = if let Some(domain) = &domain {
                 query.filter(instance::dsl::domain.eq(domain))
             } else {
                 query.filter(article::dsl::local.eq(true))
             };
-            query.get_result(conn.deref_mut())?
+            match query.get_result(conn.deref_mut()).optional()? {
+                Some(res) => res,
+                None => {
+                    // the article may have been renamed, so follow redirect from old title. if
+                    // the title was used by multiple articles, the newest redirect wins.
+                    let query = article_redirect::table
+                        .inner_join(article::table.inner_join(instance::table))
+                        .filter(article_redirect::title.eq(title))
+                        .select((article::all_columns, instance::all_columns))
+                        .order_by((
+                            article_redirect::published.desc(),
+                            article_redirect::id.desc(),
+                        ))
+                        .into_boxed();
+                    let query = if let Some(domain) = domain {
+                        query.filter(instance::dsl::domain.eq(domain))
+                    } else {
+                        query.filter(article::dsl::local.eq(true))
+                    };
+                    query.first(conn.deref_mut())?
+                }
+            }
         };
         let comments = DbComment::read_for_article(article.id, context)?;
         let latest_version = article.latest_edit_version(context)?;
@@ -146,9 +280,52 @@ impl DbArticle {
             instance,
             comments,
             latest_version,
+            watched: false,
         })
     }
 
+    /// Start or stop watching an article, to get notified about new edits.
+    pub fn watch(
+        id: ArticleId,
+        person_id: PersonId,
+        watch: bool,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        if watch {
+            insert_into(article_watch::table)
+                .values((
+                    article_watch::article_id.eq(id),
+                    article_watch::person_id.eq(person_id),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn.deref_mut())?;
+        } else {
+            delete(
+                article_watch::table
+                    .filter(article_watch::article_id.eq(id))
+                    .filter(article_watch::person_id.eq(person_id)),
+            )
+            .execute(conn.deref_mut())?;
+            DbNotification::delete_article_edited(id, person_id, context)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_watched(
+        id: ArticleId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<bool> {
+        let mut conn = context.db_pool.get()?;
+        Ok(select(exists(
+            article_watch::table
+                .filter(article_watch::article_id.eq(id))
+                .filter(article_watch::person_id.eq(person_id)),
+        ))
+        .get_result(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(
         ap_id: &ObjectId<DbArticle>,
         context: &IbisContext,
@@ -159,47 +336,143 @@ impl DbArticle {
             .get_result(conn.deref_mut())?)
     }
 
-    /// Read all articles, ordered by most recently edited first.
+    /// Read a page of articles which are not deleted, in the given sort order.
     ///
     /// TODO: Should get rid of only_local param and rely on instance_id
     pub fn read_all(
-        only_local: Option<bool>,
-        instance_id: Option<InstanceId>,
+        params: &ListArticlesParams,
         context: &IbisContext,
     ) -> BackendResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
+        let last_edited = max(edit::dsl::published);
         let mut query = article::table
             .inner_join(edit::table)
             .inner_join(instance::table)
             .filter(article::dsl::approved.eq(true))
+            .filter(article::dsl::deleted.eq(false))
             .group_by(article::dsl::id)
-            .order_by(max(edit::dsl::published).desc())
             .select(article::all_columns)
+            .limit(page_limit(params.limit))
             .into_boxed();
 
-        if let Some(true) = only_local {
+        if let Some(true) = params.only_local {
             query = query.filter(article::dsl::local.eq(true));
         }
-        if let Some(instance_id) = instance_id {
+        if let Some(instance_id) = params.instance_id {
             query = query.filter(instance::dsl::id.eq(instance_id));
         }
+        // Keyset pagination, continue after the cursor article in the same sort order
+        let cursor = params
+            .cursor
+            .map(|id| DbArticle::read(id, context))
+            .transpose()?;
+        match params.sort.unwrap_or_default() {
+            ArticleSort::RecentlyEdited => {
+                query = query.order_by((last_edited.desc(), article::dsl::id.desc()));
+                if let Some(cursor) = cursor {
+                    let cursor_edited = edit::table
+                        .filter(edit::dsl::article_id.eq(cursor.id))
+                        .select(max(edit::dsl::published))
+                        .get_result::<Option<DateTime<Utc>>>(conn.deref_mut())?
+                        .ok_or(anyhow!("Article has no edits"))?;
+                    query = query.having(
+                        last_edited.lt(cursor_edited).or(last_edited
+                            .eq(cursor_edited)
+                            .and(article::dsl::id.lt(cursor.id))),
+                    );
+                }
+            }
+            ArticleSort::Newest => {
+                query = query.order_by((article::dsl::published.desc(), article::dsl::id.desc()));
+                if let Some(cursor) = cursor {
+                    query = query.filter(
+                        article::dsl::published
+                            .lt(cursor.published)
+                            .or(article::dsl::published
+                                .eq(cursor.published)
+                                .and(article::dsl::id.lt(cursor.id))),
+                    );
+                }
+            }
+            ArticleSort::Alphabetical => {
+                query = query.order_by((article::dsl::title.asc(), article::dsl::id.asc()));
+                if let Some(cursor) = cursor {
+                    query = query.filter(
+                        article::dsl::title
+                            .gt(cursor.title.clone())
+                            .or(article::dsl::title
+                                .eq(cursor.title)
+                                .and(article::dsl::id.gt(cursor.id))),
+                    );
+                }
+            }
+        }
         Ok(query.get_results(&mut conn)?)
     }
 
-    pub fn search(query: &str, context: &IbisContext) -> BackendResult<Vec<Self>> {
+    /// Full-text search over title and text, ordered by relevance.
+    pub fn search(
+        params: &SearchArticleParams,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<SearchArticleResult>> {
         let mut conn = context.db_pool.get()?;
-        let replaced = query
-            .replace('%', "\\%")
-            .replace('_', "\\_")
-            .replace(' ', "%");
-        let replaced = format!("%{replaced}%");
-        Ok(article::table
+        let tsquery = || {
+            sql::<Text>("websearch_to_tsquery('english', ")
+                .bind::<Text, _>(params.query.clone())
+                .sql(")")
+        };
+        let rank = || {
+            sql::<Float>("ts_rank(")
+                .sql(SEARCH_VECTOR)
+                .sql(", ")
+                .bind::<Text, _>(tsquery())
+                .sql(")")
+        };
+        let mut query = article::table
+            .filter(article::dsl::deleted.eq(false))
             .filter(
-                article::dsl::title
-                    .ilike(&replaced)
-                    .or(article::dsl::text.ilike(&replaced)),
+                sql::<Bool>(SEARCH_VECTOR)
+                    .sql(" @@ ")
+                    .bind::<Text, _>(tsquery()),
             )
-            .get_results(conn.deref_mut())?)
+            .order_by((rank().desc(), article::dsl::id.desc()))
+            .select((
+                article::dsl::id,
+                article::dsl::title,
+                article::dsl::ap_id,
+                article::dsl::instance_id,
+                article::dsl::local,
+                sql::<Text>("ts_headline('english', article.text, ")
+                    .bind::<Text, _>(tsquery())
+                    .sql(", ")
+                    .bind::<Text, _>(SNIPPET_OPTIONS)
+                    .sql(")"),
+            ))
+            .limit(page_limit(params.limit))
+            .into_boxed();
+        // Keyset pagination, continue after the cursor article in order of relevance
+        if let Some(cursor) = params.cursor {
+            let cursor_rank: f32 = article::table
+                .filter(article::dsl::id.eq(cursor))
+                .select(rank())
+                .get_result(conn.deref_mut())?;
+            query = query.filter(
+                rank()
+                    .lt(cursor_rank)
+                    .or(rank().eq(cursor_rank).and(article::dsl::id.lt(cursor))),
+            );
+        }
+        if let Some(instance_id) = params.instance_id {
+            query = query.filter(article::dsl::instance_id.eq(instance_id));
+        }
+        if let Some(local) = params.local {
+            query = query.filter(article::dsl::local.eq(local));
+        }
+        let mut results: Vec<SearchArticleResult> = query.get_results(conn.deref_mut())?;
+        for r in &mut results {
+            r.snippet = escape_snippet(&r.snippet);
+        }
+        Ok(results)
     }
 
     pub fn latest_edit_version(&self, context: &IbisContext) -> BackendResult<EditVersion> {
@@ -217,3 +490,43 @@ impl DbArticle {
         }
     }
 }
+
+fn insert_redirect(
+    id: ArticleId,
+    old_title: &str,
+    new_title: &str,
+    conn: &mut PgConnection,
+) -> BackendResult<()> {
+    insert_into(article_redirect::table)
+        .values((
+            article_redirect::article_id.eq(id),
+            article_redirect::title.eq(old_title),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)?;
+    // in case the article is renamed back to an earlier title
+    diesel::delete(
+        article_redirect::table
+            .filter(article_redirect::article_id.eq(id))
+            .filter(article_redirect::title.eq(new_title)),
+    )
+    .execute(conn)?;
+    Ok(())
+}
+
+fn escape_snippet(snippet: &str) -> String {
+    let mut escaped = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            SNIPPET_START => escaped.push_str("<mark>"),
+            SNIPPET_STOP => escaped.push_str("</mark>"),
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,61 @@
+use super::{schema::captcha_answer, IbisContext};
+use crate::backend::utils::error::BackendResult;
+use anyhow::anyhow;
+use chrono::{DateTime, TimeDelta, Utc};
+use diesel::{delete, insert_into, ExpressionMethods, QueryDsl, Queryable, RunQueryDsl};
+use std::ops::DerefMut;
+use uuid::Uuid;
+
+/// How long a captcha can be solved after it was generated.
+const CAPTCHA_VALIDITY: TimeDelta = TimeDelta::minutes(10);
+
+#[derive(Clone, Debug, Queryable)]
+#[diesel(table_name = captcha_answer, check_for_backend(diesel::pg::Pg))]
+pub struct DbCaptchaAnswer {
+    pub uuid: Uuid,
+    pub answer: String,
+    pub published: DateTime<Utc>,
+}
+
+impl DbCaptchaAnswer {
+    pub fn create(answer: &str, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(captcha_answer::table)
+            .values(captcha_answer::answer.eq(answer))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read(uuid: Uuid, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(captcha_answer::table
+            .find(uuid)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Compare the answer of the user with the stored one. Each captcha can only be used for a
+    /// single attempt, so it is deleted in any case.
+    pub fn check(uuid: Uuid, answer: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let captcha: Self = delete(captcha_answer::table.find(uuid))
+            .get_result(conn.deref_mut())
+            .map_err(|_| anyhow!("Invalid captcha"))?;
+        if captcha.published + CAPTCHA_VALIDITY < Utc::now() {
+            return Err(anyhow!("Captcha expired").into());
+        }
+        if captcha.answer != answer.trim() {
+            return Err(anyhow!("Wrong captcha answer").into());
+        }
+        Ok(())
+    }
+
+    /// Remove captchas which were never used.
+    pub fn delete_expired(context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(
+            captcha_answer::table
+                .filter(captcha_answer::published.lt(Utc::now() - CAPTCHA_VALIDITY)),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use super::{
-    notifications::parent_comment,
+    notifications::DbNotification,
     schema::{comment, person},
     IbisContext,
 };
@@ -19,8 +19,6 @@ use diesel::{
     AsChangeset,
     ExpressionMethods,
     Insertable,
-    JoinOnDsl,
-    NullableExpressionMethods,
     QueryDsl,
     RunQueryDsl,
 };
@@ -53,9 +51,11 @@ pub struct DbCommentUpdateForm {
 impl DbComment {
     pub fn create(form: DbCommentInsertForm, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(insert_into(comment::table)
+        let comment: Self = insert_into(comment::table)
             .values(form)
-            .get_result(conn.deref_mut())?)
+            .get_result(conn.deref_mut())?;
+        DbNotification::notify_reply(&comment, context)?;
+        Ok(comment)
     }
 
     pub fn update(
@@ -67,6 +67,9 @@ impl DbComment {
         let comment: DbComment = update(comment::table.find(id))
             .set(form)
             .get_result(conn.deref_mut())?;
+        if comment.deleted {
+            DbNotification::delete_reply(comment.id, context)?;
+        }
         let creator = DbPerson::read(comment.creator_id, context)?;
         Ok(DbCommentView { comment, creator })
     }
@@ -75,13 +78,24 @@ impl DbComment {
         form: DbCommentInsertForm,
         context: &IbisContext,
     ) -> BackendResult<Self> {
+        let is_new = form
+            .ap_id
+            .as_ref()
+            .map(|ap_id| Self::read_from_ap_id(ap_id, context).is_err())
+            .unwrap_or(true);
         let mut conn = context.db_pool.get()?;
-        Ok(insert_into(comment::table)
+        let comment: Self = insert_into(comment::table)
             .values(&form)
             .on_conflict(comment::dsl::ap_id)
             .do_update()
             .set(&form)
-            .get_result(conn.deref_mut())?)
+            .get_result(conn.deref_mut())?;
+        if is_new {
+            DbNotification::notify_reply(&comment, context)?;
+        } else if comment.deleted {
+            DbNotification::delete_reply(comment.id, context)?;
+        }
+        Ok(comment)
     }
 
     pub fn read(id: CommentId, context: &IbisContext) -> BackendResult<Self> {
@@ -133,29 +147,4 @@ impl DbComment {
             })
             .collect())
     }
-
-    pub fn mark_as_read(
-        comment_id: CommentId,
-        person_id: PersonId,
-        context: &IbisContext,
-    ) -> BackendResult<()> {
-        let mut conn = context.db_pool.get()?;
-        // check that person_id matches the parent comment's creator_id
-        let comment_id: CommentId = comment::table
-            .find(comment_id)
-            .inner_join(
-                parent_comment.on(parent_comment
-                    .field(comment::id)
-                    .nullable()
-                    .eq(comment::parent_id)),
-            )
-            .filter(parent_comment.field(comment::creator_id).eq(person_id))
-            .select(comment::id)
-            .get_result(conn.deref_mut())?;
-
-        update(comment::table.find(comment_id))
-            .set(comment::read_by_parent_creator.eq(true))
-            .execute(conn.deref_mut())?;
-        Ok(())
-    }
 }
@@ -1,11 +1,12 @@
 use crate::{
     backend::{
         database::{
+            notifications::DbNotification,
             schema::{conflict, edit},
             IbisContext,
         },
         federation::activities::submit_article_update,
-        utils::{error::BackendResult, generate_article_version},
+        utils::error::BackendResult,
     },
     common::{
         article::{ApiConflict, DbArticle, DbEdit, EditVersion},
@@ -58,9 +59,11 @@ pub struct DbConflictForm {
 impl DbConflict {
     pub fn create(form: &DbConflictForm, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
-        Ok(insert_into(conflict::table)
+        let conflict: Self = insert_into(conflict::table)
             .values(form)
-            .get_result(conn.deref_mut())?)
+            .get_result(conn.deref_mut())?;
+        DbNotification::notify_edit_conflict(&conflict, context)?;
+        Ok(conflict)
     }
 
     pub fn read(
@@ -111,8 +114,8 @@ impl DbConflict {
         };
 
         // create common ancestor version
-        let edits = DbEdit::list_for_article(original_article.id, context)?;
-        let ancestor = generate_article_version(&edits, &self.previous_version_id)?;
+        let mut edits = DbEdit::list_for_article(original_article.id, context)?;
+        let ancestor = DbEdit::generate_version(&mut edits, &self.previous_version_id, context)?;
 
         let patch = Patch::from_str(&self.diff)?;
         // apply self.diff to ancestor to get `ours`
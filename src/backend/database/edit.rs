@@ -1,30 +1,45 @@
 use crate::{
     backend::{
-        database::schema::{article, edit, person},
-        utils::error::BackendResult,
+        database::{
+            notifications::DbNotification,
+            page_limit,
+            schema::{article, edit, person},
+        },
+        utils::{
+            error::{BackendError, BackendResult},
+            generate_article_version,
+        },
         IbisContext,
     },
     common::{
-        article::{DbArticle, DbEdit, EditVersion, EditView},
-        newtypes::{ArticleId, PersonId},
+        article::{DbArticle, DbEdit, EditSort, EditVersion, EditView, GetEditList},
+        newtypes::{ArticleId, EditId, PersonId},
         user::LocalUserView,
     },
 };
 use activitypub_federation::fetch::object_id::ObjectId;
 use chrono::{DateTime, Utc};
 use diesel::{
-    dsl::not,
+    dsl::{count, not},
     insert_into,
+    sql_types::{Nullable, Text},
     AsChangeset,
     BoolExpressionMethods,
     ExpressionMethods,
     Insertable,
+    IntoSql,
+    NullableExpressionMethods,
+    OptionalExtension,
     QueryDsl,
     RunQueryDsl,
 };
 use diffy::create_patch;
 use std::ops::DerefMut;
 
+/// Store the full article text when there are this many edits since the last snapshot, see
+/// [DbEdit::snapshot].
+const SNAPSHOT_INTERVAL: usize = 25;
+
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = edit, check_for_backend(diesel::pg::Pg))]
 pub struct DbEditForm {
@@ -78,13 +93,137 @@ impl DbEditForm {
 
 impl DbEdit {
     pub fn create(form: &DbEditForm, context: &IbisContext) -> BackendResult<Self> {
+        let previous = Self::read_from_ap_id(&form.ap_id, context).ok();
         let mut conn = context.db_pool.get()?;
-        Ok(insert_into(edit::table)
+        let edit: Self = insert_into(edit::table)
             .values(form)
             .on_conflict(edit::dsl::ap_id)
             .do_update()
             .set(form)
-            .get_result(conn.deref_mut())?)
+            .get_result(conn.deref_mut())?;
+        // Edits are upserted whenever the edits collection is fetched, so only notify once the
+        // edit becomes visible for the first time.
+        if !edit.pending && previous.as_ref().map(|p| p.pending).unwrap_or(true) {
+            DbNotification::notify_article_edited(&edit, context)?;
+        }
+        if previous.is_none() {
+            edit.clear_later_snapshots(context)?;
+        }
+        edit.create_snapshot(context)
+    }
+
+    /// Federated edits may arrive out of order, in which case snapshots of later edits don't
+    /// include the changes from this edit and need to be regenerated.
+    fn clear_later_snapshots(&self, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        diesel::update(
+            edit::table
+                .filter(edit::article_id.eq(self.article_id))
+                .filter(edit::snapshot.is_not_null())
+                .filter(
+                    edit::published
+                        .gt(self.published)
+                        .or(edit::published.eq(self.published).and(edit::id.gt(self.id))),
+                ),
+        )
+        .set(edit::snapshot.eq(None::<String>))
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Stores the full article text if there are enough edits since the last snapshot.
+    fn create_snapshot(self, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        if self.snapshot.is_some() {
+            return Ok(self);
+        }
+        // check the distance with a cheap count first, to avoid loading all edits every time
+        let until_self = edit::published
+            .lt(self.published)
+            .or(edit::published.eq(self.published).and(edit::id.le(self.id)));
+        let last_snapshot = edit::table
+            .filter(edit::article_id.eq(self.article_id))
+            .filter(edit::snapshot.is_not_null())
+            .filter(until_self)
+            .order((edit::published.desc(), edit::id.desc()))
+            .select((edit::published, edit::id))
+            .first::<(DateTime<Utc>, EditId)>(conn.deref_mut())
+            .optional()?;
+        let mut query = edit::table
+            .filter(edit::article_id.eq(self.article_id))
+            .filter(until_self)
+            .select(count(edit::id))
+            .into_boxed();
+        if let Some((published, id)) = last_snapshot {
+            query = query.filter(
+                edit::published
+                    .gt(published)
+                    .or(edit::published.eq(published).and(edit::id.gt(id))),
+            );
+        }
+        let distance = query.get_result::<i64>(conn.deref_mut())?;
+        if distance < SNAPSHOT_INTERVAL as i64 {
+            return Ok(self);
+        }
+        let mut edits = Self::list_for_article(self.article_id, context)?;
+        Self::generate_version(&mut edits, &self.hash, context)?;
+        Self::read_from_id(self.id, context)
+    }
+
+    /// Generate the article text at the given version, starting from the nearest snapshot. If
+    /// that is too far away, the text is stored as new snapshot. This way articles which were
+    /// created before snapshots existed get them when older versions are read.
+    pub fn generate_version(
+        edits: &mut [DbEdit],
+        version: &EditVersion,
+        context: &IbisContext,
+    ) -> BackendResult<String> {
+        let Some(end) = edits.iter().position(|e| &e.hash == version) else {
+            // handles the empty version, and returns error for invalid ones
+            return generate_article_version(edits, version);
+        };
+        let snapshot = Self::load_snapshot(edits, end, context)?;
+        let text = generate_article_version(edits, version)?;
+        // number of edits which were applied on top of the snapshot
+        let distance = end + 1 - snapshot.map(|i| i + 1).unwrap_or(0);
+        if distance >= SNAPSHOT_INTERVAL {
+            let mut conn = context.db_pool.get()?;
+            diesel::update(edit::table.find(edits[end].id))
+                .set(edit::snapshot.eq(&text))
+                .execute(conn.deref_mut())?;
+        }
+        Ok(text)
+    }
+
+    /// [DbEdit::list_for_article] doesn't include snapshots, so load the one nearest before
+    /// `edits[end]`. Returns its position in `edits`.
+    fn load_snapshot(
+        edits: &mut [DbEdit],
+        end: usize,
+        context: &IbisContext,
+    ) -> BackendResult<Option<usize>> {
+        let mut conn = context.db_pool.get()?;
+        let last = &edits[end];
+        let snapshot = edit::table
+            .filter(edit::article_id.eq(last.article_id))
+            .filter(
+                edit::published
+                    .lt(last.published)
+                    .or(edit::published.eq(last.published).and(edit::id.le(last.id))),
+            )
+            .filter(edit::snapshot.is_not_null())
+            .order((edit::published.desc(), edit::id.desc()))
+            .select((edit::id, edit::snapshot.assume_not_null()))
+            .first::<(EditId, String)>(conn.deref_mut())
+            .optional()?;
+        let Some((id, text)) = snapshot else {
+            return Ok(None);
+        };
+        let position = edits[..=end].iter().position(|e| e.id == id);
+        if let Some(i) = position {
+            edits[i].snapshot = Some(text);
+        }
+        Ok(position)
     }
 
     pub fn read(version: &EditVersion, context: &IbisContext) -> BackendResult<Self> {
@@ -94,6 +233,11 @@ impl DbEdit {
             .get_result(conn.deref_mut())?)
     }
 
+    pub fn read_from_id(id: EditId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table.find(id).get_result(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(ap_id: &ObjectId<DbEdit>, context: &IbisContext) -> BackendResult<Self> {
         let mut conn = context.db_pool.get()?;
         Ok(edit::table
@@ -101,34 +245,111 @@ impl DbEdit {
             .get_result(conn.deref_mut())?)
     }
 
+    /// All edits of an article, oldest first. Snapshots are not loaded here, as they are only
+    /// needed by [DbEdit::generate_version].
     pub fn list_for_article(id: ArticleId, context: &IbisContext) -> BackendResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
         Ok(edit::table
             .filter(edit::article_id.eq(id))
-            .order(edit::published)
+            .order((edit::published, edit::id))
+            .select((
+                edit::id,
+                edit::creator_id,
+                edit::hash,
+                edit::ap_id,
+                edit::diff,
+                edit::summary,
+                edit::article_id,
+                edit::previous_version_id,
+                edit::published,
+                edit::pending,
+                None::<String>.into_sql::<Nullable<Text>>(),
+            ))
             .get_results(conn.deref_mut())?)
     }
 
+    /// Page of edits for federation, oldest first so that they can be applied in order.
+    pub fn list_for_article_page(
+        id: ArticleId,
+        cursor: Option<EditId>,
+        limit: Option<i64>,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = edit::table
+            .filter(edit::article_id.eq(id))
+            .order((edit::published, edit::id))
+            .limit(page_limit(limit))
+            .into_boxed();
+        if let Some(cursor) = cursor {
+            let published = Self::read_published(cursor, context)?;
+            query = query.filter(
+                edit::published
+                    .gt(published)
+                    .or(edit::published.eq(published).and(edit::id.gt(cursor))),
+            );
+        }
+        Ok(query.get_results(conn.deref_mut())?)
+    }
+
     pub fn view(
         params: ViewEditParams,
+        page: &GetEditList,
         user: &Option<LocalUserView>,
         context: &IbisContext,
     ) -> BackendResult<Vec<EditView>> {
         let mut conn = context.db_pool.get()?;
         let person_id = user.as_ref().map(|u| u.person.id).unwrap_or(PersonId(-1));
-        let query = edit::table
+        let mut query = edit::table
             .inner_join(article::table)
             .inner_join(person::table)
             // only the creator can view pending edits
             .filter(not(edit::pending).or(edit::creator_id.eq(person_id)))
+            .limit(page_limit(page.limit))
             .into_boxed();
 
-        let query = match params {
+        query = match params {
             ViewEditParams::PersonId(person_id) => query.filter(edit::creator_id.eq(person_id)),
             ViewEditParams::ArticleId(article_id) => query.filter(edit::article_id.eq(article_id)),
         };
 
-        Ok(query.order(edit::published).get_results(conn.deref_mut())?)
+        // Keyset pagination, continue after the cursor edit in the same sort order
+        let cursor = page
+            .cursor
+            .map(|id| Ok::<_, BackendError>((Self::read_published(id, context)?, id)))
+            .transpose()?;
+        query = match page.sort.unwrap_or_default() {
+            EditSort::Oldest => {
+                if let Some((published, id)) = cursor {
+                    query = query.filter(
+                        edit::published
+                            .gt(published)
+                            .or(edit::published.eq(published).and(edit::id.gt(id))),
+                    );
+                }
+                query.order((edit::published.asc(), edit::id.asc()))
+            }
+            EditSort::Newest => {
+                if let Some((published, id)) = cursor {
+                    query = query.filter(
+                        edit::published
+                            .lt(published)
+                            .or(edit::published.eq(published).and(edit::id.lt(id))),
+                    );
+                }
+                query.order((edit::published.desc(), edit::id.desc()))
+            }
+        };
+
+        Ok(query.get_results(conn.deref_mut())?)
+    }
+
+    fn read_published(id: EditId, context: &IbisContext) -> BackendResult<DateTime<Utc>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table
+            .find(id)
+            .select(edit::published)
+            .get_result(conn.deref_mut())?)
     }
 }
 
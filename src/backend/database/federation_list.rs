@@ -0,0 +1,111 @@
+use super::{schema::federation_list, IbisContext};
+use crate::{backend::utils::error::BackendResult, common::instance::DbFederationListEntry};
+use anyhow::anyhow;
+use diesel::{delete, insert_into, ExpressionMethods, QueryDsl, RunQueryDsl};
+use log::warn;
+use std::{
+    ops::DerefMut,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+/// Federation allowlist and blocklist kept in memory, because they are checked for every fetch
+/// and every incoming activity. Needs to be refreshed after the lists are changed in the
+/// database.
+#[derive(Clone, Default)]
+pub struct FederationListCache(Arc<RwLock<Vec<DbFederationListEntry>>>);
+
+impl FederationListCache {
+    pub fn refresh(&self, context: &IbisContext) -> BackendResult<()> {
+        let list = DbFederationListEntry::list(context)?;
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = list;
+        Ok(())
+    }
+
+    /// Returns an error message if federation with the domain is not allowed.
+    pub fn check(&self, domain: &str) -> Option<String> {
+        let list = self.0.read().unwrap_or_else(PoisonError::into_inner);
+        let mut allowlist = list.iter().filter(|e| !e.blocked).peekable();
+        if allowlist.peek().is_some() && !allowlist.any(|e| e.domain == domain) {
+            return Some(format!("Domain {domain} is not allowed"));
+        }
+        if list.iter().any(|e| e.blocked && e.domain == domain) {
+            return Some(format!("Domain {domain} is blocked"));
+        }
+        None
+    }
+}
+
+impl DbFederationListEntry {
+    pub fn list(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(federation_list::table
+            .order(federation_list::domain)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Add domain to allowlist or blocklist. If it is already listed, it is moved to the other
+    /// list if necessary.
+    pub fn add(domain: &str, blocked: bool, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let entry = insert_into(federation_list::table)
+            .values((
+                federation_list::domain.eq(domain),
+                federation_list::blocked.eq(blocked),
+            ))
+            .on_conflict(federation_list::domain)
+            .do_update()
+            .set(federation_list::blocked.eq(blocked))
+            .get_result(conn.deref_mut())?;
+        context.federation_list.refresh(context)?;
+        Ok(entry)
+    }
+
+    pub fn remove(domain: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let deleted = delete(federation_list::table.filter(federation_list::domain.eq(domain)))
+            .execute(conn.deref_mut())?;
+        if deleted == 0 {
+            return Err(anyhow!("Domain {domain} is not listed").into());
+        }
+        context.federation_list.refresh(context)?;
+        Ok(())
+    }
+
+    /// Copy the deprecated allowlist and blocklist from config file into the database. This is
+    /// only done if no domains are listed yet, otherwise domains which were removed in the
+    /// instance settings would be added again on every startup.
+    pub fn import_from_config(context: &IbisContext) -> BackendResult<()> {
+        let federation = &context.config.federation;
+        if federation.allowlist.is_none() && federation.blocklist.is_none() {
+            return Ok(());
+        }
+        warn!(
+            "Config options federation.allowlist and federation.blocklist are deprecated, remove \
+            them and manage the lists in instance settings instead"
+        );
+        let mut conn = context.db_pool.get()?;
+        let count: i64 = federation_list::table
+            .count()
+            .get_result(conn.deref_mut())?;
+        if count > 0 {
+            return Ok(());
+        }
+        let lists = [
+            (&federation.allowlist, false),
+            (&federation.blocklist, true),
+        ];
+        for (list, blocked) in lists {
+            let Some(list) = list else { continue };
+            for domain in list.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+                insert_into(federation_list::table)
+                    .values((
+                        federation_list::domain.eq(domain),
+                        federation_list::blocked.eq(blocked),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn.deref_mut())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     backend::{
         database::{
-            schema::{article, comment, edit, instance, instance_follow},
+            page_limit,
+            schema::{article, comment, edit, instance, instance_follow, person},
             IbisContext,
         },
         federation::objects::{
@@ -11,8 +12,8 @@ use crate::{
         utils::error::BackendResult,
     },
     common::{
-        instance::{DbInstance, InstanceView, InstanceView2},
-        newtypes::{CommentId, InstanceId},
+        instance::{DbInstance, InstanceView, InstanceView2, ListInstancesParams},
+        newtypes::{CommentId, InstanceId, PersonId},
         user::DbPerson,
     },
 };
@@ -71,6 +72,27 @@ impl DbInstance {
             .get_result(conn.deref_mut())?)
     }
 
+    /// Change metadata of a remote instance, without touching its collections.
+    pub fn update_remote(
+        ap_id: &ObjectId<DbInstance>,
+        name: Option<String>,
+        topic: Option<String>,
+        public_key: String,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(update(instance::table)
+            .filter(instance::ap_id.eq(ap_id))
+            .filter(instance::local.eq(false))
+            .set((
+                instance::name.eq(name),
+                instance::topic.eq(topic),
+                instance::public_key.eq(public_key),
+                instance::last_refreshed_at.eq(Utc::now()),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(
         ap_id: &ObjectId<DbInstance>,
         context: &Data<IbisContext>,
@@ -144,9 +166,19 @@ impl DbInstance {
             .get_results(conn.deref_mut())?)
     }
 
-    pub fn list_views(context: &Data<IbisContext>) -> BackendResult<Vec<InstanceView>> {
+    pub fn list_views(
+        params: &ListInstancesParams,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<Vec<InstanceView>> {
         let mut conn = context.db_pool.get()?;
-        let instances = instance::table.get_results::<DbInstance>(conn.deref_mut())?;
+        let mut query = instance::table
+            .order_by(instance::id)
+            .limit(page_limit(params.limit))
+            .into_boxed();
+        if let Some(cursor) = params.cursor {
+            query = query.filter(instance::id.gt(cursor));
+        }
+        let instances = query.get_results::<DbInstance>(conn.deref_mut())?;
         let mut res = vec![];
         // Get the last edited articles for each instance.
         // TODO: This is very inefficient, should use single query with lateral join
@@ -154,6 +186,7 @@ impl DbInstance {
         for instance in instances {
             let articles = article::table
                 .filter(article::instance_id.eq(instance.id))
+                .filter(article::deleted.eq(false))
                 .inner_join(edit::table)
                 .group_by(article::id)
                 .order_by((article::local.desc(), max(edit::published).desc()))
@@ -180,4 +213,61 @@ impl DbInstance {
             .select(instance::all_columns)
             .get_result(conn.deref_mut())?)
     }
+
+    /// Remote instances which host articles that the user has edited or commented on.
+    pub fn read_with_content_from(
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<DbInstance>> {
+        let mut conn = context.db_pool.get()?;
+        let mut instances: Vec<DbInstance> = instance::table
+            .inner_join(article::table)
+            .inner_join(edit::table.on(edit::article_id.eq(article::id)))
+            .filter(edit::creator_id.eq(person_id))
+            .filter(instance::local.eq(false))
+            .select(instance::all_columns)
+            .distinct()
+            .get_results(conn.deref_mut())?;
+        let commented: Vec<DbInstance> = instance::table
+            .inner_join(article::table)
+            .inner_join(comment::table.on(comment::article_id.eq(article::id)))
+            .filter(comment::creator_id.eq(person_id))
+            .filter(instance::local.eq(false))
+            .select(instance::all_columns)
+            .distinct()
+            .get_results(conn.deref_mut())?;
+        for instance in commented {
+            if !instances.iter().any(|i| i.id == instance.id) {
+                instances.push(instance);
+            }
+        }
+        Ok(instances)
+    }
+
+    /// Remove all content from a blocked domain. Articles which are hosted on the domain are
+    /// deleted entirely. Users are deleted in the same way as with account deletion, so that
+    /// the edit history of articles on other instances stays intact.
+    pub fn purge(domain: &str, context: &Data<IbisContext>) -> BackendResult<()> {
+        let ghost = DbPerson::ghost(context)?;
+        let mut conn = context.db_pool.get()?;
+        conn.deref_mut().transaction(|conn| {
+            // exact match on the host part of `scheme://host[:port]/path`
+            let persons: Vec<PersonId> = person::table
+                .filter(
+                    dsl::sql::<sql_types::Text>("lower(split_part(person.ap_id, '/', 3))")
+                        .eq(domain),
+                )
+                .filter(person::local.eq(false))
+                .select(person::id)
+                .get_results(conn)?;
+            DbPerson::delete_with_conn(&persons, ghost.id, conn)?;
+            delete(
+                instance::table
+                    .filter(instance::domain.eq(domain))
+                    .filter(instance::local.eq(false)),
+            )
+            .execute(conn)?;
+            Ok(())
+        })
+    }
 }
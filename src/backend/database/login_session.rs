@@ -0,0 +1,89 @@
+use super::{schema::login_session, IbisContext};
+use crate::{
+    backend::utils::error::BackendResult,
+    common::{
+        newtypes::{LoginSessionId, PersonId},
+        user::DbLoginSession,
+    },
+};
+use anyhow::anyhow;
+use chrono::{TimeDelta, Utc};
+use diesel::{delete, insert_into, update, ExpressionMethods, QueryDsl, RunQueryDsl};
+use std::ops::DerefMut;
+
+/// Avoid writing to the database on every request, it is enough to update the last used time
+/// once in a while.
+const LAST_USED_INTERVAL: TimeDelta = TimeDelta::minutes(5);
+
+impl DbLoginSession {
+    pub fn create(
+        person_id: PersonId,
+        user_agent: Option<String>,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(login_session::table)
+            .values((
+                login_session::person_id.eq(person_id),
+                login_session::user_agent.eq(user_agent),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Check that the session from a login token still exists and belongs to the user, and
+    /// update the time when it was last used.
+    pub fn validate(
+        id: LoginSessionId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let session: Self = login_session::table
+            .find(id)
+            .filter(login_session::person_id.eq(person_id))
+            .get_result(conn.deref_mut())
+            .map_err(|_| anyhow!("Login session was revoked"))?;
+        let now = Utc::now();
+        if session.last_used + LAST_USED_INTERVAL < now {
+            update(login_session::table.find(id))
+                .set(login_session::last_used.eq(now))
+                .execute(conn.deref_mut())?;
+        }
+        Ok(())
+    }
+
+    pub fn list(person_id: PersonId, context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(login_session::table
+            .filter(login_session::person_id.eq(person_id))
+            .order_by(login_session::last_used.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Revoke the session, so that its login token can't be used anymore.
+    pub fn delete(
+        id: LoginSessionId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let deleted = delete(
+            login_session::table
+                .find(id)
+                .filter(login_session::person_id.eq(person_id)),
+        )
+        .execute(conn.deref_mut())?;
+        if deleted == 0 {
+            return Err(anyhow!("Login session not found").into());
+        }
+        Ok(())
+    }
+
+    /// Revoke all sessions of the user, eg after password change.
+    pub fn delete_all(person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(login_session::table.filter(login_session::person_id.eq(person_id)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+}
@@ -1,22 +1,36 @@
 use crate::backend::{
-    database::schema::jwt_secret,
-    utils::{config::IbisConfig, error::BackendResult},
+    database::{
+        federation_list::FederationListCache,
+        schema::{jwt_secret, login_session as login_session_table},
+    },
+    utils::{config::IbisConfig, email::generate_token, error::BackendResult},
 };
 use diesel::{
+    delete,
     r2d2::{ConnectionManager, Pool},
+    update,
+    ExpressionMethods,
     PgConnection,
     QueryDsl,
     RunQueryDsl,
 };
 use std::ops::DerefMut;
 
+pub mod api_token;
 pub mod article;
+pub mod captcha;
 pub mod comment;
 pub mod conflict;
 pub mod edit;
+pub mod federation_list;
 pub mod instance;
 pub mod instance_stats;
+pub mod login_session;
+pub mod mod_log;
 pub mod notifications;
+pub mod oauth_account;
+pub mod registration_application;
+pub mod report;
 pub(crate) mod schema;
 pub mod user;
 
@@ -26,6 +40,15 @@ pub type DbPool = Pool<ConnectionManager<PgConnection>>;
 pub struct IbisContext {
     pub db_pool: DbPool,
     pub config: IbisConfig,
+    pub federation_list: FederationListCache,
+}
+
+/// Number of items to return for paginated listings, if the client doesn't specify a limit.
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+const MAX_PAGE_LIMIT: i64 = 100;
+
+pub(crate) fn page_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
 }
 
 pub fn read_jwt_secret(context: &IbisContext) -> BackendResult<String> {
@@ -34,3 +57,13 @@ pub fn read_jwt_secret(context: &IbisContext) -> BackendResult<String> {
         .select(jwt_secret::dsl::secret)
         .first(conn.deref_mut())?)
 }
+
+/// Generate a new secret for login tokens, which logs out all users.
+pub fn rotate_jwt_secret(context: &IbisContext) -> BackendResult<()> {
+    let mut conn = context.db_pool.get()?;
+    update(jwt_secret::table)
+        .set(jwt_secret::secret.eq(generate_token()))
+        .execute(conn.deref_mut())?;
+    delete(login_session_table::table).execute(conn.deref_mut())?;
+    Ok(())
+}
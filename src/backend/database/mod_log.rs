@@ -0,0 +1,101 @@
+use super::{
+    page_limit,
+    schema::{article, mod_log, person},
+    IbisContext,
+};
+use crate::{
+    backend::utils::error::BackendResult,
+    common::{
+        mod_log::{DbModLog, ListModLogParams, ModAction, ModLogView},
+        newtypes::{ArticleId, PersonId},
+    },
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+    deserialize::{self, FromSql},
+    insert_into,
+    pg::{Pg, PgValue},
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+    ExpressionMethods,
+    Insertable,
+    JoinOnDsl,
+    NullableExpressionMethods,
+    QueryDsl,
+    RunQueryDsl,
+};
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = mod_log, check_for_backend(diesel::pg::Pg))]
+pub struct DbModLogForm {
+    pub mod_id: PersonId,
+    pub action: ModAction,
+    pub target_person_id: Option<PersonId>,
+    pub target_article_id: Option<ArticleId>,
+    pub reason: Option<String>,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl DbModLogForm {
+    pub fn article(mod_id: PersonId, action: ModAction, article_id: ArticleId) -> Self {
+        DbModLogForm {
+            mod_id,
+            action,
+            target_person_id: None,
+            target_article_id: Some(article_id),
+            reason: None,
+            expires: None,
+        }
+    }
+}
+
+impl DbModLog {
+    pub fn create(form: DbModLogForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(mod_log::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn list(
+        params: &ListModLogParams,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ModLogView>> {
+        let mut conn = context.db_pool.get()?;
+        let target_person = diesel::alias!(person as target_person);
+        let mut query = mod_log::table
+            .left_join(person::table.on(mod_log::mod_id.eq(person::id.nullable())))
+            .left_join(
+                target_person
+                    .on(mod_log::target_person_id.eq(target_person.field(person::id).nullable())),
+            )
+            .left_join(article::table.on(mod_log::target_article_id.eq(article::id.nullable())))
+            .select((
+                mod_log::all_columns,
+                person::all_columns.nullable(),
+                target_person.fields(person::all_columns).nullable(),
+                article::all_columns.nullable(),
+            ))
+            .order(mod_log::id.desc())
+            .limit(page_limit(params.limit))
+            .into_boxed();
+        if let Some(cursor) = params.cursor {
+            query = query.filter(mod_log::id.lt(cursor));
+        }
+        Ok(query.get_results(conn.deref_mut())?)
+    }
+}
+
+impl ToSql<Text, Pg> for ModAction {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for ModAction {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let action = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        ModAction::from_name(&action).ok_or_else(|| format!("Unknown mod action {action}").into())
+    }
+}
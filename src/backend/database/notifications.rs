@@ -1,124 +1,496 @@
 use super::{
     conflict::DbConflict,
-    schema::{article, comment, conflict, person},
+    page_limit,
+    schema::{
+        article,
+        article_watch,
+        comment,
+        conflict,
+        edit,
+        local_user,
+        notification,
+        person,
+        registration_application,
+        report,
+    },
     IbisContext,
 };
 use crate::{
-    backend::{api::check_is_admin, utils::error::BackendResult},
-    common::{comment::CommentViewWithArticle, user::LocalUserView, Notification},
+    backend::utils::error::BackendResult,
+    common::{
+        article::{DbArticle, DbEdit, EditView},
+        comment::{CommentViewWithArticle, DbComment},
+        newtypes::{
+            ArticleId,
+            CommentId,
+            ConflictId,
+            EditId,
+            NotificationId,
+            PersonId,
+            RegistrationApplicationId,
+            ReportId,
+        },
+        registration_application::{DbRegistrationApplication, RegistrationApplicationView},
+        report::{DbReport, ReportView},
+        user::{DbPerson, LocalUserView},
+        ApiNotification,
+        ListNotificationsParams,
+        Notification,
+    },
 };
-use activitypub_federation::config::Data;
+use chrono::{DateTime, Utc};
 use diesel::{
-    dsl::{count, not},
+    delete,
+    dsl::{count, exists, not},
+    insert_into,
+    select,
+    update,
+    BoolExpressionMethods,
     ExpressionMethods,
+    Identifiable,
+    Insertable,
     JoinOnDsl,
     NullableExpressionMethods,
     QueryDsl,
+    Queryable,
     RunQueryDsl,
 };
-use futures::future::try_join_all;
 use std::ops::DerefMut;
 
-diesel::alias!(comment as parent_comment: DbComment);
+/// A notification for a local user. It is written when the event happens, so listing and
+/// counting notifications only needs to read this table. The kind of notification depends on
+/// which of the optional foreign keys is set. If none is set, the article requires approval.
+/// Registration applications are the only notifications which are not about an article.
+#[derive(Clone, Debug, Queryable, Identifiable)]
+#[diesel(table_name = notification, check_for_backend(diesel::pg::Pg))]
+pub struct DbNotification {
+    pub id: NotificationId,
+    pub person_id: PersonId,
+    pub article_id: Option<ArticleId>,
+    pub conflict_id: Option<ConflictId>,
+    pub comment_id: Option<CommentId>,
+    pub edit_id: Option<EditId>,
+    pub read: bool,
+    pub published: DateTime<Utc>,
+    pub emailed: bool,
+    pub report_id: Option<ReportId>,
+    pub registration_application_id: Option<RegistrationApplicationId>,
+}
 
-impl Notification {
-    pub(crate) async fn list(
-        user: &LocalUserView,
-        context: &Data<IbisContext>,
-    ) -> BackendResult<Vec<Notification>> {
+#[derive(Debug, Clone, Default, Insertable)]
+#[diesel(table_name = notification, check_for_backend(diesel::pg::Pg))]
+pub struct DbNotificationForm {
+    pub person_id: PersonId,
+    pub article_id: Option<ArticleId>,
+    pub conflict_id: Option<ConflictId>,
+    pub comment_id: Option<CommentId>,
+    pub edit_id: Option<EditId>,
+    pub report_id: Option<ReportId>,
+    pub registration_application_id: Option<RegistrationApplicationId>,
+}
+
+type NotificationTuple = (
+    DbNotification,
+    Option<DbArticle>,
+    Option<DbConflict>,
+    Option<DbComment>,
+    Option<DbEdit>,
+    Option<DbPerson>,
+    Option<DbReport>,
+    Option<DbRegistrationApplication>,
+);
+
+impl DbNotification {
+    fn create(forms: &[DbNotificationForm], context: &IbisContext) -> BackendResult<()> {
+        if forms.is_empty() {
+            return Ok(());
+        }
         let mut conn = context.db_pool.get()?;
-        let mut notifications: Vec<Notification> = vec![];
+        insert_into(notification::table)
+            .values(forms)
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Notify the creator of a merge conflict, so that it can be resolved later.
+    pub fn notify_edit_conflict(conflict: &DbConflict, context: &IbisContext) -> BackendResult<()> {
+        let form = DbNotificationForm {
+            person_id: conflict.creator_id,
+            article_id: Some(conflict.article_id),
+            conflict_id: Some(conflict.id),
+            ..Default::default()
+        };
+        Self::create(&[form], context)
+    }
 
-        // edit conflicts
-        let conflicts: Vec<DbConflict> = conflict::table
-            .filter(conflict::dsl::creator_id.eq(user.person.id))
+    /// Notify the creator of the parent comment about a new reply, if it is a local user.
+    pub fn notify_reply(comment: &DbComment, context: &IbisContext) -> BackendResult<()> {
+        let Some(parent_id) = comment.parent_id else {
+            return Ok(());
+        };
+        let parent = DbComment::read(parent_id, context)?;
+        if parent.creator_id == comment.creator_id || comment.deleted {
+            return Ok(());
+        }
+        let mut conn = context.db_pool.get()?;
+        let is_local: bool = select(exists(
+            local_user::table.filter(local_user::person_id.eq(parent.creator_id)),
+        ))
+        .get_result(conn.deref_mut())?;
+        if !is_local {
+            return Ok(());
+        }
+        let form = DbNotificationForm {
+            person_id: parent.creator_id,
+            article_id: Some(comment.article_id),
+            comment_id: Some(comment.id),
+            ..Default::default()
+        };
+        Self::create(&[form], context)
+    }
+
+    /// Notify all users who are watching the article about a new edit.
+    pub fn notify_article_edited(edit: &DbEdit, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let watchers: Vec<PersonId> = article_watch::table
+            .filter(article_watch::article_id.eq(edit.article_id))
+            .filter(article_watch::person_id.ne(edit.creator_id))
+            .select(article_watch::person_id)
             .get_results(conn.deref_mut())?;
-        let conflicts = try_join_all(conflicts.into_iter().map(|c| {
-            let data = context.reset_request_count();
-            async move { c.to_api_conflict(false, &data).await }
-        }))
-        .await?
-        .into_iter();
-        notifications.extend(conflicts.flatten().map(Notification::EditConflict));
-
-        // comment replies
-        let comment_replies = comment::table
-            .inner_join(article::table)
-            .inner_join(person::table.on(person::id.eq(comment::creator_id)))
-            .inner_join(
-                parent_comment.on(parent_comment
-                    .field(comment::id)
-                    .nullable()
-                    .eq(comment::parent_id)),
+        let forms: Vec<_> = watchers
+            .into_iter()
+            .map(|person_id| DbNotificationForm {
+                person_id,
+                article_id: Some(edit.article_id),
+                edit_id: Some(edit.id),
+                ..Default::default()
+            })
+            .collect();
+        Self::create(&forms, context)
+    }
+
+    fn read_admins(context: &IbisContext) -> BackendResult<Vec<PersonId>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(local_user::table
+            .filter(local_user::admin)
+            .select(local_user::person_id)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Notify all local admins about a new article which needs to be approved.
+    pub fn notify_approval_required(
+        article: &DbArticle,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let admins = Self::read_admins(context)?;
+        let forms: Vec<_> = admins
+            .into_iter()
+            .map(|person_id| DbNotificationForm {
+                person_id,
+                article_id: Some(article.id),
+                ..Default::default()
+            })
+            .collect();
+        Self::create(&forms, context)
+    }
+
+    /// Notify all local admins about a new report. The reported edit or comment is also
+    /// referenced, so that it can be shown in the notification.
+    pub fn notify_report(report: &DbReport, context: &IbisContext) -> BackendResult<()> {
+        let admins = Self::read_admins(context)?;
+        let forms: Vec<_> = admins
+            .into_iter()
+            .map(|person_id| DbNotificationForm {
+                person_id,
+                article_id: Some(report.article_id),
+                comment_id: report.comment_id,
+                edit_id: report.edit_id,
+                report_id: Some(report.id),
+                ..Default::default()
+            })
+            .collect();
+        Self::create(&forms, context)
+    }
+
+    /// Notify all local admins about a new user who needs to be approved.
+    pub fn notify_registration_application(
+        application: &DbRegistrationApplication,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let admins = Self::read_admins(context)?;
+        let forms: Vec<_> = admins
+            .into_iter()
+            .map(|person_id| DbNotificationForm {
+                person_id,
+                registration_application_id: Some(application.id),
+                ..Default::default()
+            })
+            .collect();
+        Self::create(&forms, context)
+    }
+
+    /// Remove application notifications for all admins, once one of them approved or denied it.
+    pub fn delete_registration_application(
+        id: RegistrationApplicationId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(notification::table.filter(notification::registration_application_id.eq(id)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Remove report notifications for all admins, once one of them resolved the report.
+    pub fn delete_report(report_id: ReportId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(notification::table.filter(notification::report_id.eq(report_id)))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Remove approval notifications for all admins, once one of them approved the article.
+    pub fn delete_approval_required(
+        article_id: ArticleId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(
+            notification::table
+                .filter(notification::article_id.eq(article_id))
+                .filter(notification::conflict_id.is_null())
+                .filter(notification::comment_id.is_null())
+                .filter(notification::edit_id.is_null())
+                .filter(notification::report_id.is_null()),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Remove edit notifications for an article which the user doesn't watch anymore.
+    pub fn delete_article_edited(
+        article_id: ArticleId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(
+            notification::table
+                .filter(notification::article_id.eq(article_id))
+                .filter(notification::person_id.eq(person_id))
+                .filter(notification::edit_id.is_not_null())
+                .filter(notification::report_id.is_null()),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Remove reply notifications for a comment which was deleted.
+    pub fn delete_reply(comment_id: CommentId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(
+            notification::table
+                .filter(notification::comment_id.eq(comment_id))
+                .filter(notification::report_id.is_null()),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn list(
+        user: &LocalUserView,
+        params: &ListNotificationsParams,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ApiNotification>> {
+        Self::list_inner(user.person.id, params, false, context)
+    }
+
+    /// Unread notifications which were not sent by email yet.
+    pub fn list_for_email(
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ApiNotification>> {
+        let params = ListNotificationsParams {
+            unread_only: Some(true),
+            ..Default::default()
+        };
+        Self::list_inner(person_id, &params, true, context)
+    }
+
+    /// Called after the email digest was sent successfully, so that each notification is only
+    /// included in a single digest.
+    pub fn mark_as_emailed(ids: Vec<NotificationId>, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(notification::table.filter(notification::id.eq_any(ids)))
+            .set(notification::emailed.eq(true))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    fn list_inner(
+        person_id: PersonId,
+        params: &ListNotificationsParams,
+        not_emailed_only: bool,
+        context: &IbisContext,
+    ) -> BackendResult<Vec<ApiNotification>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = notification::table
+            .left_join(article::table)
+            .left_join(conflict::table.on(notification::conflict_id.eq(conflict::id.nullable())))
+            .left_join(comment::table.on(notification::comment_id.eq(comment::id.nullable())))
+            .left_join(edit::table.on(notification::edit_id.eq(edit::id.nullable())))
+            .left_join(report::table.on(notification::report_id.eq(report::id.nullable())))
+            .left_join(
+                registration_application::table.on(notification::registration_application_id
+                    .eq(registration_application::id.nullable())),
             )
-            .filter(parent_comment.field(comment::creator_id).eq(user.person.id))
-            .filter(comment::creator_id.ne(user.person.id))
-            .filter(not(comment::deleted))
-            .filter(not(comment::read_by_parent_creator))
-            .order_by(comment::published.desc())
+            // creator of the comment or edit, or the user who applied for registration
+            .left_join(
+                person::table.on(comment::creator_id
+                    .eq(person::id)
+                    .or(edit::creator_id.eq(person::id))
+                    .or(registration_application::person_id.eq(person::id))),
+            )
+            .filter(notification::person_id.eq(person_id))
             .select((
-                comment::all_columns,
-                person::all_columns,
-                article::all_columns,
+                notification::all_columns,
+                article::all_columns.nullable(),
+                conflict::all_columns.nullable(),
+                comment::all_columns.nullable(),
+                edit::all_columns.nullable(),
+                person::all_columns.nullable(),
+                report::all_columns.nullable(),
+                registration_application::all_columns.nullable(),
             ))
-            .get_results::<CommentViewWithArticle>(conn.deref_mut())?
-            .into_iter();
-        notifications.extend(comment_replies.map(Notification::Reply));
-
-        // new articles requiring approval
-        if check_is_admin(user).is_ok() {
-            let articles = article::table
-                .group_by(article::dsl::id)
-                .filter(article::dsl::approved.eq(false))
-                .select(article::all_columns)
-                .get_results(&mut conn)?
-                .into_iter();
-            notifications.extend(articles.map(Notification::ArticleApprovalRequired))
+            .order(notification::id.desc())
+            .limit(page_limit(params.limit))
+            .into_boxed();
+        if params.unread_only.unwrap_or_default() {
+            query = query.filter(not(notification::read));
+        }
+        if not_emailed_only {
+            query = query.filter(not(notification::emailed));
         }
-        notifications.sort_by(|a, b| a.published().cmp(b.published()));
+        if let Some(cursor) = params.cursor {
+            query = query.filter(notification::id.lt(cursor));
+        }
+        Ok(query
+            .get_results::<NotificationTuple>(conn.deref_mut())?
+            .into_iter()
+            .filter_map(Self::api_notification)
+            .collect())
+    }
 
-        Ok(notifications)
+    fn api_notification(tuple: NotificationTuple) -> Option<ApiNotification> {
+        use Notification::*;
+        let (notification, article, conflict, comment, edit, creator, report, application) = tuple;
+        let data = match (application, article) {
+            (Some(application), _) => RegistrationApplication(RegistrationApplicationView {
+                application,
+                person: creator?,
+            }),
+            (None, Some(article)) => match (report, conflict, comment, edit, creator) {
+                (Some(report), _, comment, edit, creator) => Report(ReportView {
+                    report,
+                    article,
+                    edit,
+                    comment,
+                    creator,
+                }),
+                (_, Some(conflict), _, _, _) => EditConflict {
+                    conflict_id: conflict.id,
+                    summary: conflict.summary,
+                    article,
+                },
+                (_, _, Some(comment), _, Some(creator)) => Reply(CommentViewWithArticle {
+                    comment,
+                    creator,
+                    article,
+                }),
+                (_, _, _, Some(edit), Some(creator)) => ArticleEdited(EditView {
+                    edit,
+                    article,
+                    creator,
+                }),
+                _ => ArticleApprovalRequired(article),
+            },
+            (None, None) => return None,
+        };
+        Some(ApiNotification {
+            id: notification.id,
+            read: notification.read,
+            published: notification.published,
+            data,
+        })
     }
 
-    pub fn count(user: &LocalUserView, context: &Data<IbisContext>) -> BackendResult<i64> {
+    /// Number of unread notifications
+    pub fn count(user: &LocalUserView, context: &IbisContext) -> BackendResult<i64> {
         let mut conn = context.db_pool.get()?;
-        let mut num = 0;
-        // edit conflicts
-        let conflicts = conflict::table
-            .filter(conflict::dsl::creator_id.eq(user.person.id))
-            .select(count(conflict::id))
-            .first::<i64>(conn.deref_mut())
-            .unwrap_or(0);
-        num += conflicts;
+        Ok(notification::table
+            .filter(notification::person_id.eq(user.person.id))
+            .filter(not(notification::read))
+            .select(count(notification::id))
+            .first(conn.deref_mut())?)
+    }
 
-        // comment replies
-        let comment_replies = comment::table
-            .inner_join(
-                parent_comment.on(parent_comment
-                    .field(comment::id)
-                    .nullable()
-                    .eq(comment::parent_id)),
-            )
-            .filter(parent_comment.field(comment::creator_id).eq(user.person.id))
-            .filter(comment::creator_id.ne(user.person.id))
-            .filter(not(comment::deleted))
-            .filter(not(comment::read_by_parent_creator))
-            .select(count(comment::id))
-            .first::<i64>(conn.deref_mut())
-            .unwrap_or(0);
-        num += comment_replies;
-
-        // new articles requiring approval
-        if check_is_admin(user).is_ok() {
-            let articles = article::table
-                .group_by(article::dsl::id)
-                .filter(article::dsl::approved.eq(false))
-                .select(count(article::id))
-                .first::<i64>(conn.deref_mut())
-                .unwrap_or(0);
-            num += articles;
-        }
+    pub fn mark_as_read(
+        id: NotificationId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(
+            notification::table
+                .find(id)
+                .filter(notification::person_id.eq(person_id)),
+        )
+        .set(notification::read.eq(true))
+        .get_result::<Self>(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Mark the reply notification for a comment as read.
+    pub fn mark_reply_as_read(
+        comment_id: CommentId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(
+            notification::table
+                .filter(notification::comment_id.eq(comment_id))
+                .filter(notification::report_id.is_null())
+                .filter(notification::person_id.eq(person_id)),
+        )
+        .set(notification::read.eq(true))
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
 
-        Ok(num)
+    pub fn mark_all_as_read(person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(
+            notification::table
+                .filter(notification::person_id.eq(person_id))
+                .filter(not(notification::read)),
+        )
+        .set(notification::read.eq(true))
+        .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn delete(
+        id: NotificationId,
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(
+            notification::table
+                .find(id)
+                .filter(notification::person_id.eq(person_id)),
+        )
+        .get_result::<Self>(conn.deref_mut())?;
+        Ok(())
     }
 }
@@ -0,0 +1,48 @@
+use super::{schema::oauth_account, IbisContext};
+use crate::{backend::utils::error::BackendResult, common::newtypes::PersonId};
+use chrono::{DateTime, Utc};
+use diesel::{insert_into, ExpressionMethods, OptionalExtension, QueryDsl, Queryable, RunQueryDsl};
+use std::ops::DerefMut;
+
+/// Account at an OpenID Connect provider which is linked to a local user, identified by the
+/// subject claim of the provider.
+#[derive(Clone, Debug, Queryable)]
+#[diesel(table_name = oauth_account, check_for_backend(diesel::pg::Pg))]
+pub struct DbOAuthAccount {
+    pub id: i32,
+    pub person_id: PersonId,
+    pub provider: String,
+    pub subject: String,
+    pub published: DateTime<Utc>,
+}
+
+impl DbOAuthAccount {
+    pub fn create(
+        person_id: PersonId,
+        provider: &str,
+        subject: &str,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(oauth_account::table)
+            .values((
+                oauth_account::person_id.eq(person_id),
+                oauth_account::provider.eq(provider),
+                oauth_account::subject.eq(subject),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read(
+        provider: &str,
+        subject: &str,
+        context: &IbisContext,
+    ) -> BackendResult<Option<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(oauth_account::table
+            .filter(oauth_account::provider.eq(provider))
+            .filter(oauth_account::subject.eq(subject))
+            .get_result(conn.deref_mut())
+            .optional()?)
+    }
+}
@@ -0,0 +1,79 @@
+use super::{notifications::DbNotification, schema::registration_application, IbisContext};
+use crate::{
+    backend::utils::error::BackendResult,
+    common::{
+        newtypes::{PersonId, RegistrationApplicationId},
+        registration_application::DbRegistrationApplication,
+    },
+};
+use anyhow::anyhow;
+use diesel::{insert_into, update, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use std::ops::DerefMut;
+
+impl DbRegistrationApplication {
+    /// Store the answer of a new user, and notify local admins so that they can review it.
+    pub fn create(
+        person_id: PersonId,
+        answer: String,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let application: Self = insert_into(registration_application::table)
+            .values((
+                registration_application::person_id.eq(person_id),
+                registration_application::answer.eq(answer),
+            ))
+            .get_result(conn.deref_mut())?;
+        DbNotification::notify_registration_application(&application, context)?;
+        Ok(application)
+    }
+
+    pub fn read(id: RegistrationApplicationId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(registration_application::table
+            .find(id)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Accept or deny the application, and remove the corresponding notifications of all admins.
+    pub fn update(
+        id: RegistrationApplicationId,
+        accepted: bool,
+        admin_id: PersonId,
+        deny_reason: Option<String>,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let application = update(registration_application::table.find(id))
+            .set((
+                registration_application::accepted.eq(accepted),
+                registration_application::admin_id.eq(admin_id),
+                registration_application::deny_reason.eq(deny_reason),
+            ))
+            .get_result(conn.deref_mut())?;
+        DbNotification::delete_registration_application(id, context)?;
+        Ok(application)
+    }
+
+    /// Users who registered with an application can only login after it was accepted. Users
+    /// without application are always allowed.
+    pub fn check_accepted(person_id: PersonId, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let application: Option<Self> = registration_application::table
+            .filter(registration_application::person_id.eq(person_id))
+            .get_result(conn.deref_mut())
+            .optional()?;
+        match application {
+            None => Ok(()),
+            Some(a) if a.accepted => Ok(()),
+            Some(a) if a.admin_id.is_none() => {
+                Err(anyhow!("Registration application is pending approval").into())
+            }
+            Some(a) => Err(anyhow!(
+                "Registration application was denied: {}",
+                a.deny_reason.unwrap_or_default()
+            )
+            .into()),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::{notifications::DbNotification, schema::report, IbisContext};
+use crate::{
+    backend::utils::error::BackendResult,
+    common::{
+        newtypes::{ArticleId, CommentId, EditId, PersonId, ReportId},
+        report::DbReport,
+    },
+};
+use diesel::{insert_into, ExpressionMethods, Insertable, QueryDsl, RunQueryDsl};
+use std::ops::DerefMut;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = report, check_for_backend(diesel::pg::Pg))]
+pub struct DbReportForm {
+    pub creator_id: PersonId,
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+    pub ap_id: String,
+    pub local: bool,
+}
+
+impl DbReport {
+    /// Store a new report, and notify local admins about it.
+    pub fn create(form: DbReportForm, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let report: Self = insert_into(report::table)
+            .values(form)
+            .get_result(conn.deref_mut())?;
+        DbNotification::notify_report(&report, context)?;
+        Ok(report)
+    }
+
+    pub fn read(id: ReportId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(report::table.find(id).get_result(conn.deref_mut())?)
+    }
+
+    /// Close the report, and remove the corresponding notifications of all admins.
+    pub fn resolve(id: ReportId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let report = diesel::update(report::table.find(id))
+            .set(report::resolved.eq(true))
+            .get_result(conn.deref_mut())?;
+        DbNotification::delete_report(id, context)?;
+        Ok(report)
+    }
+}
@@ -1,5 +1,20 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_token (id) {
+        id -> Int4,
+        person_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scope_read -> Bool,
+        scope_edit -> Bool,
+        scope_comment -> Bool,
+        scope_admin -> Bool,
+        published -> Timestamptz,
+        last_used -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     article (id) {
         id -> Int4,
@@ -12,6 +27,32 @@ diesel::table! {
         protected -> Bool,
         approved -> Bool,
         published -> Timestamptz,
+        deleted -> Bool,
+    }
+}
+
+diesel::table! {
+    article_redirect (id) {
+        id -> Int4,
+        article_id -> Int4,
+        title -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    article_watch (id) {
+        id -> Int4,
+        article_id -> Int4,
+        person_id -> Int4,
+    }
+}
+
+diesel::table! {
+    captcha_answer (uuid) {
+        uuid -> Uuid,
+        answer -> Text,
+        published -> Timestamptz,
     }
 }
 
@@ -29,7 +70,6 @@ diesel::table! {
         deleted -> Bool,
         published -> Timestamptz,
         updated -> Nullable<Timestamptz>,
-        read_by_parent_creator -> Bool,
     }
 }
 
@@ -59,6 +99,25 @@ diesel::table! {
         previous_version_id -> Uuid,
         published -> Timestamptz,
         pending -> Bool,
+        snapshot -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    email_verification (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        token -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    federation_list (id) {
+        id -> Int4,
+        domain -> Text,
+        blocked -> Bool,
+        published -> Timestamptz,
     }
 }
 
@@ -116,6 +175,72 @@ diesel::table! {
         password_encrypted -> Text,
         person_id -> Int4,
         admin -> Bool,
+        email -> Nullable<Text>,
+        email_verified -> Bool,
+        email_notifications -> Bool,
+        password_changed -> Nullable<Timestamptz>,
+        totp_secret -> Nullable<Text>,
+        totp_enabled -> Bool,
+        totp_recovery_codes -> Array<Text>,
+        totp_last_step -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    login_session (id) {
+        id -> Int4,
+        person_id -> Int4,
+        user_agent -> Nullable<Text>,
+        published -> Timestamptz,
+        last_used -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    mod_log (id) {
+        id -> Int4,
+        mod_id -> Nullable<Int4>,
+        action -> Text,
+        target_person_id -> Nullable<Int4>,
+        target_article_id -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        expires -> Nullable<Timestamptz>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    notification (id) {
+        id -> Int4,
+        person_id -> Int4,
+        article_id -> Nullable<Int4>,
+        conflict_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        edit_id -> Nullable<Int4>,
+        read -> Bool,
+        published -> Timestamptz,
+        emailed -> Bool,
+        report_id -> Nullable<Int4>,
+        registration_application_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    oauth_account (id) {
+        id -> Int4,
+        person_id -> Int4,
+        provider -> Text,
+        subject -> Text,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    password_reset_request (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        token -> Text,
+        published -> Timestamptz,
     }
 }
 
@@ -135,29 +260,96 @@ diesel::table! {
         display_name -> Nullable<Varchar>,
         #[max_length = 1000]
         bio -> Nullable<Varchar>,
+        #[max_length = 255]
+        avatar -> Nullable<Varchar>,
+        #[max_length = 255]
+        banner -> Nullable<Varchar>,
+        banned -> Bool,
+        ban_expires -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    registration_application (id) {
+        id -> Int4,
+        person_id -> Int4,
+        answer -> Text,
+        accepted -> Bool,
+        admin_id -> Nullable<Int4>,
+        deny_reason -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    report (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        article_id -> Int4,
+        edit_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        reason -> Text,
+        #[max_length = 255]
+        ap_id -> Varchar,
+        local -> Bool,
+        resolved -> Bool,
+        published -> Timestamptz,
     }
 }
 
+diesel::joinable!(api_token -> person (person_id));
 diesel::joinable!(article -> instance (instance_id));
+diesel::joinable!(article_redirect -> article (article_id));
+diesel::joinable!(article_watch -> article (article_id));
+diesel::joinable!(article_watch -> person (person_id));
 diesel::joinable!(comment -> article (article_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(conflict -> article (article_id));
 diesel::joinable!(conflict -> person (creator_id));
 diesel::joinable!(edit -> article (article_id));
 diesel::joinable!(edit -> person (creator_id));
+diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(instance_follow -> instance (instance_id));
 diesel::joinable!(instance_follow -> person (follower_id));
 diesel::joinable!(local_user -> person (person_id));
+diesel::joinable!(login_session -> person (person_id));
+diesel::joinable!(mod_log -> article (target_article_id));
+diesel::joinable!(notification -> article (article_id));
+diesel::joinable!(notification -> comment (comment_id));
+diesel::joinable!(notification -> conflict (conflict_id));
+diesel::joinable!(notification -> edit (edit_id));
+diesel::joinable!(notification -> person (person_id));
+diesel::joinable!(notification -> registration_application (registration_application_id));
+diesel::joinable!(notification -> report (report_id));
+diesel::joinable!(oauth_account -> person (person_id));
+diesel::joinable!(password_reset_request -> local_user (local_user_id));
+diesel::joinable!(report -> article (article_id));
+diesel::joinable!(report -> comment (comment_id));
+diesel::joinable!(report -> edit (edit_id));
+diesel::joinable!(report -> person (creator_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_token,
     article,
+    article_redirect,
+    article_watch,
+    captcha_answer,
     comment,
     conflict,
     edit,
+    email_verification,
+    federation_list,
     instance,
     instance_follow,
     instance_stats,
     jwt_secret,
     local_user,
+    login_session,
+    mod_log,
+    notification,
+    oauth_account,
+    password_reset_request,
     person,
+    registration_application,
+    report,
 );
@@ -1,33 +1,62 @@
 use crate::{
     backend::{
         database::{
-            schema::{instance, instance_follow, local_user, person},
+            schema::{
+                comment,
+                edit,
+                email_verification,
+                instance,
+                instance_follow,
+                local_user,
+                password_reset_request,
+                person,
+            },
             IbisContext,
         },
-        utils::{error::BackendResult, generate_keypair},
+        utils::{error::BackendResult, generate_keypair, media::ImageKind},
     },
     common::{
         instance::DbInstance,
         newtypes::PersonId,
-        user::{DbLocalUser, DbPerson, LocalUserView, UpdateUserParams},
+        user::{
+            DbLocalUser,
+            DbLoginSession,
+            DbPerson,
+            LocalUserView,
+            UpdateEmailParams,
+            UpdateUserParams,
+        },
         utils::http_protocol_str,
     },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use anyhow::anyhow;
 use bcrypt::{hash, DEFAULT_COST};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use diesel::{
+    define_sql_function,
+    delete,
+    dsl::exists,
     insert_into,
+    select,
+    sql_types::{Array, Text},
     AsChangeset,
+    BoolExpressionMethods,
+    Connection,
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
+    OptionalExtension,
+    PgArrayExpressionMethods,
+    PgConnection,
     PgTextExpressionMethods,
     QueryDsl,
     RunQueryDsl,
 };
 use std::ops::DerefMut;
 
+define_sql_function!(fn array_remove(array: Array<Text>, element: Text) -> Array<Text>);
+
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = local_user, check_for_backend(diesel::pg::Pg))]
 pub struct DbLocalUserForm {
@@ -48,6 +77,8 @@ pub struct DbPersonForm {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    pub avatar: Option<String>,
+    pub banner: Option<String>,
 }
 
 impl DbPerson {
@@ -90,6 +121,8 @@ impl DbPerson {
             local: true,
             display_name: None,
             bio: None,
+            avatar: None,
+            banner: None,
         };
 
         let person = insert_into(person::table)
@@ -145,17 +178,47 @@ impl DbPerson {
     }
 
     pub fn update_profile(
+        id: PersonId,
         params: &UpdateUserParams,
         context: &Data<IbisContext>,
-    ) -> BackendResult<()> {
+    ) -> BackendResult<DbPerson> {
         let mut conn = context.db_pool.get()?;
-        diesel::update(person::table.find(params.person_id))
+        Ok(diesel::update(person::table.find(id))
             .set((
                 person::dsl::display_name.eq(&params.display_name),
                 person::dsl::bio.eq(&params.bio),
             ))
-            .execute(conn.deref_mut())?;
-        Ok(())
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn update_image(
+        id: PersonId,
+        kind: ImageKind,
+        url: String,
+        context: &IbisContext,
+    ) -> BackendResult<DbPerson> {
+        let mut conn = context.db_pool.get()?;
+        let query = diesel::update(person::table.find(id));
+        Ok(match kind {
+            ImageKind::Avatar => query
+                .set(person::dsl::avatar.eq(url))
+                .get_result(conn.deref_mut())?,
+            ImageKind::Banner => query
+                .set(person::dsl::banner.eq(url))
+                .get_result(conn.deref_mut())?,
+        })
+    }
+
+    pub fn update_ban(
+        id: PersonId,
+        banned: bool,
+        expires: Option<DateTime<Utc>>,
+        context: &IbisContext,
+    ) -> BackendResult<DbPerson> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(person::table.find(id))
+            .set((person::banned.eq(banned), person::ban_expires.eq(expires)))
+            .get_result(conn.deref_mut())?)
     }
 
     pub fn read_local_from_name(
@@ -177,6 +240,20 @@ impl DbPerson {
         })
     }
 
+    pub fn read_local_from_id(id: PersonId, context: &IbisContext) -> BackendResult<LocalUserView> {
+        let mut conn = context.db_pool.get()?;
+        let (person, local_user) = person::table
+            .inner_join(local_user::table)
+            .filter(person::dsl::id.eq(id))
+            .get_result::<(DbPerson, DbLocalUser)>(conn.deref_mut())?;
+        let following = Self::read_following(person.id, context)?;
+        Ok(LocalUserView {
+            person,
+            local_user,
+            following,
+        })
+    }
+
     fn read_following(id_: PersonId, context: &IbisContext) -> BackendResult<Vec<DbInstance>> {
         use instance_follow::dsl::{follower_id, instance_id};
         let mut conn = context.db_pool.get()?;
@@ -211,8 +288,247 @@ impl DbPerson {
                 local: true,
                 display_name: None,
                 bio: None,
+                avatar: None,
+                banner: None,
             };
             DbPerson::create(&person_form, context)
         }
     }
+
+    /// Delete an account, but keep the content. Edits are reassigned to the ghost user, comments
+    /// are blanked and also reassigned. Moderation log entries are kept without the user. All
+    /// other data of the user such as notifications or local_user is removed by cascade.
+    pub fn delete(id: PersonId, context: &Data<IbisContext>) -> BackendResult<()> {
+        let ghost = DbPerson::ghost(context)?;
+        let mut conn = context.db_pool.get()?;
+        conn.deref_mut()
+            .transaction(|conn| DbPerson::delete_with_conn(&[id], ghost.id, conn))
+    }
+
+    /// Same as [DbPerson::delete] for multiple accounts, using an existing connection so that
+    /// it can be part of a larger transaction.
+    pub(crate) fn delete_with_conn(
+        ids: &[PersonId],
+        ghost_id: PersonId,
+        conn: &mut PgConnection,
+    ) -> BackendResult<()> {
+        diesel::update(edit::table.filter(edit::creator_id.eq_any(ids)))
+            .set(edit::creator_id.eq(ghost_id))
+            .execute(conn)?;
+        diesel::update(comment::table.filter(comment::creator_id.eq_any(ids)))
+            .set((
+                comment::creator_id.eq(ghost_id),
+                comment::content.eq(""),
+                comment::deleted.eq(true),
+            ))
+            .execute(conn)?;
+        delete(person::table.filter(person::id.eq_any(ids))).execute(conn)?;
+        Ok(())
+    }
+}
+
+impl DbLocalUser {
+    /// Change the email address. If it is different from before, it needs to be verified again.
+    pub fn update_email(
+        local_user: &DbLocalUser,
+        params: &UpdateEmailParams,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        // check manually instead of relying on the unique constraint, whose error would reveal
+        // that the address is registered
+        if let Some(email) = &params.email {
+            let in_use = select(exists(
+                local_user::table
+                    .filter(local_user::email.eq(email))
+                    .filter(local_user::id.ne(local_user.id)),
+            ))
+            .get_result::<bool>(conn.deref_mut())?;
+            if in_use {
+                return Err(anyhow!("Email address can't be used").into());
+            }
+        }
+        let email_verified = local_user.email_verified && local_user.email == params.email;
+        diesel::update(local_user::table.find(local_user.id))
+            .set((
+                local_user::email.eq(&params.email),
+                local_user::email_verified.eq(email_verified),
+                local_user::email_notifications.eq(params.email_notifications),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    pub fn create_email_verification(
+        local_user: &DbLocalUser,
+        token: &str,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(email_verification::table)
+            .values((
+                email_verification::local_user_id.eq(local_user.id),
+                email_verification::token.eq(token),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Mark the email as verified using the token from verification email. Tokens can only be
+    /// used once and expire after one day.
+    pub fn verify_email(token: &str, context: &IbisContext) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let local_user_id: i32 = delete(
+            email_verification::table
+                .filter(email_verification::token.eq(token))
+                .filter(email_verification::published.gt(Utc::now() - TimeDelta::days(1))),
+        )
+        .returning(email_verification::local_user_id)
+        .get_result(conn.deref_mut())
+        .optional()?
+        .ok_or(anyhow!("Invalid or expired email verification token"))?;
+        diesel::update(local_user::table.find(local_user_id))
+            .set(local_user::email_verified.eq(true))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Read user by email address, only if the address was verified.
+    pub fn read_from_email(email: &str, context: &IbisContext) -> BackendResult<Option<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(local_user::table
+            .filter(local_user::email.eq(email))
+            .filter(local_user::email_verified)
+            .get_result(conn.deref_mut())
+            .optional()?)
+    }
+
+    pub fn create_password_reset(
+        local_user: &DbLocalUser,
+        token: &str,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(password_reset_request::table)
+            .values((
+                password_reset_request::local_user_id.eq(local_user.id),
+                password_reset_request::token.eq(token),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Set a new password using the token from password reset email. Tokens can only be used
+    /// once and expire after one hour.
+    pub fn reset_password(
+        token: &str,
+        new_password: &str,
+        context: &IbisContext,
+    ) -> BackendResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let local_user_id: i32 = delete(
+            password_reset_request::table
+                .filter(password_reset_request::token.eq(token))
+                .filter(password_reset_request::published.gt(Utc::now() - TimeDelta::hours(1))),
+        )
+        .returning(password_reset_request::local_user_id)
+        .get_result(conn.deref_mut())
+        .optional()?
+        .ok_or(anyhow!("Invalid or expired password reset token"))?;
+        Self::update_password(local_user_id, new_password, context)?;
+        Ok(())
+    }
+
+    /// Change the password, which also invalidates all existing login tokens.
+    pub fn update_password(
+        local_user_id: i32,
+        new_password: &str,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let local_user: Self = diesel::update(local_user::table.find(local_user_id))
+            .set((
+                local_user::password_encrypted.eq(hash(new_password, DEFAULT_COST)?),
+                local_user::password_changed.eq(Utc::now()),
+            ))
+            .get_result(conn.deref_mut())?;
+        // login tokens are invalid now, so remove the sessions
+        DbLoginSession::delete_all(local_user.person_id, context)?;
+        Ok(local_user)
+    }
+
+    /// Change the settings for two-factor authentication. The secret is stored before totp is
+    /// enabled, so that the user can confirm it with a valid token.
+    pub fn update_totp(
+        local_user_id: i32,
+        secret: Option<String>,
+        enabled: bool,
+        recovery_codes: Vec<String>,
+        context: &IbisContext,
+    ) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(diesel::update(local_user::table.find(local_user_id))
+            .set((
+                local_user::totp_secret.eq(secret),
+                local_user::totp_enabled.eq(enabled),
+                local_user::totp_recovery_codes.eq(recovery_codes),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Store the time step of a totp token which was used for login. Returns false if a token
+    /// with the same or a later time step was used before.
+    pub fn update_totp_last_step(
+        local_user_id: i32,
+        step: i64,
+        context: &IbisContext,
+    ) -> BackendResult<bool> {
+        let mut conn = context.db_pool.get()?;
+        let updated = diesel::update(
+            local_user::table.find(local_user_id).filter(
+                local_user::totp_last_step
+                    .is_null()
+                    .or(local_user::totp_last_step.lt(step)),
+            ),
+        )
+        .set(local_user::totp_last_step.eq(step))
+        .execute(conn.deref_mut())?;
+        Ok(updated == 1)
+    }
+
+    /// Remove a recovery code after it was used for login. Returns false if the code doesn't
+    /// exist, or was already used.
+    pub fn remove_recovery_code(
+        local_user_id: i32,
+        hash: &str,
+        context: &IbisContext,
+    ) -> BackendResult<bool> {
+        let mut conn = context.db_pool.get()?;
+        let updated = diesel::update(
+            local_user::table
+                .find(local_user_id)
+                .filter(local_user::totp_recovery_codes.contains(vec![hash])),
+        )
+        .set(
+            local_user::totp_recovery_codes.eq(array_remove(local_user::totp_recovery_codes, hash)),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(updated == 1)
+    }
+
+    pub fn read_from_person(person_id: PersonId, context: &IbisContext) -> BackendResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(local_user::table
+            .filter(local_user::person_id.eq(person_id))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Users with verified email address who want to receive notifications by email.
+    pub fn list_for_email_notifications(context: &IbisContext) -> BackendResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(local_user::table
+            .filter(local_user::email_verified)
+            .filter(local_user::email_notifications)
+            .get_results(conn.deref_mut())?)
+    }
 }
@@ -8,7 +8,10 @@ use crate::{
             send_activity_to_instance,
         },
         generate_activity_id,
-        utils::error::{BackendError, BackendResult},
+        utils::{
+            error::{BackendError, BackendResult},
+            validate::validate_not_banned,
+        },
     },
     common::{comment::DbComment, instance::DbInstance, user::DbPerson},
 };
@@ -77,9 +80,11 @@ impl ActivityHandler for CreateOrUpdateComment {
         self.actor.inner()
     }
 
-    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         verify_domains_match(&self.id, self.object.id.inner())?;
         verify_domains_match(&self.id, self.actor.inner())?;
+        let creator = self.object.attributed_to.dereference(context).await?;
+        validate_not_banned(&creator)?;
         Ok(())
     }
 
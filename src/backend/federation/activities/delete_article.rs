@@ -0,0 +1,100 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        utils::{
+            error::{BackendError, BackendResult},
+            generate_activity_id,
+        },
+    },
+    common::{article::DbArticle, instance::DbInstance},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::DeleteType, object::TombstoneType},
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::ActivityHandler,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Deleted articles are sent as tombstone, so that the activity can be distinguished from
+/// [DeleteComment](super::comment::delete_comment::DeleteComment) which only contains the
+/// object id.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArticleTombstone {
+    pub(crate) id: ObjectId<DbArticle>,
+    #[serde(rename = "type")]
+    pub(crate) kind: TombstoneType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteArticle {
+    pub(crate) actor: ObjectId<DbInstance>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ArticleTombstone,
+    #[serde(rename = "type")]
+    pub(crate) kind: DeleteType,
+    pub(crate) id: Url,
+}
+
+impl DeleteArticle {
+    pub fn new(
+        article: &DbArticle,
+        local_instance: &DbInstance,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<Self> {
+        let id = generate_activity_id(context)?;
+        Ok(DeleteArticle {
+            actor: local_instance.ap_id.clone(),
+            to: local_instance.follower_ids(context)?,
+            object: ArticleTombstone {
+                id: article.ap_id.clone(),
+                kind: Default::default(),
+            },
+            kind: Default::default(),
+            id,
+        })
+    }
+
+    /// Sent from article origin instance
+    pub async fn send(article: &DbArticle, context: &Data<IbisContext>) -> BackendResult<()> {
+        debug_assert!(article.local);
+        let local_instance = DbInstance::read_local(context)?;
+        let activity = Self::new(article, &local_instance, context)?;
+        local_instance
+            .send_to_followers(activity, vec![], context)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for DeleteArticle {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        verify_domains_match(self.actor.inner(), self.object.id.inner())?;
+        Ok(())
+    }
+
+    /// Received on article follower instances (where article is always remote)
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let article = DbArticle::read_from_ap_id(&self.object.id, context)?;
+        DbArticle::update_deleted(article.id, true, context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::send_activity,
+        utils::{
+            error::{BackendError, BackendResult},
+            generate_activity_id,
+        },
+    },
+    common::{instance::DbInstance, user::DbPerson},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::DeleteType, actor::PersonType, object::TombstoneType, public},
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::ActivityHandler,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The former type is required to distinguish this from
+/// [ArticleTombstone](super::delete_article::ArticleTombstone).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonTombstone {
+    pub(crate) id: ObjectId<DbPerson>,
+    #[serde(rename = "type")]
+    pub(crate) kind: TombstoneType,
+    pub(crate) former_type: PersonType,
+}
+
+/// Sent when a local user deletes their account, so that remote instances remove it as well.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteUser {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: PersonTombstone,
+    #[serde(rename = "type")]
+    pub(crate) kind: DeleteType,
+    pub(crate) id: Url,
+}
+
+impl DeleteUser {
+    /// Sent to all known instances. This needs to happen before the account is deleted locally,
+    /// so that the activity can still be signed.
+    pub async fn send(person: &DbPerson, context: &Data<IbisContext>) -> BackendResult<()> {
+        debug_assert!(person.local);
+        let inboxes = DbInstance::list(context)?
+            .iter()
+            .map(|i| i.inbox_url.parse())
+            .collect::<Result<Vec<Url>, _>>()?;
+        let id = generate_activity_id(context)?;
+        let activity = DeleteUser {
+            actor: person.ap_id.clone(),
+            to: vec![public()],
+            object: PersonTombstone {
+                id: person.ap_id.clone(),
+                kind: Default::default(),
+                former_type: Default::default(),
+            },
+            kind: Default::default(),
+            id,
+        };
+        send_activity(person, activity, inboxes, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for DeleteUser {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        // users can only delete their own account
+        verify_urls_match(self.actor.inner(), self.object.id.inner())?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let person = DbPerson::read_from_ap_id(&self.object.id, context)?;
+        DbPerson::delete(person.id, context)?;
+        Ok(())
+    }
+}
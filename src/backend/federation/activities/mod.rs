@@ -19,10 +19,16 @@ pub mod accept;
 pub mod announce;
 pub mod comment;
 pub mod create_article;
+pub mod delete_article;
+pub mod delete_user;
 pub mod follow;
 pub mod reject;
+pub mod report;
+pub mod undo_delete_article;
+pub mod update_instance;
 pub mod update_local_article;
 pub mod update_remote_article;
+pub mod update_user;
 
 pub async fn submit_article_update(
     new_text: String,
@@ -19,6 +19,7 @@ use activitypub_federation::{
     protocol::helpers::deserialize_one_or_many,
     traits::ActivityHandler,
 };
+use log::warn;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -89,7 +90,16 @@ impl ActivityHandler for RejectEdit {
             article_id: article.id,
             previous_version_id: self.object.previous_version,
         };
-        DbConflict::create(&form, context)?;
+        let conflict = DbConflict::create(&form, context)?;
+        // If the edit can be merged automatically, the conflict and its notification are
+        // deleted again. This is done in the background so that a failure doesn't cause the
+        // activity to be retried, which would store the conflict twice.
+        let context_ = context.reset_request_count();
+        tokio::spawn(async move {
+            if let Err(e) = conflict.to_api_conflict(false, &context_).await {
+                warn!("Failed to merge edit conflict {}: {e}", conflict.id.0);
+            }
+        });
         Ok(())
     }
 }
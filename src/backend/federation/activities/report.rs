@@ -0,0 +1,123 @@
+use crate::{
+    backend::{
+        database::{report::DbReportForm, IbisContext},
+        federation::send_activity,
+        utils::error::{BackendError, BackendResult},
+    },
+    common::{
+        article::{DbArticle, DbEdit},
+        comment::DbComment,
+        instance::DbInstance,
+        report::DbReport,
+        user::DbPerson,
+    },
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::FlagType,
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::ActivityHandler,
+};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent by a user to report an article, edit or comment to the instance where the article is
+/// hosted, so that its admins can take action.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    /// Id of the reported article, edit or comment
+    pub(crate) object: Url,
+    /// Reason for the report
+    pub(crate) summary: String,
+    #[serde(rename = "type")]
+    pub(crate) kind: FlagType,
+    pub(crate) id: Url,
+}
+
+impl Report {
+    pub async fn send(
+        report: &DbReport,
+        article: &DbArticle,
+        creator: &DbPerson,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<()> {
+        let object = if let Some(comment_id) = report.comment_id {
+            DbComment::read(comment_id, context)?.ap_id.into_inner()
+        } else if let Some(edit_id) = report.edit_id {
+            DbEdit::read_from_id(edit_id, context)?.ap_id.into_inner()
+        } else {
+            article.ap_id.clone().into_inner()
+        };
+        let instance = DbInstance::read(article.instance_id, context)?;
+        let activity = Report {
+            actor: creator.ap_id.clone(),
+            to: vec![instance.ap_id.into_inner()],
+            object,
+            summary: report.reason.clone(),
+            kind: Default::default(),
+            id: Url::parse(&report.ap_id)?,
+        };
+        send_activity(
+            creator,
+            activity,
+            vec![Url::parse(&instance.inbox_url)?],
+            context,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Report {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let object = self.object.clone();
+        let (article_id, edit_id, comment_id) =
+            if let Ok(comment) = DbComment::read_from_ap_id(&object.clone().into(), context) {
+                (comment.article_id, None, Some(comment.id))
+            } else if let Ok(edit) = DbEdit::read_from_ap_id(&object.clone().into(), context) {
+                (edit.article_id, Some(edit.id), None)
+            } else {
+                let article = DbArticle::read_from_ap_id(&object.into(), context)?;
+                (article.id, None, None)
+            };
+        let article = DbArticle::read(article_id, context)?;
+        if !article.local {
+            return Err(anyhow!("Reports can only be received for local articles").into());
+        }
+        let creator = self.actor.dereference(context).await?;
+        let form = DbReportForm {
+            creator_id: creator.id,
+            article_id,
+            edit_id,
+            comment_id,
+            reason: self.summary,
+            ap_id: self.id.to_string(),
+            local: false,
+        };
+        DbReport::create(form, context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use super::delete_article::DeleteArticle;
+use crate::{
+    backend::{
+        database::IbisContext,
+        utils::{
+            error::{BackendError, BackendResult},
+            generate_activity_id,
+        },
+    },
+    common::{article::DbArticle, instance::DbInstance},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::UndoType,
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::ActivityHandler,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoDeleteArticle {
+    pub(crate) actor: ObjectId<DbInstance>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: DeleteArticle,
+    #[serde(rename = "type")]
+    pub(crate) kind: UndoType,
+    pub(crate) id: Url,
+}
+
+impl UndoDeleteArticle {
+    /// Sent from article origin instance
+    pub async fn send(article: &DbArticle, context: &Data<IbisContext>) -> BackendResult<()> {
+        debug_assert!(article.local);
+        let local_instance = DbInstance::read_local(context)?;
+        let id = generate_activity_id(context)?;
+        let object = DeleteArticle::new(article, &local_instance, context)?;
+        let activity = UndoDeleteArticle {
+            actor: local_instance.ap_id.clone(),
+            to: local_instance.follower_ids(context)?,
+            object,
+            kind: Default::default(),
+            id,
+        };
+        local_instance
+            .send_to_followers(activity, vec![], context)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UndoDeleteArticle {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_urls_match(self.actor.inner(), self.object.actor.inner())?;
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        verify_domains_match(self.actor.inner(), self.object.object.id.inner())?;
+        Ok(())
+    }
+
+    /// Received on article follower instances (where article is always remote)
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let article = DbArticle::read_from_ap_id(&self.object.object.id, context)?;
+        DbArticle::update_deleted(article.id, false, context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::objects::instance::ApubInstance,
+        utils::{
+            error::{BackendError, BackendResult},
+            generate_activity_id,
+        },
+    },
+    common::instance::DbInstance,
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::UpdateType, public},
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::{ActivityHandler, Object},
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent when the admin changes name or topic of the local instance, so that followers don't show
+/// outdated instance metadata.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInstance {
+    pub(crate) actor: ObjectId<DbInstance>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ApubInstance,
+    #[serde(rename = "type")]
+    pub(crate) kind: UpdateType,
+    pub(crate) id: Url,
+}
+
+impl UpdateInstance {
+    pub async fn send(instance: DbInstance, context: &Data<IbisContext>) -> BackendResult<()> {
+        debug_assert!(instance.local);
+        let id = generate_activity_id(context)?;
+        let activity = UpdateInstance {
+            actor: instance.ap_id.clone(),
+            to: vec![public()],
+            object: instance.clone().into_json(context).await?,
+            kind: Default::default(),
+            id,
+        };
+        instance
+            .send_to_followers(activity, vec![], context)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UpdateInstance {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        // instances can only update themselves
+        verify_urls_match(self.actor.inner(), self.object.id.inner())?;
+        DbInstance::verify(&self.object, self.actor.inner(), context).await?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // only update the metadata, from_json would also resync all articles
+        DbInstance::update_remote(
+            &self.object.id,
+            self.object.name,
+            self.object.summary,
+            self.object.public_key.public_key_pem,
+            context,
+        )?;
+        Ok(())
+    }
+}
@@ -16,6 +16,7 @@ use activitypub_federation::{
     protocol::helpers::deserialize_one_or_many,
     traits::{ActivityHandler, Object},
 };
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -70,7 +71,12 @@ impl ActivityHandler for UpdateLocalArticle {
         self.actor.inner()
     }
 
-    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        if let Ok(article) = DbArticle::read_from_ap_id(&self.object.id, context) {
+            if article.deleted {
+                return Err(anyhow!("Article is deleted").into());
+            }
+        }
         Ok(())
     }
 
@@ -9,6 +9,7 @@ use crate::{
         utils::{
             error::{BackendError, BackendResult},
             generate_activity_id,
+            validate::validate_not_banned,
         },
     },
     common::{
@@ -83,6 +84,8 @@ impl ActivityHandler for UpdateRemoteArticle {
     async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         let article = DbArticle::read_from_ap_id(&self.object.object, context)?;
         can_edit_article(&article, false)?;
+        let creator = self.object.attributed_to.dereference(context).await?;
+        validate_not_banned(&creator)?;
         Ok(())
     }
 
@@ -0,0 +1,93 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::{objects::user::ApubUser, send_activity},
+        utils::{
+            error::{BackendError, BackendResult},
+            generate_activity_id,
+        },
+    },
+    common::{instance::DbInstance, user::DbPerson},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::UpdateType, public},
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::{ActivityHandler, Object},
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent when a local user changes their profile, so that remote instances don't show outdated
+/// displayname or bio.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUser {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ApubUser,
+    #[serde(rename = "type")]
+    pub(crate) kind: UpdateType,
+    pub(crate) id: Url,
+}
+
+impl UpdateUser {
+    /// Sent to followers of the local instance, and to all remote instances with articles which
+    /// the user has edited or commented on.
+    pub async fn send(person: DbPerson, context: &Data<IbisContext>) -> BackendResult<()> {
+        debug_assert!(person.local);
+        let local_instance = DbInstance::read_local(context)?;
+        let mut inboxes: Vec<_> = DbInstance::read_followers(local_instance.id, context)?
+            .iter()
+            .map(|f| f.inbox_url())
+            .collect();
+        for instance in DbInstance::read_with_content_from(person.id, context)? {
+            inboxes.push(instance.inbox_url.parse()?);
+        }
+        inboxes.sort();
+        inboxes.dedup();
+
+        let id = generate_activity_id(context)?;
+        let activity = UpdateUser {
+            actor: person.ap_id.clone(),
+            to: vec![public()],
+            object: person.clone().into_json(context).await?,
+            kind: Default::default(),
+            id,
+        };
+        send_activity(&person, activity, inboxes, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UpdateUser {
+    type DataType = IbisContext;
+    type Error = BackendError;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        // users can only update their own profile
+        verify_urls_match(self.actor.inner(), self.object.id.inner())?;
+        DbPerson::verify(&self.object, self.actor.inner(), context).await?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        DbPerson::from_json(self.object, context).await?;
+        Ok(())
+    }
+}
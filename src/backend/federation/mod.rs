@@ -1,6 +1,6 @@
 use super::utils::error::BackendResult;
 use crate::{
-    backend::{database::IbisContext, utils::config::IbisConfig},
+    backend::database::IbisContext,
     common::{instance::DbInstance, user::DbPerson},
 };
 use activities::announce::AnnounceActivity;
@@ -52,28 +52,20 @@ pub async fn send_activity_to_instance(
 }
 
 #[derive(Clone)]
-pub struct VerifyUrlData(pub IbisConfig);
+pub struct VerifyUrlData(pub IbisContext);
 
 #[async_trait]
 impl UrlVerifier for VerifyUrlData {
-    /// Check domain against allowlist and blocklist from config file.
+    /// Check domain against allowlist and blocklist. These are cached in memory and refreshed
+    /// when admins change them, so that changes take effect immediately.
     async fn verify(&self, url: &Url) -> Result<(), ActivityPubError> {
-        let domain = url.domain().expect("url has domain");
-        if let Some(allowlist) = &self.0.federation.allowlist {
-            let allowlist = allowlist.split(',').collect::<Vec<_>>();
-            if !allowlist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is not allowed"
-                )));
-            }
+        // include port, so that the format is the same as in DbInstance.domain
+        let mut domain = url.host_str().expect("url has domain").to_string();
+        if let Some(port) = url.port() {
+            domain = format!("{domain}:{port}");
         }
-        if let Some(blocklist) = &self.0.federation.blocklist {
-            let blocklist = blocklist.split(',').collect::<Vec<_>>();
-            if blocklist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is blocked"
-                )));
-            }
+        if let Some(err) = self.0.federation_list.check(&domain) {
+            return Err(ActivityPubError::Other(err));
         }
         Ok(())
     }
@@ -1,7 +1,7 @@
 use crate::{
     backend::{
         database::{article::DbArticleForm, IbisContext},
-        federation::objects::edits_collection::DbEditCollection,
+        federation::objects::{edits_collection::DbEditCollection, user::ApubImage},
         utils::{error::BackendError, validate::validate_article_title},
     },
     common::{
@@ -36,6 +36,9 @@ pub struct ApubArticle {
     content: String,
     name: String,
     protected: bool,
+    /// Images which are embedded in the article
+    #[serde(default)]
+    attachment: Vec<ApubImage>,
 }
 
 #[async_trait::async_trait]
@@ -52,6 +55,11 @@ impl Object for DbArticle {
         Ok(article)
     }
 
+    async fn delete(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        DbArticle::update_deleted(self.id, true, context)?;
+        Ok(())
+    }
+
     async fn into_json(self, context: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
         let local_instance = DbInstance::read_local(context)?;
         Ok(ApubArticle {
@@ -61,6 +69,7 @@ impl Object for DbArticle {
             to: vec![public(), local_instance.followers_url()?],
             edits: self.edits_id()?,
             latest_version: self.latest_edit_version(context)?,
+            attachment: ApubImage::article_images(&self.text, context)?,
             content: self.text,
             name: self.title,
             protected: self.protected,
@@ -82,6 +91,7 @@ impl Object for DbArticle {
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
         let instance = json.attributed_to.dereference(context).await?;
+        ApubImage::cache_article_images(json.attachment, &[json.id.inner()], context);
         let mut form = DbArticleForm {
             title: json.name,
             text: json.content,
@@ -92,7 +102,13 @@ impl Object for DbArticle {
             approved: true,
         };
         form.title = validate_article_title(&form.title)?;
+        let previous = DbArticle::read_from_ap_id(&form.ap_id, context).ok();
         let article = DbArticle::create_or_update(form, context)?;
+        if let Some(previous) = previous {
+            if previous.title != article.title {
+                DbArticle::create_redirect(article.id, &previous.title, &article.title, context)?;
+            }
+        }
 
         json.edits.dereference(&article, context).await?;
 
@@ -1,15 +1,24 @@
+use super::collection_page::{
+    CollectionItems,
+    CollectionKind,
+    CollectionPage,
+    MAX_COLLECTION_PAGES,
+};
 use crate::{
     backend::{
-        database::IbisContext,
+        database::{page_limit, IbisContext},
         federation::objects::article::ApubArticle,
         utils::error::{BackendError, BackendResult},
     },
-    common::{article::DbArticle, utils::http_protocol_str},
+    common::{
+        article::{DbArticle, ListArticlesParams},
+        newtypes::ArticleId,
+        utils::http_protocol_str,
+    },
 };
 use activitypub_federation::{
     config::Data,
     fetch::collection_id::CollectionId,
-    kinds::collection::CollectionType,
     protocol::verification::verify_domains_match,
     traits::{Collection, Object},
 };
@@ -18,13 +27,14 @@ use log::warn;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// Local articles, most recently edited first.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArticleCollection {
-    pub r#type: CollectionType,
+    pub r#type: CollectionKind,
     pub id: Url,
-    pub total_items: i32,
-    pub items: Vec<ApubArticle>,
+    #[serde(flatten)]
+    pub items: CollectionItems<ApubArticle>,
 }
 
 #[derive(Clone, Debug)]
@@ -37,6 +47,35 @@ pub fn local_articles_url(domain: &str) -> BackendResult<CollectionId<DbArticleC
     ))?)
 }
 
+impl DbArticleCollection {
+    pub async fn read_local_page(
+        cursor: Option<i32>,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<CollectionPage<ApubArticle>> {
+        let params = ListArticlesParams {
+            only_local: Some(true),
+            cursor: cursor.map(ArticleId),
+            ..Default::default()
+        };
+        let local_articles = DbArticle::read_all(&params, context)?;
+        let last_id = local_articles.last().map(|a| a.id.0);
+        let articles = try_join_all(
+            local_articles
+                .into_iter()
+                .map(|a| a.into_json(context))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+        Ok(CollectionPage::new(
+            local_articles_url(&context.config.federation.domain)?.into(),
+            cursor,
+            articles,
+            last_id,
+            page_limit(params.limit),
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl Collection for DbArticleCollection {
     type Owner = ();
@@ -48,19 +87,16 @@ impl Collection for DbArticleCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
-        let local_articles = DbArticle::read_all(Some(true), None, context)?;
-        let articles = try_join_all(
-            local_articles
-                .into_iter()
-                .map(|a| a.into_json(context))
-                .collect::<Vec<_>>(),
-        )
-        .await?;
+        let items = CollectionItems::from_first_page(Self::read_local_page(None, context).await?);
+        // older versions expect type `Collection` for inline items
+        let r#type = match items {
+            CollectionItems::Inline { .. } => CollectionKind::Collection,
+            CollectionItems::Paged { .. } => CollectionKind::OrderedCollection,
+        };
         let collection = ArticleCollection {
-            r#type: Default::default(),
+            r#type,
             id: local_articles_url(&context.config.federation.domain)?.into(),
-            total_items: articles.len() as i32,
-            items: articles,
+            items,
         };
         Ok(collection)
     }
@@ -71,6 +107,7 @@ impl Collection for DbArticleCollection {
         _context: &Data<Self::DataType>,
     ) -> Result<(), Self::Error> {
         verify_domains_match(&json.id, expected_domain)?;
+        json.items.verify(expected_domain)?;
         Ok(())
     }
 
@@ -79,19 +116,34 @@ impl Collection for DbArticleCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
-        let articles = apub
-            .items
-            .into_iter()
-            .filter(|i| !i.id.is_local(context))
-            .map(|article| async {
-                let id = article.id.clone();
-                let res = DbArticle::from_json(article, context).await;
-                if let Err(e) = &res {
-                    warn!("Failed to synchronize article {id}: {e}");
-                }
-                res
-            });
-        join_all(articles).await;
+        let mut page = Some(apub.items.into_first_page(apub.id));
+        for _ in 0..MAX_COLLECTION_PAGES {
+            let Some(current) = page.take() else {
+                break;
+            };
+            page = current
+                .fetch_next(context)
+                .await
+                .map_err(|e| warn!("Failed to fetch articles page: {e}"))
+                .ok()
+                .flatten();
+            let articles = current
+                .ordered_items
+                .into_iter()
+                .filter(|i| !i.id.is_local(context))
+                .map(|article| async {
+                    let id = article.id.clone();
+                    let res = DbArticle::from_json(article, context).await;
+                    if let Err(e) = &res {
+                        warn!("Failed to synchronize article {id}: {e}");
+                    }
+                    res
+                });
+            join_all(articles).await;
+        }
+        if page.is_some() {
+            warn!("Stopped fetching articles after {MAX_COLLECTION_PAGES} pages");
+        }
 
         Ok(DbArticleCollection(()))
     }
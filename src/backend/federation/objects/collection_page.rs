@@ -0,0 +1,142 @@
+use crate::backend::{database::IbisContext, utils::error::BackendResult};
+use activitypub_federation::{
+    config::Data,
+    fetch::fetch_object_http,
+    kinds::collection::OrderedCollectionPageType,
+    protocol::verification::verify_domains_match,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use url::Url;
+
+/// Single page of an `OrderedCollection`. The first page is embedded in the collection, further
+/// pages need to be fetched by following `next`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionPage<T> {
+    pub r#type: OrderedCollectionPageType,
+    pub id: Url,
+    pub part_of: Url,
+    pub ordered_items: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<Url>,
+}
+
+/// Maximum number of pages which are fetched from a single remote collection, so that a malicious
+/// instance can't keep the receiver busy by serving endless `next` links.
+pub const MAX_COLLECTION_PAGES: usize = 100;
+
+/// Items of a collection. Older versions embedded all items directly instead of using pages, and
+/// can't read paged collections. So collections which fit into a single page are still served
+/// inline, only larger collections require the new format.
+// TODO: remove `Inline` in 0.3
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CollectionItems<T> {
+    Paged {
+        first: Box<CollectionPage<T>>,
+    },
+    Inline {
+        #[serde(rename = "totalItems")]
+        total_items: i32,
+        items: Vec<T>,
+    },
+}
+
+/// Article collections used to have type `Collection` before pagination was added.
+#[derive(Clone, Debug, Deserialize, Serialize, Default)]
+pub enum CollectionKind {
+    #[default]
+    OrderedCollection,
+    Collection,
+}
+
+impl<T> CollectionItems<T> {
+    pub fn from_first_page(first: CollectionPage<T>) -> Self {
+        if first.next.is_none() {
+            CollectionItems::Inline {
+                total_items: first.ordered_items.len() as i32,
+                items: first.ordered_items,
+            }
+        } else {
+            CollectionItems::Paged {
+                first: Box::new(first),
+            }
+        }
+    }
+
+    pub fn verify(&self, expected_domain: &Url) -> BackendResult<()> {
+        if let CollectionItems::Paged { first } = self {
+            verify_domains_match(&first.id, expected_domain)?;
+        }
+        Ok(())
+    }
+
+    /// Inline items are converted to a single page without `next`, so that both formats can be
+    /// handled the same way.
+    pub fn into_first_page(self, collection_id: Url) -> CollectionPage<T> {
+        match self {
+            CollectionItems::Paged { first } => *first,
+            CollectionItems::Inline { items, .. } => CollectionPage {
+                r#type: Default::default(),
+                id: collection_id.clone(),
+                part_of: collection_id,
+                ordered_items: items,
+                next: None,
+            },
+        }
+    }
+}
+
+/// Query params for federation routes which serve paged collections.
+#[derive(Deserialize, Debug, Default)]
+pub struct CollectionPageQuery {
+    #[serde(default)]
+    pub page: bool,
+    /// Database id of the last item on the previous page
+    pub cursor: Option<i32>,
+}
+
+impl<T> CollectionPage<T> {
+    /// Build a page with the given items, `last_id` is the database id of the last item. The page
+    /// is considered full and gets a `next` link if it contains `limit` items.
+    pub fn new(
+        collection_id: Url,
+        cursor: Option<i32>,
+        ordered_items: Vec<T>,
+        last_id: Option<i32>,
+        limit: i64,
+    ) -> Self {
+        let page_url = |cursor: Option<i32>| {
+            let mut url = collection_id.clone();
+            match cursor {
+                Some(cursor) => url.set_query(Some(&format!("page=true&cursor={cursor}"))),
+                None => url.set_query(Some("page=true")),
+            }
+            url
+        };
+        let next = last_id
+            .filter(|_| ordered_items.len() as i64 >= limit)
+            .map(|last_id| page_url(Some(last_id)));
+        CollectionPage {
+            r#type: Default::default(),
+            id: page_url(cursor),
+            part_of: collection_id,
+            ordered_items,
+            next,
+        }
+    }
+
+    /// Fetch the next page from a remote instance, if there is any.
+    pub async fn fetch_next(&self, context: &Data<IbisContext>) -> BackendResult<Option<Self>>
+    where
+        T: Clone + DeserializeOwned,
+    {
+        let Some(next) = &self.next else {
+            return Ok(None);
+        };
+        verify_domains_match(next, &self.part_of)?;
+        let page: Self = fetch_object_http(next, context).await?.object;
+        verify_domains_match(&page.id, &self.part_of)?;
+        Ok(Some(page))
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     backend::{
         database::{edit::DbEditForm, IbisContext},
+        federation::objects::user::ApubImage,
         utils::error::BackendError,
     },
     common::{
@@ -39,6 +40,9 @@ pub struct ApubEdit {
     pub object: ObjectId<DbArticle>,
     pub attributed_to: ObjectId<DbPerson>,
     pub published: DateTime<Utc>,
+    /// Images which are embedded in the new version of the article
+    #[serde(default)]
+    pub attachment: Vec<ApubImage>,
 }
 
 #[async_trait::async_trait]
@@ -57,6 +61,7 @@ impl Object for DbEdit {
     async fn into_json(self, context: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
         let article = DbArticle::read_view(self.article_id, context)?;
         let creator = DbPerson::read(self.creator_id, context)?;
+        let attachment = ApubImage::article_images(&self.diff, context)?;
         Ok(ApubEdit {
             kind: PatchType::Patch,
             id: self.ap_id,
@@ -67,6 +72,7 @@ impl Object for DbEdit {
             object: article.article.ap_id,
             attributed_to: creator.ap_id,
             published: self.published,
+            attachment,
         })
     }
 
@@ -85,6 +91,12 @@ impl Object for DbEdit {
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
         let article = json.object.dereference(context).await?;
+        // images are hosted by the creator's instance, or by the instance which sent the edit
+        ApubImage::cache_article_images(
+            json.attachment,
+            &[json.attributed_to.inner(), json.id.inner()],
+            context,
+        );
         let creator = match json.attributed_to.dereference(context).await {
             Ok(c) => c,
             Err(e) => {
@@ -1,10 +1,14 @@
+use super::collection_page::{CollectionItems, CollectionPage, MAX_COLLECTION_PAGES};
 use crate::{
     backend::{
-        database::IbisContext,
+        database::{page_limit, IbisContext},
         federation::objects::edit::ApubEdit,
-        utils::error::BackendError,
+        utils::error::{BackendError, BackendResult},
+    },
+    common::{
+        article::{DbArticle, DbEdit},
+        newtypes::EditId,
     },
-    common::article::{DbArticle, DbEdit},
 };
 use activitypub_federation::{
     config::Data,
@@ -17,18 +21,46 @@ use log::warn;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// Edits of an article, oldest first.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApubEditCollection {
     pub r#type: OrderedCollectionType,
     pub id: Url,
-    pub total_items: i32,
-    pub items: Vec<ApubEdit>,
+    #[serde(flatten)]
+    pub items: CollectionItems<ApubEdit>,
 }
 
 #[derive(Clone, Debug)]
 pub struct DbEditCollection();
 
+impl DbEditCollection {
+    pub async fn read_local_page(
+        article: &DbArticle,
+        cursor: Option<i32>,
+        context: &Data<IbisContext>,
+    ) -> BackendResult<CollectionPage<ApubEdit>> {
+        let limit = page_limit(None);
+        let edits =
+            DbEdit::list_for_article_page(article.id, cursor.map(EditId), Some(limit), context)?;
+        let last_id = edits.last().map(|e| e.id.0);
+        let edits = future::try_join_all(
+            edits
+                .into_iter()
+                .map(|e| e.into_json(context))
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+        Ok(CollectionPage::new(
+            Url::from(article.edits_id()?),
+            cursor,
+            edits,
+            last_id,
+            limit,
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl Collection for DbEditCollection {
     type Owner = DbArticle;
@@ -41,19 +73,12 @@ impl Collection for DbEditCollection {
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
         let article = DbArticle::read(article.id, context)?;
-        let edits = DbEdit::list_for_article(article.id, context)?;
-        let edits = future::try_join_all(
-            edits
-                .into_iter()
-                .map(|e| e.into_json(context))
-                .collect::<Vec<_>>(),
-        )
-        .await?;
         let collection = ApubEditCollection {
             r#type: Default::default(),
             id: Url::from(article.edits_id()?),
-            total_items: edits.len() as i32,
-            items: edits,
+            items: CollectionItems::from_first_page(
+                Self::read_local_page(&article, None, context).await?,
+            ),
         };
         Ok(collection)
     }
@@ -64,6 +89,7 @@ impl Collection for DbEditCollection {
         _context: &Data<Self::DataType>,
     ) -> Result<(), Self::Error> {
         verify_domains_match(&json.id, expected_domain)?;
+        json.items.verify(expected_domain)?;
         Ok(())
     }
 
@@ -72,14 +98,33 @@ impl Collection for DbEditCollection {
         owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
-        try_join_all(
-            apub.items
-                .into_iter()
-                .map(|i| DbEdit::from_json(i, context)),
-        )
-        .await
-        .map_err(|e| warn!("Failed to synchronize edits for {}: {e}", owner.ap_id))
-        .ok();
+        let mut page = Some(apub.items.into_first_page(apub.id));
+        for _ in 0..MAX_COLLECTION_PAGES {
+            let Some(current) = page.take() else {
+                break;
+            };
+            page = current
+                .fetch_next(context)
+                .await
+                .map_err(|e| warn!("Failed to fetch edits page for {}: {e}", owner.ap_id))
+                .ok()
+                .flatten();
+            try_join_all(
+                current
+                    .ordered_items
+                    .into_iter()
+                    .map(|i| DbEdit::from_json(i, context)),
+            )
+            .await
+            .map_err(|e| warn!("Failed to synchronize edits for {}: {e}", owner.ap_id))
+            .ok();
+        }
+        if page.is_some() {
+            warn!(
+                "Stopped fetching edits for {} after {MAX_COLLECTION_PAGES} pages",
+                owner.ap_id
+            );
+        }
         Ok(DbEditCollection())
     }
 }
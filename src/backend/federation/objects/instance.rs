@@ -28,12 +28,12 @@ pub struct ApubInstance {
     #[serde(rename = "type")]
     kind: ServiceType,
     pub id: ObjectId<DbInstance>,
-    name: Option<String>,
-    summary: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) summary: Option<String>,
     articles: Option<CollectionId<DbArticleCollection>>,
     instances: Option<CollectionId<DbInstanceCollection>>,
     inbox: Url,
-    public_key: PublicKey,
+    pub(crate) public_key: PublicKey,
 }
 
 impl DbInstance {
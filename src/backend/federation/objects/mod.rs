@@ -1,6 +1,7 @@
 pub mod article;
 mod article_or_comment;
 pub mod articles_collection;
+pub mod collection_page;
 pub mod comment;
 pub mod edit;
 pub mod edits_collection;
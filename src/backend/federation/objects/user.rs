@@ -1,14 +1,17 @@
 use crate::{
     backend::{
         database::{user::DbPersonForm, IbisContext},
-        utils::error::BackendError,
+        utils::{
+            error::BackendError,
+            media::{article_image_urls, cache_article_image, cache_remote_image, ImageKind},
+        },
     },
     common::user::DbPerson,
 };
 use activitypub_federation::{
     config::Data,
     fetch::object_id::ObjectId,
-    kinds::actor::PersonType,
+    kinds::{actor::PersonType, object::ImageType},
     protocol::{public_key::PublicKey, verification::verify_domains_match},
     traits::{Actor, Object},
 };
@@ -22,13 +25,98 @@ use url::Url;
 pub struct ApubUser {
     #[serde(rename = "type")]
     kind: PersonType,
-    id: ObjectId<DbPerson>,
+    pub(crate) id: ObjectId<DbPerson>,
     preferred_username: String,
     /// displayname
     name: Option<String>,
     summary: Option<String>,
     inbox: Url,
     public_key: PublicKey,
+    /// avatar
+    icon: Option<ApubImage>,
+    /// banner
+    image: Option<ApubImage>,
+}
+
+/// Maximum number of images which are fetched for a single article or edit.
+const MAX_ARTICLE_IMAGES: usize = 20;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApubImage {
+    #[serde(rename = "type")]
+    pub(crate) kind: ImageType,
+    pub(crate) url: Url,
+}
+
+impl ApubImage {
+    pub(crate) fn from_url(url: Url) -> Self {
+        ApubImage {
+            kind: Default::default(),
+            url,
+        }
+    }
+
+    fn new(url: Option<String>) -> Result<Option<Self>, BackendError> {
+        Ok(match url {
+            Some(url) => Some(ApubImage::from_url(url.parse()?)),
+            None => None,
+        })
+    }
+
+    /// Store a local copy of images which are embedded in a remote article or edit. Only images
+    /// from the given domains are fetched, and at most `MAX_ARTICLE_IMAGES`. Downloads happen in
+    /// the background and errors are only logged, so that a broken image doesn't prevent
+    /// federation of the article.
+    pub(crate) fn cache_article_images(
+        images: Vec<ApubImage>,
+        domains: &[&Url],
+        context: &Data<IbisContext>,
+    ) {
+        let images: Vec<_> = images
+            .into_iter()
+            .filter(|i| domains.iter().any(|d| i.url.host_str() == d.host_str()))
+            .take(MAX_ARTICLE_IMAGES)
+            .collect();
+        if images.is_empty() {
+            return;
+        }
+        let context_ = context.reset_request_count();
+        tokio::spawn(async move {
+            for image in images {
+                if let Err(e) = cache_article_image(&image.url, &context_).await {
+                    tracing::warn!("Failed to fetch image {}: {e}", image.url);
+                }
+            }
+        });
+    }
+
+    pub(crate) fn article_images(
+        text: &str,
+        context: &IbisContext,
+    ) -> Result<Vec<ApubImage>, BackendError> {
+        Ok(article_image_urls(text, context)?
+            .into_iter()
+            .map(ApubImage::from_url)
+            .collect())
+    }
+
+    /// Store a local copy of the remote image. Errors are only logged, so that a broken image
+    /// doesn't prevent fetching the user.
+    async fn cache(
+        image: Option<ApubImage>,
+        actor_id: &Url,
+        kind: ImageKind,
+        context: &IbisContext,
+    ) -> Option<String> {
+        let url = image?.url;
+        match cache_remote_image(&url, actor_id, kind, context).await {
+            Ok(cached) => Some(cached.to_string()),
+            Err(e) => {
+                tracing::warn!("Failed to fetch image {url}: {e}");
+                None
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -57,6 +145,8 @@ impl Object for DbPerson {
             public_key: __self.public_key(),
             name: self.display_name,
             summary: self.bio,
+            icon: ApubImage::new(self.avatar)?,
+            image: ApubImage::new(self.banner)?,
         })
     }
 
@@ -73,6 +163,9 @@ impl Object for DbPerson {
         json: Self::Kind,
         context: &Data<Self::DataType>,
     ) -> Result<Self, Self::Error> {
+        let avatar = ApubImage::cache(json.icon, json.id.inner(), ImageKind::Avatar, context).await;
+        let banner =
+            ApubImage::cache(json.image, json.id.inner(), ImageKind::Banner, context).await;
         let form = DbPersonForm {
             username: json.preferred_username,
             ap_id: json.id,
@@ -83,6 +176,8 @@ impl Object for DbPerson {
             local: false,
             display_name: json.name,
             bio: json.summary,
+            avatar,
+            banner,
         };
         DbPerson::create(&form, context)
     }
@@ -17,15 +17,21 @@ use crate::{
                 accept::Accept,
                 announce::AnnounceActivity,
                 create_article::CreateArticle,
+                delete_article::{ArticleTombstone, DeleteArticle},
+                delete_user::DeleteUser,
                 follow::Follow,
                 reject::RejectEdit,
+                report::Report,
+                undo_delete_article::UndoDeleteArticle,
+                update_instance::UpdateInstance,
                 update_local_article::UpdateLocalArticle,
                 update_remote_article::UpdateRemoteArticle,
+                update_user::UpdateUser,
             },
             objects::{
-                article::ApubArticle,
-                articles_collection::{ArticleCollection, DbArticleCollection},
-                edits_collection::{ApubEditCollection, DbEditCollection},
+                articles_collection::DbArticleCollection,
+                collection_page::CollectionPageQuery,
+                edits_collection::DbEditCollection,
                 instance::ApubInstance,
                 user::ApubUser,
             },
@@ -38,6 +44,7 @@ use crate::{
         instance::DbInstance,
         newtypes::CommentId,
         user::DbPerson,
+        utils::http_protocol_str,
     },
 };
 use activitypub_federation::{
@@ -46,12 +53,14 @@ use activitypub_federation::{
         json::FederationJson,
     },
     config::Data,
+    fetch::object_id::ObjectId,
     protocol::context::WithContext,
     traits::{ActivityHandler, Actor, Collection, Object},
 };
 use axum::{
-    extract::Path,
-    response::IntoResponse,
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     routing::{get, post},
     Router,
 };
@@ -93,10 +102,15 @@ async fn http_get_person(
 
 #[debug_handler]
 async fn http_get_all_articles(
+    Query(query): Query<CollectionPageQuery>,
     context: Data<IbisContext>,
-) -> BackendResult<FederationJson<WithContext<ArticleCollection>>> {
+) -> BackendResult<Response> {
+    if query.page {
+        let page = DbArticleCollection::read_local_page(query.cursor, &context).await?;
+        return Ok(FederationJson(WithContext::new_default(page)).into_response());
+    }
     let collection = DbArticleCollection::read_local(&(), &context).await?;
-    Ok(FederationJson(WithContext::new_default(collection)))
+    Ok(FederationJson(WithContext::new_default(collection)).into_response())
 }
 
 #[debug_handler]
@@ -111,20 +125,48 @@ async fn http_get_linked_instances(
 async fn http_get_article(
     Path(title): Path<String>,
     context: Data<IbisContext>,
-) -> BackendResult<FederationJson<WithContext<ApubArticle>>> {
-    let article = DbArticle::read_view_title(&title, None, &context)?;
-    let json = article.article.into_json(&context).await?;
-    Ok(FederationJson(WithContext::new_default(json)))
+) -> BackendResult<Response> {
+    let article = read_local_article(&title, &context)?;
+    if article.deleted {
+        // Other instances mark their copy as deleted when fetching it returns 410
+        let tombstone = ArticleTombstone {
+            id: article.ap_id,
+            kind: Default::default(),
+        };
+        let json = FederationJson(WithContext::new_default(tombstone));
+        return Ok((StatusCode::GONE, json).into_response());
+    }
+    let json = article.into_json(&context).await?;
+    Ok(FederationJson(WithContext::new_default(json)).into_response())
 }
 
 #[debug_handler]
 async fn http_get_article_edits(
     Path(title): Path<String>,
+    Query(query): Query<CollectionPageQuery>,
     context: Data<IbisContext>,
-) -> BackendResult<FederationJson<WithContext<ApubEditCollection>>> {
-    let article = DbArticle::read_view_title(&title, None, &context)?;
-    let json = DbEditCollection::read_local(&article.article, &context).await?;
-    Ok(FederationJson(WithContext::new_default(json)))
+) -> BackendResult<Response> {
+    let article = read_local_article(&title, &context)?;
+    if query.page {
+        let page = DbEditCollection::read_local_page(&article, query.cursor, &context).await?;
+        return Ok(FederationJson(WithContext::new_default(page)).into_response());
+    }
+    let json = DbEditCollection::read_local(&article, &context).await?;
+    Ok(FederationJson(WithContext::new_default(json)).into_response())
+}
+
+/// The article ap_id contains the title at time of creation, which doesn't change when the
+/// article is moved. So look it up by ap_id first, then by current or redirected title.
+fn read_local_article(title: &str, context: &Data<IbisContext>) -> BackendResult<DbArticle> {
+    let ap_id = ObjectId::parse(&format!(
+        "{}://{}/article/{title}",
+        http_protocol_str(),
+        context.config.federation.domain
+    ))?;
+    match DbArticle::read_from_ap_id(&ap_id, context) {
+        Ok(article) => Ok(article),
+        Err(_) => Ok(DbArticle::read_view_title(title, None, context)?.article),
+    }
 }
 
 #[debug_handler]
@@ -148,6 +190,13 @@ pub enum InboxActivities {
     UpdateLocalArticle(UpdateLocalArticle),
     UpdateRemoteArticle(UpdateRemoteArticle),
     RejectEdit(RejectEdit),
+    // needs to be before DeleteArticle, which would also match
+    DeleteUser(DeleteUser),
+    DeleteArticle(DeleteArticle),
+    UndoDeleteArticle(UndoDeleteArticle),
+    UpdateUser(UpdateUser),
+    UpdateInstance(UpdateInstance),
+    Report(Report),
     AnnounceActivity(AnnounceActivity),
     AnnouncableActivities(AnnouncableActivities),
 }
@@ -4,7 +4,7 @@ use crate::{
         federation::VerifyUrlData,
         utils::{config::IbisConfig, error::BackendResult, generate_activity_id},
     },
-    common::instance::DbInstance,
+    common::instance::{DbFederationListEntry, DbInstance},
 };
 use activitypub_federation::config::FederationConfig;
 use diesel::{
@@ -40,10 +40,16 @@ pub async fn start(
         .get()?
         .run_pending_migrations(MIGRATIONS)
         .expect("run migrations");
-    let context = IbisContext { db_pool, config };
+    let context = IbisContext {
+        db_pool,
+        config,
+        federation_list: Default::default(),
+    };
+    DbFederationListEntry::import_from_config(&context)?;
+    context.federation_list.refresh(&context)?;
     let data = FederationConfig::builder()
         .domain(context.config.federation.domain.clone())
-        .url_verifier(Box::new(VerifyUrlData(context.config.clone())))
+        .url_verifier(Box::new(VerifyUrlData(context.clone())))
         .app_data(context)
         .http_fetch_limit(1000)
         .debug(cfg!(debug_assertions))
@@ -55,9 +61,9 @@ pub async fn start(
         setup(&data.to_request_data()).await?;
     }
 
-    let db_pool = data.db_pool.clone();
+    let context = (*data).clone();
     thread::spawn(move || {
-        scheduled_tasks::start(db_pool);
+        scheduled_tasks::start(context);
     });
 
     start_server(data, override_hostname, notify_start).await?;
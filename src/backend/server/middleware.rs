@@ -1,17 +1,44 @@
 use crate::{
-    backend::{api::user::validate, database::IbisContext},
-    common::{Auth, AUTH_COOKIE},
+    backend::{
+        api::user::{validate, validate_api_token},
+        database::IbisContext,
+        utils::rate_limit::{RateLimitKey, RateLimitType, RateLimiter},
+    },
+    common::{
+        api_token::{ApiTokenScope, API_TOKEN_PREFIX},
+        user::LocalUserView,
+        Auth,
+        AUTH_COOKIE,
+    },
+};
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
 };
-use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
 use axum_macros::debug_middleware;
-use http::{header::COOKIE, HeaderValue};
-use std::{collections::HashSet, sync::Arc};
+use http::{
+    header::{AUTHORIZATION, COOKIE, RETRY_AFTER},
+    HeaderValue,
+    Method,
+    StatusCode,
+};
+use std::{
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
 
 pub(super) const FEDERATION_ROUTES_PREFIX: &str = "/federation_routes";
 
 /// Checks all headers and cookies (including duplicates) for first valid auth token.
 /// We need to extract cookies manually because CookieJar ignores duplicates.
-/// If user is authenticated sets extensions `Auth` and `LocalUserView`.
+/// If user is authenticated sets extensions `Auth`, `LocalUserView` and `LoginSessionId`.
+/// Api tokens can also be passed as `Authorization: Bearer`, in that case `DbApiToken` is set
+/// instead of `Auth` so that its scopes can be checked. For read requests with an api token that
+/// is missing the read scope, the user is not set so that public routes are handled as anonymous.
 #[debug_middleware]
 pub(super) async fn auth_middleware(
     State(context): State<Arc<IbisContext>>,
@@ -31,17 +58,116 @@ pub(super) async fn auth_middleware(
         .get_all(AUTH_COOKIE)
         .into_iter()
         .filter_map(|h| h.to_str().ok());
-    let auth: HashSet<_> = headers.chain(cookies).map(|s| s.to_string()).collect();
+    let bearer = request
+        .headers()
+        .get_all(AUTHORIZATION)
+        .into_iter()
+        .filter_map(|h| h.to_str().ok()?.strip_prefix("Bearer "));
+    let auth: HashSet<_> = headers
+        .chain(bearer)
+        .chain(cookies)
+        .map(|s| s.to_string())
+        .collect();
 
     for auth in auth {
-        if let Ok(local_user) = validate(&auth, &context).await {
+        if auth.starts_with(API_TOKEN_PREFIX) {
+            if let Ok((local_user, api_token)) = validate_api_token(&auth, &context).await {
+                if request.method() != Method::GET || api_token.has_scope(ApiTokenScope::Read) {
+                    request.extensions_mut().insert(local_user);
+                }
+                request.extensions_mut().insert(api_token);
+            }
+        } else if let Ok((local_user, session_id)) = validate(&auth, &context).await {
             request.extensions_mut().insert(Auth(Some(auth)));
             request.extensions_mut().insert(local_user);
+            request.extensions_mut().insert(session_id);
         }
     }
     next.run(request).await
 }
 
+/// Limits how often a single IP or user can call endpoints which are expensive or prone to spam.
+/// Requests over the limit are rejected with `429 Too Many Requests` and a `Retry-After` header.
+/// Needs to run after `auth_middleware` so that logged in users can be identified.
+#[debug_middleware]
+pub(super) async fn rate_limit_middleware(
+    State(rate_limiter): State<Arc<RateLimiter>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    use RateLimitType::*;
+    let Some(type_) = rate_limit_type(request.method(), request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let user = request.extensions().get::<LocalUserView>();
+    let key = match (type_, user) {
+        (Edit | Comment | Search, Some(user)) => RateLimitKey::User(user.person.id),
+        _ => RateLimitKey::Ip(client_ip(&request, &rate_limiter)),
+    };
+    if let Err(retry_after) = rate_limiter.check(type_, key) {
+        // round up so that the client doesnt retry too early
+        let retry_after = retry_after.as_secs() + 1;
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(RETRY_AFTER, retry_after.to_string())],
+            format!("Rate limit exceeded, try again in {retry_after} seconds"),
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
+fn rate_limit_type(method: &Method, path: &str) -> Option<RateLimitType> {
+    use RateLimitType::*;
+    if path.strip_prefix(FEDERATION_ROUTES_PREFIX) == Some("/inbox") {
+        return Some(Inbox);
+    }
+    let path = path.strip_prefix("/api/v1")?;
+    match (method, path) {
+        (&Method::POST, "/account/register") => Some(Register),
+        (&Method::POST, "/account/login" | "/account/oauth/login") => Some(Login),
+        (&Method::POST | &Method::PATCH, "/article")
+        | (
+            &Method::POST,
+            "/article/fork" | "/article/move" | "/article/revert" | "/article/image",
+        ) => Some(Edit),
+        (&Method::POST | &Method::PATCH, "/comment") => Some(Comment),
+        (&Method::GET, "/search") => Some(Search),
+        _ => None,
+    }
+}
+
+/// Returns the address of the connected socket. If that is a trusted reverse proxy, the
+/// `X-Forwarded-For` header is read from right to left, and the first address which is not a
+/// trusted proxy is used. Addresses further left can be set arbitrarily by the client.
+fn client_ip(request: &Request<Body>, rate_limiter: &RateLimiter) -> IpAddr {
+    let mut client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|c| c.0.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    if !rate_limiter.is_trusted_proxy(client) {
+        return client;
+    }
+    let forwarded: Vec<_> = request
+        .headers()
+        .get_all("X-Forwarded-For")
+        .into_iter()
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(','))
+        .collect();
+    for ip in forwarded.into_iter().rev() {
+        let Ok(ip) = ip.trim().parse() else {
+            break;
+        };
+        client = ip;
+        if !rate_limiter.is_trusted_proxy(ip) {
+            break;
+        }
+    }
+    client
+}
+
 /// Rewrite federation routes to use `FEDERATION_ROUTES_PREFIX`, to avoid conflicts
 /// with frontend routes. If a request is an Activitypub fetch as indicated by
 /// `Accept: application/activity+json` header, use the federation routes. Otherwise
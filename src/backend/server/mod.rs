@@ -1,4 +1,7 @@
-use super::{database::IbisContext, utils::error::BackendResult};
+use super::{
+    database::IbisContext,
+    utils::{error::BackendResult, rate_limit::RateLimiter},
+};
 use crate::{
     backend::{api::api_routes, federation::routes::federation_routes},
     common::Auth,
@@ -20,10 +23,15 @@ use axum::{
 use leptos::prelude::*;
 use leptos_axum::{generate_route_list, LeptosRoutes};
 use log::info;
-use middleware::{auth_middleware, federation_routes_middleware, FEDERATION_ROUTES_PREFIX};
+use middleware::{
+    auth_middleware,
+    federation_routes_middleware,
+    rate_limit_middleware,
+    FEDERATION_ROUTES_PREFIX,
+};
 use std::{net::SocketAddr, ops::Deref, sync::Arc};
 use tokio::{net::TcpListener, sync::oneshot};
-use tower_http::{compression::CompressionLayer, cors::CorsLayer};
+use tower_http::{compression::CompressionLayer, cors::CorsLayer, services::ServeDir};
 use tower_layer::Layer;
 
 mod assets;
@@ -44,16 +52,19 @@ pub(super) async fn start_server(
     let routes = generate_route_list(App);
 
     let arc_data = Arc::new(context.deref().clone());
+    let rate_limiter = Arc::new(RateLimiter::new(context.config.rate_limit.clone()));
     let app = Router::new()
         .leptos_routes_with_handler(routes, get(leptos_routes_handler))
         .fallback(file_and_error_handler)
         .with_state(leptos_options)
         .nest(FEDERATION_ROUTES_PREFIX, federation_routes())
         .nest("/api/v1", api_routes())
+        .nest_service("/media", ServeDir::new(&context.config.media.directory))
         .nest("", nodeinfo::config())
         .layer(FederationMiddleware::new(context))
         .layer(CorsLayer::permissive())
         .layer(CompressionLayer::new())
+        .route_layer(from_fn_with_state(rate_limiter, rate_limit_middleware))
         .route_layer(from_fn_with_state(arc_data, auth_middleware));
 
     // Rewrite federation routes
@@ -66,7 +77,11 @@ pub(super) async fn start_server(
     if let Some(notify_start) = notify_start {
         notify_start.send(()).expect("send oneshot");
     }
-    axum::serve(listener, app_with_middleware.into_make_service()).await?;
+    axum::serve(
+        listener,
+        app_with_middleware.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
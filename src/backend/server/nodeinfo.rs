@@ -3,7 +3,7 @@ use crate::{
         database::{instance_stats::InstanceStats, IbisContext},
         utils::error::BackendResult,
     },
-    common::utils::http_protocol_str,
+    common::{instance::RegistrationMode, utils::http_protocol_str},
 };
 use activitypub_federation::config::Data;
 use axum::{routing::get, Json, Router};
@@ -51,7 +51,7 @@ async fn node_info(context: Data<IbisContext>) -> BackendResult<Json<NodeInfo>>
             local_posts: stats.articles,
             local_comments: stats.comments,
         },
-        open_registrations: context.config.options.registration_open,
+        open_registrations: context.config.options.registration_mode != RegistrationMode::Closed,
         services: Default::default(),
         metadata: vec![],
     }))
@@ -0,0 +1,76 @@
+use crate::backend::utils::error::BackendResult;
+use anyhow::anyhow;
+use image::{ImageFormat, Rgb, RgbImage};
+use rand::{thread_rng, Rng};
+use std::io::Cursor;
+
+/// Number of digits which the user needs to enter.
+const CAPTCHA_LENGTH: usize = 6;
+
+/// Each pixel of the font is drawn as a square with this size.
+const SCALE: u32 = 6;
+
+/// Digits 0-9 in a 3x5 pixel font. Each row is stored in the three lowest bits.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+pub fn generate_captcha_answer() -> String {
+    let mut rng = thread_rng();
+    (0..CAPTCHA_LENGTH)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+        .collect()
+}
+
+/// Render the answer as png image. Digits are randomly shifted and colored, with noise drawn
+/// on top, so that simple text recognition doesn't work. This avoids depending on a third party
+/// captcha service.
+pub fn render_captcha(answer: &str) -> BackendResult<Vec<u8>> {
+    let mut rng = thread_rng();
+    let width = (answer.len() as u32 * 4 + 2) * SCALE;
+    let height = 9 * SCALE;
+    let mut image = RgbImage::from_pixel(width, height, Rgb([255, 255, 255]));
+    for (i, c) in answer.chars().enumerate() {
+        let digit = c
+            .to_digit(10)
+            .ok_or_else(|| anyhow!("Invalid captcha answer"))?;
+        let x = (i as u32 * 4 + 1) * SCALE + rng.gen_range(0..SCALE);
+        let y = 2 * SCALE + rng.gen_range(0..2 * SCALE);
+        let color = Rgb([
+            rng.gen_range(0..150),
+            rng.gen_range(0..150),
+            rng.gen_range(0..150),
+        ]);
+        for (row, bits) in DIGITS[digit as usize].iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dx in 0..SCALE {
+                    for dy in 0..SCALE {
+                        let px = x + col * SCALE + dx;
+                        let py = y + row as u32 * SCALE + dy;
+                        image.put_pixel(px, py, color);
+                    }
+                }
+            }
+        }
+    }
+    for _ in 0..(width * height / 6) {
+        let x = rng.gen_range(0..width);
+        let y = rng.gen_range(0..height);
+        image.put_pixel(x, y, Rgb(rng.gen()));
+    }
+    let mut encoded = Cursor::new(vec![]);
+    image.write_to(&mut encoded, ImageFormat::Png)?;
+    Ok(encoded.into_inner())
+}
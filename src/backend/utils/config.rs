@@ -1,6 +1,10 @@
-use crate::{backend::utils::error::BackendResult, common::instance::Options};
+use crate::{
+    backend::utils::error::BackendResult,
+    common::instance::{Options, RegistrationMode},
+};
 use config::Config;
 use doku::Document;
+use log::warn;
 use serde::Deserialize;
 use smart_default::SmartDefault;
 
@@ -14,6 +18,17 @@ pub struct IbisConfig {
     pub setup: IbisConfigSetup,
     pub federation: IbisConfigFederation,
     pub options: Options,
+    /// Storage for uploaded images
+    pub media: IbisConfigMedia,
+    /// Limits for how often a single IP or user can call certain endpoints
+    pub rate_limit: IbisConfigRateLimit,
+    /// Email sending configuration. All options except login/password are mandatory
+    #[default(None)]
+    #[doku(example = "Some(Default::default())")]
+    pub email: Option<IbisConfigEmail>,
+    /// OpenID Connect providers which users can use to login, in addition to username and
+    /// password. Redirect url for the provider is https://example.com/account/oauth_callback
+    pub oauth_providers: Vec<IbisConfigOAuthProvider>,
 }
 
 impl IbisConfig {
@@ -24,7 +39,17 @@ impl IbisConfig {
             .add_source(config::Environment::with_prefix("IBIS").separator("__"))
             .build()?;
 
-        Ok(config.try_deserialize()?)
+        let mut config: IbisConfig = config.try_deserialize()?;
+        // TODO: remove in 0.3
+        if let Some(open) = config.options.registration_open.take() {
+            warn!("Config option registration_open is deprecated, use registration_mode instead");
+            config.options.registration_mode = if open {
+                RegistrationMode::Open
+            } else {
+                RegistrationMode::Closed
+            };
+        }
+        Ok(config)
     }
 }
 
@@ -63,12 +88,106 @@ pub struct IbisConfigFederation {
     #[doku(example = "example.com")]
     pub domain: String,
     /// Comma separated list of instances which are allowed for federation. If set, federation
-    /// with other domains is blocked
+    /// with other domains is blocked. Deprecated, the domains are copied into the database on
+    /// first startup and can be managed in the instance settings
     #[default(None)]
     #[doku(example = "good.com,friends.org")]
     pub allowlist: Option<String>,
-    /// Comma separated list of instances which are blocked for federation
+    /// Comma separated list of instances which are blocked for federation. Deprecated, the
+    /// domains are copied into the database on first startup and can be managed in the instance
+    /// settings
     #[default(None)]
     #[doku(example = "evil.com,bad.org")]
     pub blocklist: Option<String>,
 }
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document, SmartDefault)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigMedia {
+    /// Folder where uploaded images are stored. It is created automatically if necessary
+    #[default("media")]
+    #[doku(example = "media")]
+    pub directory: String,
+    /// Allow fetching remote images from private and loopback addresses. Only enable this for
+    /// testing federation on a local network
+    #[default(false)]
+    #[doku(example = "false")]
+    pub allow_private_addresses: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document, SmartDefault)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigRateLimit {
+    /// Maximum number of account registrations per IP and hour. Set any of the limits to 0 to
+    /// disable it
+    #[default(5)]
+    #[doku(example = "5")]
+    pub register: u32,
+    /// Maximum number of login attempts per IP and minute
+    #[default(10)]
+    #[doku(example = "10")]
+    pub login: u32,
+    /// Maximum number of article creations, edits, forks, moves and reverts per user and minute
+    #[default(60)]
+    #[doku(example = "60")]
+    pub edit: u32,
+    /// Maximum number of new or edited comments per user and minute
+    #[default(30)]
+    #[doku(example = "30")]
+    pub comment: u32,
+    /// Maximum number of searches per user or IP and minute
+    #[default(60)]
+    #[doku(example = "60")]
+    pub search: u32,
+    /// Maximum number of activities received in the federation inbox per IP and minute
+    #[default(300)]
+    #[doku(example = "300")]
+    pub inbox: u32,
+    /// Comma separated ip addresses of reverse proxies in front of Ibis. The client ip is only
+    /// read from the `X-Forwarded-For` header for requests coming from one of these addresses.
+    #[default(None)]
+    #[doku(example = "127.0.0.1,::1")]
+    pub trusted_proxies: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document, SmartDefault)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigEmail {
+    /// Hostname and port of the smtp server
+    #[doku(example = "localhost:25")]
+    pub smtp_server: String,
+    /// Login name for smtp server
+    pub smtp_login: Option<String>,
+    /// Password to login to the smtp server
+    pub smtp_password: Option<String>,
+    /// Address to send emails from, eg "noreply@your-instance.com"
+    #[doku(example = "noreply@example.com")]
+    pub smtp_from_address: String,
+    /// Whether or not smtp connections should use tls. Can be none, tls, or starttls
+    #[default("none")]
+    #[doku(example = "none")]
+    pub tls_type: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document, SmartDefault)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigOAuthProvider {
+    /// Short identifier for the provider. Users who login through the provider are linked to it,
+    /// so it must not be changed later
+    #[doku(example = "keycloak")]
+    pub name: String,
+    /// Name which is shown on the login button
+    #[doku(example = "Company Login")]
+    pub display_name: String,
+    /// Issuer url of the provider, used to discover the endpoints via
+    /// /.well-known/openid-configuration
+    #[doku(example = "https://login.example.com/realms/example")]
+    pub issuer: String,
+    /// Credentials of the client which was registered with the provider
+    #[doku(example = "ibis")]
+    pub client_id: String,
+    #[doku(example = "secret")]
+    pub client_secret: String,
+}
@@ -0,0 +1,175 @@
+use crate::{
+    backend::{
+        database::{notifications::DbNotification, IbisContext},
+        utils::error::BackendResult,
+    },
+    common::{user::DbLocalUser, utils::http_protocol_str, ApiNotification, Notification},
+};
+use anyhow::anyhow;
+use lettre::{
+    message::header::ContentType,
+    transport::smtp::{authentication::Credentials, extension::ClientId},
+    Message,
+    SmtpTransport,
+    Transport,
+};
+use log::{error, info};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+
+/// Send a plaintext email using the smtp server from config.
+fn send_email(to: &str, subject: &str, body: String, context: &IbisContext) -> BackendResult<()> {
+    let config = context
+        .config
+        .email
+        .as_ref()
+        .ok_or(anyhow!("Email is not configured"))?;
+    let (host, port) = config
+        .smtp_server
+        .split_once(':')
+        .ok_or(anyhow!("Smtp server must be in format host:port"))?;
+    let port = port.parse()?;
+    let mut builder = match config.tls_type.as_str() {
+        "tls" => SmtpTransport::relay(host)?,
+        "starttls" => SmtpTransport::starttls_relay(host)?,
+        _ => SmtpTransport::builder_dangerous(host),
+    }
+    .port(port)
+    .hello_name(ClientId::Domain(context.config.federation.domain.clone()));
+    if let (Some(login), Some(password)) = (&config.smtp_login, &config.smtp_password) {
+        builder = builder.credentials(Credentials::new(login.clone(), password.clone()));
+    }
+
+    let email = Message::builder()
+        .from(config.smtp_from_address.parse()?)
+        .to(to.parse()?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)?;
+    builder.build().send(&email)?;
+    Ok(())
+}
+
+/// Smtp sending is blocking, so run it on a separate thread when called from async handlers.
+async fn send_email_async(
+    to: &str,
+    subject: &str,
+    body: String,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    let (to, subject, context) = (to.to_string(), subject.to_string(), context.clone());
+    tokio::task::spawn_blocking(move || send_email(&to, &subject, body, &context)).await?
+}
+
+/// Random token for links in verification and password reset emails.
+pub fn generate_token() -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+fn link(path: &str, context: &IbisContext) -> String {
+    let domain = &context.config.federation.domain;
+    format!("{}://{domain}{path}", http_protocol_str())
+}
+
+pub async fn send_verification_email(
+    local_user: &DbLocalUser,
+    email: &str,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    let token = generate_token();
+    DbLocalUser::create_email_verification(local_user, &token, context)?;
+    let link = link(&format!("/account/verify_email?token={token}"), context);
+    let body = format!(
+        "Open the following link within one day to verify your email address for {}:\n\n{link}\n",
+        context.config.federation.domain
+    );
+    send_email_async(email, "Verify your email address", body, context).await
+}
+
+/// Create a password reset token and return the link for setting a new password with it.
+pub fn password_reset_link(
+    local_user: &DbLocalUser,
+    context: &IbisContext,
+) -> BackendResult<String> {
+    let token = generate_token();
+    DbLocalUser::create_password_reset(local_user, &token, context)?;
+    let path = format!("/account/reset_password?token={token}");
+    Ok(link(&path, context))
+}
+
+pub async fn send_password_reset_email(
+    local_user: &DbLocalUser,
+    email: &str,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    let link = password_reset_link(local_user, context)?;
+    let body = format!(
+        "Open the following link within one hour to set a new password for {}:\n\n{link}\n\n\
+        If you didn't request a password reset, you can ignore this email.\n",
+        context.config.federation.domain
+    );
+    send_email_async(email, "Reset your password", body, context).await
+}
+
+/// Send an email with all new unread notifications to each user who enabled it.
+pub fn send_notification_digests(context: &IbisContext) -> BackendResult<()> {
+    if context.config.email.is_none() {
+        return Ok(());
+    }
+    info!("Sending notification emails");
+    for local_user in DbLocalUser::list_for_email_notifications(context)? {
+        let Some(email) = &local_user.email else {
+            continue;
+        };
+        let notifications = DbNotification::list_for_email(local_user.person_id, context)?;
+        if notifications.is_empty() {
+            continue;
+        }
+        let mut body = notifications
+            .iter()
+            .map(notification_text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        body.push_str(&format!(
+            "\n\nView all: {}\n",
+            link("/notifications", context)
+        ));
+        let subject = format!("{} new notifications", notifications.len());
+        // notifications are sent again with the next digest if this fails
+        if let Err(e) = send_email(email, &subject, body, context) {
+            error!("Failed to send notification email: {e}");
+            continue;
+        }
+        let ids = notifications.iter().map(|n| n.id).collect();
+        DbNotification::mark_as_emailed(ids, context)?;
+    }
+    info!("Done sending notification emails");
+    Ok(())
+}
+
+fn notification_text(notification: &ApiNotification) -> String {
+    use Notification::*;
+    match &notification.data {
+        EditConflict {
+            summary, article, ..
+        } => {
+            format!("- Edit conflict on {}: {summary}", article.title)
+        }
+        ArticleApprovalRequired(article) => format!("- Approval required: {}", article.title),
+        Reply(c) => format!(
+            "- {} replied to your comment on {}",
+            c.creator.username, c.article.title
+        ),
+        ArticleEdited(e) => format!(
+            "- {} edited {}: {}",
+            e.creator.username, e.article.title, e.edit.summary
+        ),
+        Report(r) => format!("- Report on {}: {}", r.article.title, r.report.reason),
+        RegistrationApplication(r) => {
+            format!("- Registration application from {}", r.person.username)
+        }
+    }
+}
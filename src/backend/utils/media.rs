@@ -0,0 +1,375 @@
+use crate::{
+    backend::{database::IbisContext, utils::error::BackendResult},
+    common::utils::http_protocol_str,
+};
+use activitypub_federation::protocol::verification::verify_domains_match;
+use anyhow::anyhow;
+use image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits};
+use regex::Regex;
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    error::Error,
+    fs::{create_dir_all, write},
+    io::Cursor,
+    net::{IpAddr, SocketAddr},
+    path::Path,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+use tokio::{net::lookup_host, task::spawn_blocking};
+use url::{Host, Url};
+
+/// Maximum file size for uploaded and fetched images.
+pub const MAX_IMAGE_SIZE: usize = 5 * 1024 * 1024;
+
+/// Maximum width and height of thumbnails for article images.
+const THUMBNAIL_SIZE: u32 = 800;
+
+/// Maximum width and height of images which are decoded, so that a small file can't use up all
+/// memory.
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+
+#[derive(Clone, Copy, Debug)]
+pub enum ImageKind {
+    Avatar,
+    Banner,
+}
+
+impl ImageKind {
+    /// Width and height to which the image is cropped and scaled.
+    fn dimensions(&self) -> (u32, u32) {
+        match self {
+            ImageKind::Avatar => (256, 256),
+            ImageKind::Banner => (1500, 500),
+        }
+    }
+}
+
+/// Decode an uploaded image, resize it and store it in the media directory as webp. Reencoding
+/// strips metadata like EXIF and ensures that only valid images are served. Returns the url of
+/// the stored image.
+pub async fn store_image(
+    data: Vec<u8>,
+    kind: ImageKind,
+    context: &IbisContext,
+) -> BackendResult<Url> {
+    let encoded = spawn_blocking(move || encode_image(&data, kind)).await??;
+    let filename = format!("{}.webp", hex::encode(Sha256::digest(&encoded)));
+    write_media_file(&filename, &encoded, context)?;
+    media_url(&filename, context)
+}
+
+/// Download an image from a remote instance and store a resized copy locally, so that clients
+/// don't have to load it from the remote instance. The filename is derived from the remote url,
+/// so each image is only downloaded once. Images are only fetched from the domain of the actor
+/// which they belong to.
+pub async fn cache_remote_image(
+    url: &Url,
+    actor_id: &Url,
+    kind: ImageKind,
+    context: &IbisContext,
+) -> BackendResult<Url> {
+    verify_domains_match(url, actor_id)?;
+    let filename = format!("{}.webp", hex::encode(Sha256::digest(url.as_str())));
+    if media_file_exists(&filename, context) {
+        return media_url(&filename, context);
+    }
+    let data = download(url, context).await?;
+    let encoded = spawn_blocking(move || encode_image(&data, kind)).await??;
+    write_media_file(&filename, &encoded, context)?;
+    media_url(&filename, context)
+}
+
+/// Store an image which is embedded in an article. It is reencoded to strip metadata like EXIF,
+/// and named after the hash of its content. Additionally a thumbnail is generated, which is
+/// displayed in the article. Returns the path under which the image is served.
+pub async fn store_article_image(data: Vec<u8>, context: &IbisContext) -> BackendResult<String> {
+    let (filename, encoded, thumbnail) =
+        spawn_blocking(move || encode_article_image(&data)).await??;
+    write_article_image(&filename, &encoded, &thumbnail, context)?;
+    Ok(format!("/media/{filename}"))
+}
+
+/// Store a copy of an image which is embedded in a remote article, so that it is also displayed
+/// in local forks of the article. Images are only accepted if the content matches the hash in
+/// the filename, so the same image has the same filename on all instances. Like uploads, the
+/// stored copy is reencoded so that no metadata or trailing data from the remote file is served.
+pub async fn cache_article_image(url: &Url, context: &IbisContext) -> BackendResult<()> {
+    let filename = url
+        .path_segments()
+        .and_then(Iterator::last)
+        .unwrap_or_default();
+    let Some((hash, format)) = parse_article_image_filename(filename) else {
+        return Err(anyhow!("Invalid image filename {filename}").into());
+    };
+    if media_file_exists(filename, context) {
+        return Ok(());
+    }
+    let data = download(url, context).await?;
+    if hex::encode(Sha256::digest(&data)) != hash {
+        return Err(anyhow!("Image content doesn't match filename").into());
+    }
+    let (encoded, thumbnail) =
+        spawn_blocking(move || reencode_article_image(&data, format)).await??;
+    write_article_image(filename, &encoded, &thumbnail, context)
+}
+
+/// Urls of all locally available images which are embedded in the given article text or diff.
+pub fn article_image_urls(text: &str, context: &IbisContext) -> BackendResult<Vec<Url>> {
+    static IMAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"!\[[^\]]*\]\(/media/([0-9a-f]{64}\.[a-z]+)\)").expect("compile regex")
+    });
+    let mut filenames: Vec<_> = IMAGE_REGEX
+        .captures_iter(text)
+        .map(|c| c[1].to_string())
+        .filter(|f| media_file_exists(f, context))
+        .collect();
+    filenames.sort();
+    filenames.dedup();
+    filenames.iter().map(|f| media_url(f, context)).collect()
+}
+
+fn parse_article_image_filename(filename: &str) -> Option<(&str, ImageFormat)> {
+    let (hash, extension) = filename.split_once('.')?;
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let format = match extension {
+        "jpg" => ImageFormat::Jpeg,
+        "webp" => ImageFormat::WebP,
+        "png" => ImageFormat::Png,
+        _ => return None,
+    };
+    Some((hash, format))
+}
+
+/// Returns filename, reencoded image and thumbnail.
+fn encode_article_image(data: &[u8]) -> BackendResult<(String, Vec<u8>, Vec<u8>)> {
+    let image = decode_image(data)?;
+    let (format, extension) = match image::guess_format(data)? {
+        ImageFormat::Jpeg => (ImageFormat::Jpeg, "jpg"),
+        ImageFormat::WebP => (ImageFormat::WebP, "webp"),
+        _ => (ImageFormat::Png, "png"),
+    };
+    let (encoded, thumbnail) = encode_with_thumbnail(&image, format)?;
+    let filename = format!("{}.{extension}", hex::encode(Sha256::digest(&encoded)));
+    Ok((filename, encoded, thumbnail))
+}
+
+/// Returns reencoded image and thumbnail in the given format.
+fn reencode_article_image(data: &[u8], format: ImageFormat) -> BackendResult<(Vec<u8>, Vec<u8>)> {
+    encode_with_thumbnail(&decode_image(data)?, format)
+}
+
+fn encode_with_thumbnail(
+    image: &DynamicImage,
+    format: ImageFormat,
+) -> BackendResult<(Vec<u8>, Vec<u8>)> {
+    let encoded = encode(image, format)?;
+    let thumbnail = encode(&image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE), format)?;
+    Ok((encoded, thumbnail))
+}
+
+fn write_article_image(
+    filename: &str,
+    data: &[u8],
+    thumbnail: &[u8],
+    context: &IbisContext,
+) -> BackendResult<()> {
+    write_media_file(filename, data, context)?;
+    write_media_file(&format!("thumbnail/{filename}"), thumbnail, context)?;
+    Ok(())
+}
+
+/// Fetch a remote image, with limited size. The body is read in chunks so that the download is
+/// aborted as soon as it gets too large, even if the content length is missing or wrong.
+async fn download(url: &Url, context: &IbisContext) -> BackendResult<Vec<u8>> {
+    static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(Policy::custom(|attempt| {
+                if attempt.previous().len() > 5 {
+                    attempt.error("Too many redirects")
+                } else if !is_public_url(attempt.url()) {
+                    attempt.error("Redirect to private address")
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .expect("init reqwest")
+    });
+    static PRIVATE_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(Policy::limited(5))
+            .build()
+            .expect("init reqwest")
+    });
+
+    let client = if context.config.media.allow_private_addresses {
+        &PRIVATE_CLIENT
+    } else if is_public_url(url) {
+        &CLIENT
+    } else {
+        return Err(anyhow!("Remote image has private address").into());
+    };
+    let mut res = client.get(url.clone()).send().await?.error_for_status()?;
+    if res.content_length().unwrap_or_default() > MAX_IMAGE_SIZE as u64 {
+        return Err(anyhow!("Remote image is too large").into());
+    }
+    let mut data = vec![];
+    while let Some(chunk) = res.chunk().await? {
+        data.extend_from_slice(&chunk);
+        if data.len() > MAX_IMAGE_SIZE {
+            return Err(anyhow!("Remote image is too large").into());
+        }
+    }
+    Ok(data)
+}
+
+/// Resolves hostnames with the system resolver, but drops private and loopback addresses so that
+/// remote instances can't make us fetch images from internal services.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(resolve_public(name))
+    }
+}
+
+async fn resolve_public(name: Name) -> Result<Addrs, Box<dyn Error + Send + Sync>> {
+    let addrs: Vec<SocketAddr> = lookup_host((name.as_str(), 0))
+        .await?
+        .filter(|addr| is_public_ip(addr.ip()))
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("No public address for {}", name.as_str()).into());
+    }
+    Ok(Box::new(addrs.into_iter()))
+}
+
+/// Checks that the url doesn't point directly to a private ip address. Hostnames are checked
+/// by `PublicResolver`.
+fn is_public_url(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Ipv4(ip)) => is_public_ip(ip.into()),
+        Some(Host::Ipv6(ip)) => is_public_ip(ip.into()),
+        Some(Host::Domain(_)) => true,
+        None => false,
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            // 100.64.0.0/10 is used for carrier-grade NAT
+            let shared = a == 100 && (b & 0xc0) == 64;
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || shared)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(ip.into());
+            }
+            let first = ip.segments()[0];
+            // fc00::/7 is unique local, fe80::/10 is link local
+            let unique_local = (first & 0xfe00) == 0xfc00;
+            let link_local = (first & 0xffc0) == 0xfe80;
+            !(ip.is_loopback() || ip.is_unspecified() || unique_local || link_local)
+        }
+    }
+}
+
+fn media_file_exists(filename: &str, context: &IbisContext) -> bool {
+    Path::new(&context.config.media.directory)
+        .join(filename)
+        .exists()
+}
+
+/// Image decoding and encoding is cpu intensive, so the functions below should only be called
+/// from a blocking thread.
+fn encode_image(data: &[u8], kind: ImageKind) -> BackendResult<Vec<u8>> {
+    let image = decode_image(data)?;
+    let (width, height) = kind.dimensions();
+    let image = image.resize_to_fill(width, height, FilterType::Lanczos3);
+    encode(&image, ImageFormat::WebP)
+}
+
+fn decode_image(data: &[u8]) -> BackendResult<DynamicImage> {
+    if data.len() > MAX_IMAGE_SIZE {
+        return Err(anyhow!("Image is too large").into());
+    }
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    let mut reader = ImageReader::new(Cursor::new(data)).with_guessed_format()?;
+    reader.limits(limits);
+    Ok(reader.decode().map_err(|_| anyhow!("Invalid image"))?)
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> BackendResult<Vec<u8>> {
+    // jpeg doesn't support transparency, and webp encoder only supports rgb(a)
+    let image = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => DynamicImage::ImageRgba8(image.to_rgba8()),
+    };
+    let mut encoded = vec![];
+    image.write_to(&mut Cursor::new(&mut encoded), format)?;
+    Ok(encoded)
+}
+
+fn write_media_file(filename: &str, data: &[u8], context: &IbisContext) -> BackendResult<()> {
+    let path = Path::new(&context.config.media.directory).join(filename);
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+    write(path, data)?;
+    Ok(())
+}
+
+fn media_url(filename: &str, context: &IbisContext) -> BackendResult<Url> {
+    Ok(Url::parse(&format!(
+        "{}://{}/media/{filename}",
+        http_protocol_str(),
+        context.config.federation.domain
+    ))?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_public_ip() -> BackendResult<()> {
+        for ip in ["1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse()?), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse()?), "{ip}");
+        }
+        Ok(())
+    }
+}
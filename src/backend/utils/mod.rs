@@ -15,9 +15,15 @@ use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use std::sync::LazyLock;
 use url::{ParseError, Url};
 
+pub(super) mod captcha;
 pub mod config;
+pub(super) mod email;
 pub mod error;
+pub(super) mod media;
+pub(super) mod oauth;
+pub(super) mod rate_limit;
 pub(super) mod scheduled_tasks;
+pub(super) mod totp;
 pub(super) mod validate;
 
 pub(super) fn generate_activity_id(context: &Data<IbisContext>) -> Result<Url, ParseError> {
@@ -35,27 +41,35 @@ pub(super) fn generate_activity_id(context: &Data<IbisContext>) -> Result<Url, P
     ))
 }
 
-/// Starting from empty string, apply edits until the specified version is reached. If no version is
-/// given, apply all edits up to latest version.
-///
-/// TODO: testing
-/// TODO: should cache all these generated versions
+/// Apply edits until the specified version is reached. Starts from the nearest snapshot before
+/// that version, or from empty string if there is none.
 pub(super) fn generate_article_version(
-    edits: &Vec<DbEdit>,
+    edits: &[DbEdit],
     version: &EditVersion,
 ) -> BackendResult<String> {
     let mut generated = String::new();
     if version == &EditVersion::default() {
         return Ok(generated);
     }
-    for e in edits {
+    let end = edits
+        .iter()
+        .position(|e| &e.hash == version)
+        .ok_or(anyhow!("failed to generate article version"))?;
+    let mut start = 0;
+    let snapshot = edits[..=end]
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(i, e)| e.snapshot.as_ref().map(|s| (i, s)));
+    if let Some((i, snapshot)) = snapshot {
+        generated.clone_from(snapshot);
+        start = i + 1;
+    }
+    for e in &edits[start..=end] {
         let patch = Patch::from_str(&e.diff)?;
         generated = apply(&generated, &patch)?;
-        if &e.hash == version {
-            return Ok(generated);
-        }
     }
-    Err(anyhow!("failed to generate article version").into())
+    Ok(generated)
 }
 
 /// Use a single static keypair during testing which is signficantly faster than
@@ -95,6 +109,7 @@ mod test {
                 previous_version_id: Default::default(),
                 published: Utc::now(),
                 pending: false,
+                snapshot: None,
             })
         };
         Ok([
@@ -113,6 +128,19 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_generate_version_from_snapshot() -> BackendResult<()> {
+        let mut edits = create_edits()?;
+        edits[1].snapshot = Some("sda\n".to_string());
+        // edits before the snapshot are not applied
+        edits[0].diff = "invalid".to_string();
+        let generated = generate_article_version(&edits, &edits[2].hash)?;
+        assert_eq!("123\n", generated);
+        let generated = generate_article_version(&edits, &edits[1].hash)?;
+        assert_eq!("sda\n", generated);
+        Ok(())
+    }
+
     #[test]
     fn test_generate_invalid_version() -> BackendResult<()> {
         let edits = create_edits()?;
@@ -0,0 +1,143 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        utils::{config::IbisConfigOAuthProvider, error::BackendResult},
+    },
+    common::utils::http_protocol_str,
+};
+use anyhow::anyhow;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{sync::LazyLock, time::Duration};
+use url::Url;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("init reqwest")
+});
+
+/// Endpoints of the provider, from `/.well-known/openid-configuration`.
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    userinfo_endpoint: Url,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// Claims about the user which are returned by the provider.
+#[derive(Deserialize)]
+pub struct OAuthUserInfo {
+    /// Unique and stable identifier of the user at the provider
+    pub sub: String,
+    pub preferred_username: Option<String>,
+}
+
+pub fn find_provider<'a>(
+    name: &str,
+    context: &'a IbisContext,
+) -> BackendResult<&'a IbisConfigOAuthProvider> {
+    Ok(context
+        .config
+        .oauth_providers
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or(anyhow!("Unknown oauth provider"))?)
+}
+
+/// Generate a random code verifier for PKCE, and the S256 challenge which is derived from it.
+/// Only the challenge is sent to the provider during authorization, the verifier is required
+/// to redeem the authorization code so that an intercepted code is useless.
+pub fn generate_pkce() -> (String, String) {
+    let verifier: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect();
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(&verifier));
+    (verifier, challenge)
+}
+
+/// Url where the user logs in at the provider, which then redirects back to the frontend with
+/// an authorization code.
+pub async fn authorize_url(
+    provider: &IbisConfigOAuthProvider,
+    state: &str,
+    pkce_challenge: &str,
+    context: &IbisContext,
+) -> BackendResult<Url> {
+    let mut url = fetch_metadata(provider).await?.authorization_endpoint;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &provider.client_id)
+        .append_pair("redirect_uri", &redirect_url(context))
+        .append_pair("scope", "openid profile")
+        .append_pair("state", state)
+        .append_pair("code_challenge", pkce_challenge)
+        .append_pair("code_challenge_method", "S256");
+    Ok(url)
+}
+
+/// Exchange the authorization code for an access token, and use it to read the user info. These
+/// requests go directly to the provider, so there is no need to verify the signature of an
+/// id token.
+pub async fn fetch_user_info(
+    provider: &IbisConfigOAuthProvider,
+    code: &str,
+    pkce_verifier: &str,
+    context: &IbisContext,
+) -> BackendResult<OAuthUserInfo> {
+    let metadata = fetch_metadata(provider).await?;
+    let redirect_url = redirect_url(context);
+    let params = [
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_url.as_str()),
+        ("client_id", provider.client_id.as_str()),
+        ("client_secret", provider.client_secret.as_str()),
+        ("code_verifier", pkce_verifier),
+    ];
+    let token: TokenResponse = CLIENT
+        .post(metadata.token_endpoint)
+        .form(&params)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(CLIENT
+        .get(metadata.userinfo_endpoint)
+        .bearer_auth(token.access_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+async fn fetch_metadata(provider: &IbisConfigOAuthProvider) -> BackendResult<ProviderMetadata> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        provider.issuer.trim_end_matches('/')
+    );
+    Ok(CLIENT
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+fn redirect_url(context: &IbisContext) -> String {
+    let domain = &context.config.federation.domain;
+    format!("{}://{domain}/account/oauth_callback", http_protocol_str())
+}
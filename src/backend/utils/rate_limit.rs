@@ -0,0 +1,179 @@
+use crate::{backend::utils::config::IbisConfigRateLimit, common::newtypes::PersonId};
+use log::warn;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// How often buckets which are full again get removed, to avoid unbounded memory usage.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Separate buckets are used for each type, so that eg many searches dont prevent a user
+/// from editing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitType {
+    Register,
+    Login,
+    Edit,
+    Comment,
+    Search,
+    Inbox,
+}
+
+impl RateLimitType {
+    /// Maximum number of requests, and the interval in which they become available again.
+    fn limit(self, config: &IbisConfigRateLimit) -> (u32, Duration) {
+        use RateLimitType::*;
+        const MINUTE: Duration = Duration::from_secs(60);
+        match self {
+            Register => (config.register, Duration::from_secs(3600)),
+            Login => (config.login, MINUTE),
+            Edit => (config.edit, MINUTE),
+            Comment => (config.comment, MINUTE),
+            Search => (config.search, MINUTE),
+            Inbox => (config.inbox, MINUTE),
+        }
+    }
+}
+
+/// Logged in users are limited by their account, anonymous requests by IP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    Ip(IpAddr),
+    User(PersonId),
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+struct RateLimitState {
+    buckets: HashMap<(RateLimitType, RateLimitKey), Bucket>,
+    last_cleanup: Instant,
+}
+
+/// In-memory token bucket rate limiter. Each bucket starts full and refills continuously,
+/// so that short bursts up to the configured limit are allowed.
+pub struct RateLimiter {
+    config: IbisConfigRateLimit,
+    trusted_proxies: Vec<IpAddr>,
+    state: Mutex<RateLimitState>,
+}
+
+impl RateLimiter {
+    pub fn new(config: IbisConfigRateLimit) -> Self {
+        let trusted_proxies = config
+            .trusted_proxies
+            .iter()
+            .flat_map(|p| p.split(','))
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| match p.parse() {
+                Ok(ip) => Some(ip),
+                Err(_) => {
+                    warn!("Invalid ip address {p} in rate_limit.trusted_proxies");
+                    None
+                }
+            })
+            .collect();
+        RateLimiter {
+            config,
+            trusted_proxies,
+            state: Mutex::new(RateLimitState {
+                buckets: HashMap::new(),
+                last_cleanup: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.contains(&ip)
+    }
+
+    /// Take a token from the bucket for the given type and key. If the bucket is empty, returns
+    /// the time until the next token becomes available.
+    pub fn check(&self, type_: RateLimitType, key: RateLimitKey) -> Result<(), Duration> {
+        let (max_requests, interval) = type_.limit(&self.config);
+        if max_requests == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(max_requests);
+        let tokens_per_sec = capacity / interval.as_secs_f64();
+        let now = Instant::now();
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if now.duration_since(state.last_cleanup) > CLEANUP_INTERVAL {
+            let config = &self.config;
+            state
+                .buckets
+                .retain(|(t, _), b| now.duration_since(b.last_refill) < t.limit(config).1);
+            state.last_cleanup = now;
+        }
+        let bucket = state.buckets.entry((type_, key)).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * tokens_per_sec).min(capacity);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / tokens_per_sec,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_rate_limit() {
+        let config = IbisConfigRateLimit {
+            login: 2,
+            search: 0,
+            ..Default::default()
+        };
+        let limiter = RateLimiter::new(config);
+        let ip = RateLimitKey::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let user = RateLimitKey::User(PersonId(1));
+
+        // bucket is full initially, allowing a burst up to the limit
+        assert!(limiter.check(RateLimitType::Login, ip).is_ok());
+        assert!(limiter.check(RateLimitType::Login, ip).is_ok());
+        let Err(retry_after) = limiter.check(RateLimitType::Login, ip) else {
+            panic!("third login should be rate limited");
+        };
+        assert!(retry_after > Duration::from_secs(25));
+        assert!(retry_after <= Duration::from_secs(30));
+
+        // other keys and types use separate buckets
+        assert!(limiter.check(RateLimitType::Login, user).is_ok());
+        assert!(limiter.check(RateLimitType::Edit, ip).is_ok());
+
+        // limit of 0 is disabled
+        for _ in 0..100 {
+            assert!(limiter.check(RateLimitType::Search, ip).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_trusted_proxies() {
+        let config = IbisConfigRateLimit {
+            trusted_proxies: Some("127.0.0.1, ::1,invalid".to_string()),
+            ..Default::default()
+        };
+        let limiter = RateLimiter::new(config);
+        assert!(limiter.is_trusted_proxy(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(limiter.is_trusted_proxy(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+        assert!(!limiter.is_trusted_proxy(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+    }
+}
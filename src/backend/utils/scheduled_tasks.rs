@@ -1,17 +1,34 @@
-use crate::backend::{database::DbPool, utils::error::BackendResult};
+use crate::backend::{
+    database::{captcha::DbCaptchaAnswer, DbPool, IbisContext},
+    utils::{email::send_notification_digests, error::BackendResult},
+};
 use clokwerk::{Scheduler, TimeUnits};
 use diesel::{sql_query, RunQueryDsl};
 use log::{error, info};
 use std::time::Duration;
 
-pub fn start(pool: DbPool) {
+pub fn start(context: IbisContext) {
     let mut scheduler = Scheduler::new();
 
+    let pool = context.db_pool.clone();
     active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
     scheduler.every(1.hour()).run(move || {
         active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
     });
 
+    let context_ = context.clone();
+    scheduler.every(1.hour()).run(move || {
+        DbCaptchaAnswer::delete_expired(&context_)
+            .inspect_err(|e| error!("{e}"))
+            .ok();
+    });
+
+    scheduler.every(1.day()).run(move || {
+        send_notification_digests(&context)
+            .inspect_err(|e| error!("{e}"))
+            .ok();
+    });
+
     let _ = scheduler.watch_thread(Duration::from_secs(60));
 }
 
@@ -0,0 +1,91 @@
+use crate::{
+    backend::{database::IbisContext, utils::error::BackendResult},
+    common::user::DbLocalUser,
+};
+use anyhow::anyhow;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+/// Number of recovery codes which are generated when enabling totp.
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub fn generate_totp_secret() -> String {
+    Secret::generate_secret().to_encoded().to_string()
+}
+
+/// Build the totp instance with common settings so that it works with all authenticator apps.
+pub fn build_totp(secret: &str, username: &str, context: &IbisContext) -> BackendResult<TOTP> {
+    let secret = Secret::Encoded(secret.to_string()).to_bytes()?;
+    // issuer must not contain a colon, so remove the port
+    let domain = &context.config.federation.domain;
+    let issuer = domain.split(':').next().map(str::to_string);
+    Ok(TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        issuer,
+        username.to_string(),
+    )?)
+}
+
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(10)
+                .map(char::from)
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .collect()
+}
+
+pub fn hash_recovery_code(code: &str) -> String {
+    hex::encode(Sha256::digest(code.trim().to_lowercase()))
+}
+
+/// Check the second factor during login, either a totp token or one of the recovery codes.
+/// Both can only be used once: tokens are rejected if their time step is not newer than the
+/// last login, and recovery codes are removed after use.
+pub fn check_totp_token(
+    local_user: &DbLocalUser,
+    username: &str,
+    token: &str,
+    context: &IbisContext,
+) -> BackendResult<()> {
+    let Some(secret) = &local_user.totp_secret else {
+        return Err(anyhow!("Two-factor authentication is not enabled").into());
+    };
+    let totp = build_totp(secret, username, context)?;
+    let valid = if let Some(step) = matching_totp_step(&totp, token.trim())? {
+        DbLocalUser::update_totp_last_step(local_user.id.0, step, context)?
+    } else {
+        let hash = hash_recovery_code(token);
+        DbLocalUser::remove_recovery_code(local_user.id.0, &hash, context)?
+    };
+    if !valid {
+        return Err(anyhow!("Invalid two-factor authentication token").into());
+    }
+    Ok(())
+}
+
+/// Returns the time step which the token was generated for, if it is valid for the current time
+/// (including allowed skew).
+fn matching_totp_step(totp: &TOTP, token: &str) -> BackendResult<Option<i64>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let current = now / totp.step;
+    let skew = u64::from(totp.skew);
+    // check each step separately to find out which one matches
+    let exact = TOTP {
+        skew: 0,
+        ..totp.clone()
+    };
+    Ok((current.saturating_sub(skew)..=current + skew)
+        .find(|step| exact.check(token, step * totp.step))
+        .map(|step| step as i64))
+}
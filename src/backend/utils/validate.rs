@@ -1,4 +1,5 @@
 use super::error::BackendResult;
+use crate::common::user::DbPerson;
 use anyhow::anyhow;
 use regex::Regex;
 use std::sync::LazyLock;
@@ -35,6 +36,13 @@ pub fn validate_display_name(name: &Option<String>) -> BackendResult<()> {
     Ok(())
 }
 
+pub fn validate_email(email: &str) -> BackendResult<()> {
+    if email.parse::<lettre::Address>().is_err() {
+        return Err(anyhow!("Invalid email address").into());
+    }
+    Ok(())
+}
+
 pub fn validate_comment_max_depth(depth: i32) -> BackendResult<()> {
     if depth > 50 {
         return Err(anyhow!("Max comment depth reached").into());
@@ -49,6 +57,13 @@ pub fn validate_not_empty(text: &str) -> BackendResult<()> {
     Ok(())
 }
 
+pub fn validate_not_banned(person: &DbPerson) -> BackendResult<()> {
+    if person.is_banned() {
+        return Err(anyhow!("User is banned").into());
+    }
+    Ok(())
+}
+
 #[test]
 #[expect(clippy::unwrap_used)]
 fn test_validate_article_title() {
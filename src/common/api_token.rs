@@ -0,0 +1,76 @@
+use super::newtypes::{ApiTokenId, PersonId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    crate::backend::database::schema::api_token,
+    diesel::{Identifiable, Queryable, Selectable},
+};
+
+/// Prefix for all api tokens, to distinguish them from login tokens.
+pub const API_TOKEN_PREFIX: &str = "ibis_";
+
+/// Personal token which bots and scripts can use for authentication instead of the login
+/// cookie. Only the hash of the token is stored.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = api_token, check_for_backend(diesel::pg::Pg)))]
+pub struct DbApiToken {
+    pub id: ApiTokenId,
+    pub person_id: PersonId,
+    pub name: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    /// Read private data like notifications
+    pub scope_read: bool,
+    /// Create and edit articles
+    pub scope_edit: bool,
+    /// Write comments
+    pub scope_comment: bool,
+    /// Perform admin actions, only available for admins
+    pub scope_admin: bool,
+    pub published: DateTime<Utc>,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiTokenScope {
+    Read,
+    Edit,
+    Comment,
+    Admin,
+}
+
+impl DbApiToken {
+    pub fn has_scope(&self, scope: ApiTokenScope) -> bool {
+        use ApiTokenScope::*;
+        match scope {
+            Read => self.scope_read,
+            Edit => self.scope_edit,
+            Comment => self.scope_comment,
+            Admin => self.scope_admin,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct CreateApiTokenParams {
+    pub name: String,
+    pub scope_read: bool,
+    pub scope_edit: bool,
+    pub scope_comment: bool,
+    pub scope_admin: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CreateApiTokenResponse {
+    pub api_token: DbApiToken,
+    /// The token itself, pass it in the `Authorization: Bearer` or `auth` header. It is only
+    /// shown once.
+    pub token: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DeleteApiTokenParams {
+    pub id: ApiTokenId,
+}
@@ -27,6 +27,22 @@ pub struct GetArticleParams {
 pub struct ListArticlesParams {
     pub only_local: Option<bool>,
     pub instance_id: Option<InstanceId>,
+    pub sort: Option<ArticleSort>,
+    /// Id of the last article from the previous page
+    pub cursor: Option<ArticleId>,
+    /// Number of articles per page, maximum 100
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ArticleSort {
+    /// Most recently edited articles first
+    #[default]
+    RecentlyEdited,
+    /// Most recently created articles first
+    Newest,
+    /// Ordered by title
+    Alphabetical,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -37,6 +53,8 @@ pub struct DbArticleView {
     pub instance: DbInstance,
     pub comments: Vec<DbCommentView>,
     pub latest_version: EditVersion,
+    /// If the current user is watching this article for edits
+    pub watched: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -55,6 +73,7 @@ pub struct DbArticle {
     pub protected: bool,
     pub approved: bool,
     pub published: DateTime<Utc>,
+    pub deleted: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -80,12 +99,31 @@ pub struct EditArticleParams {
     pub resolve_conflict_id: Option<ConflictId>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RevertArticleParams {
+    pub article_id: ArticleId,
+    /// The earlier version which the article text should be reset to
+    pub version: EditVersion,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ProtectArticleParams {
     pub article_id: ArticleId,
     pub protected: bool,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeleteArticleParams {
+    pub article_id: ArticleId,
+    pub deleted: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MoveArticleParams {
+    pub article_id: ArticleId,
+    pub new_title: String,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ForkArticleParams {
     pub article_id: ArticleId,
@@ -98,9 +136,40 @@ pub struct ApproveArticleParams {
     pub approve: bool,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct WatchArticleParams {
+    pub article_id: ArticleId,
+    pub watch: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct SearchArticleParams {
     pub query: String,
+    /// Only return articles from this instance
+    pub instance_id: Option<InstanceId>,
+    /// If true only return local articles, if false only remote articles
+    pub local: Option<bool>,
+    /// Id of the last article from the previous page
+    pub cursor: Option<ArticleId>,
+    /// Number of results per page, maximum 100
+    pub limit: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct SearchArticleResult {
+    pub id: ArticleId,
+    pub title: String,
+    #[cfg(feature = "ssr")]
+    pub ap_id: ObjectId<DbArticle>,
+    #[cfg(not(feature = "ssr"))]
+    pub ap_id: String,
+    pub instance_id: InstanceId,
+    pub local: bool,
+    /// Excerpts of the article text which contain the search terms. The text is HTML-escaped,
+    /// and matching words are wrapped in `<mark>` tags.
+    pub snippet: String,
 }
 
 /// Represents a single change to the article.
@@ -126,12 +195,28 @@ pub struct DbEdit {
     pub previous_version_id: EditVersion,
     pub published: DateTime<Utc>,
     pub pending: bool,
+    /// Full article text after this edit was applied. Only stored for every n-th edit, so that
+    /// older versions can be generated without replaying all edits from the beginning.
+    #[serde(skip)]
+    pub snapshot: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct GetEditList {
     pub article_id: Option<ArticleId>,
     pub person_id: Option<PersonId>,
+    pub sort: Option<EditSort>,
+    /// Id of the last edit from the previous page
+    pub cursor: Option<EditId>,
+    /// Number of edits per page, maximum 100
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum EditSort {
+    #[default]
+    Oldest,
+    Newest,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -191,6 +276,12 @@ pub struct GetConflictParams {
     pub conflict_id: ConflictId,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct UploadImageResponse {
+    /// Path of the uploaded image, for use in markdown like `![description](/media/...)`
+    pub path: String,
+}
+
 #[test]
 fn test_edit_versions() {
     let default = EditVersion::default();
@@ -30,7 +30,6 @@ pub struct DbComment {
     pub deleted: bool,
     pub published: DateTime<Utc>,
     pub updated: Option<DateTime<Utc>>,
-    pub read_by_parent_creator: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -64,6 +63,7 @@ pub struct EditCommentParams {
     pub deleted: Option<bool>,
 }
 
+// TODO: deprecated, remove in 0.3
 #[derive(Deserialize, Serialize, Debug)]
 pub struct MarkAsReadParams {
     pub id: CommentId,
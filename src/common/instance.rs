@@ -1,7 +1,7 @@
 use super::{
     article::DbArticle,
-    newtypes::InstanceId,
-    user::{DbPerson, LocalUserView},
+    newtypes::{FederationListId, InstanceId},
+    user::{DbPerson, LocalUserView, OAuthProvider},
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -10,7 +10,7 @@ use url::Url;
 #[cfg(feature = "ssr")]
 use {
     crate::backend::{
-        database::schema::instance,
+        database::schema::{federation_list, instance},
         federation::objects::articles_collection::DbArticleCollection,
         federation::objects::instance_collection::DbInstanceCollection,
     },
@@ -66,28 +66,52 @@ pub struct InstanceView2 {
     pub followers: Vec<DbPerson>,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ssr", derive(Document))]
+pub enum RegistrationMode {
+    /// Anyone can create an account
+    #[default]
+    Open,
+    /// New users need to answer the registration question, and can only login after an admin
+    /// approved their application
+    RequireApplication,
+    /// No new accounts can be created
+    Closed,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, SmartDefault)]
 #[serde(default)]
 #[serde(deny_unknown_fields)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Document))]
-#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ssr", derive(Document))]
 pub struct Options {
-    /// Whether users can create new accounts
-    #[default = true]
-    #[cfg_attr(feature = "ssr", doku(example = "true"))]
-    pub registration_open: bool,
+    /// Whether users can create new accounts. Can be open, require_application or closed
+    #[cfg_attr(feature = "ssr", doku(example = "open"))]
+    pub registration_mode: RegistrationMode,
+    /// Question which users need to answer when registering with require_application
+    #[default("Why do you want to join this wiki?")]
+    #[cfg_attr(feature = "ssr", doku(example = "Why do you want to join this wiki?"))]
+    pub registration_question: String,
+    /// Whether users need to solve a captcha when registering
+    #[default = false]
+    #[cfg_attr(feature = "ssr", doku(example = "false"))]
+    pub registration_captcha: bool,
     /// Whether admins need to approve new articles
     #[default = false]
     #[cfg_attr(feature = "ssr", doku(example = "false"))]
     pub article_approval: bool,
+    /// Deprecated, use registration_mode instead. True is equivalent to open and false to closed
+    #[default(None)]
+    #[serde(skip_serializing)]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub registration_open: Option<bool>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
-#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct SiteView {
     pub my_profile: Option<LocalUserView>,
     pub config: Options,
+    pub oauth_providers: Vec<OAuthProvider>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -95,6 +119,14 @@ pub struct GetInstanceParams {
     pub id: Option<InstanceId>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListInstancesParams {
+    /// Id of the last instance from the previous page
+    pub cursor: Option<InstanceId>,
+    /// Number of instances per page, maximum 100
+    pub limit: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FollowInstanceParams {
     pub id: InstanceId,
@@ -105,3 +137,29 @@ pub struct UpdateInstanceParams {
     pub name: Option<String>,
     pub topic: Option<String>,
 }
+
+/// Domain which is explicitly allowed or blocked for federation. If any domain is allowed,
+/// federation with all other domains is blocked.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = federation_list, check_for_backend(diesel::pg::Pg)))]
+pub struct DbFederationListEntry {
+    pub id: FederationListId,
+    pub domain: String,
+    pub blocked: bool,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AddFederationListParams {
+    pub domain: String,
+    /// Add domain to blocklist if true, otherwise to allowlist
+    pub blocked: bool,
+    /// Delete all articles and users from the domain. Only possible when blocking
+    pub purge: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RemoveFederationListParams {
+    pub domain: String,
+}
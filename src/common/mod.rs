@@ -1,14 +1,21 @@
+pub mod api_token;
 pub mod article;
 pub mod comment;
 pub mod instance;
+pub mod mod_log;
 pub mod newtypes;
+pub mod registration_application;
+pub mod report;
 pub mod user;
 pub mod utils;
 pub mod validation;
 
-use article::{ApiConflict, DbArticle};
+use article::{DbArticle, EditView};
 use chrono::{DateTime, Utc};
 use comment::CommentViewWithArticle;
+use newtypes::{ConflictId, NotificationId};
+use registration_application::RegistrationApplicationView;
+use report::ReportView;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -19,7 +26,7 @@ pub static AUTH_COOKIE: &str = "auth";
 #[derive(Clone, Debug)]
 pub struct Auth(pub Option<String>);
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SuccessResponse {
     success: bool,
 }
@@ -35,21 +42,48 @@ pub struct ResolveObjectParams {
     pub id: Url,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiNotification {
+    pub id: NotificationId,
+    pub read: bool,
+    pub published: DateTime<Utc>,
+    pub data: Notification,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Notification {
-    // TODO: this should only return conflict id and article name
-    EditConflict(ApiConflict),
+    /// Use `conflict_id` with `/api/v1/conflict` to get the three-way-merge. Before notifications
+    /// were stored in the database, this contained the full `ApiConflict` instead.
+    EditConflict {
+        conflict_id: ConflictId,
+        summary: String,
+        article: DbArticle,
+    },
     ArticleApprovalRequired(DbArticle),
     Reply(CommentViewWithArticle),
+    /// New edit on a watched article
+    ArticleEdited(EditView),
+    /// Report about an article, edit or comment, sent to admins
+    Report(ReportView),
+    /// New user registered and needs to be approved, sent to admins
+    RegistrationApplication(RegistrationApplicationView),
 }
 
-impl Notification {
-    pub fn published(&self) -> &DateTime<Utc> {
-        use Notification::*;
-        match self {
-            EditConflict(api_conflict) => &api_conflict.published,
-            ArticleApprovalRequired(db_article) => &db_article.published,
-            Reply(comment) => &comment.comment.published,
-        }
-    }
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListNotificationsParams {
+    /// Only return notifications which are not marked as read
+    pub unread_only: Option<bool>,
+    /// Continue listing after the notification with this id
+    pub cursor: Option<NotificationId>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct MarkNotificationAsReadParams {
+    pub id: NotificationId,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeleteNotificationParams {
+    pub id: NotificationId,
 }
@@ -0,0 +1,147 @@
+use super::{
+    article::DbArticle,
+    newtypes::{ArticleId, ModLogId, PersonId},
+    user::DbPerson,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    crate::backend::database::schema::mod_log,
+    diesel::{
+        deserialize::FromSqlRow,
+        expression::AsExpression,
+        sql_types::Text,
+        Identifiable,
+        Queryable,
+        Selectable,
+    },
+};
+
+/// Actions taken by admins, which are listed in the public moderation log.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(AsExpression, FromSqlRow))]
+#[cfg_attr(feature = "ssr", diesel(sql_type = Text))]
+pub enum ModAction {
+    BanUser,
+    UnbanUser,
+    ProtectArticle,
+    UnprotectArticle,
+    DeleteArticle,
+    RestoreArticle,
+    ApproveArticle,
+    RejectArticle,
+    ResolveReport,
+    DismissReport,
+    ApproveRegistration,
+    DenyRegistration,
+    DeleteUser,
+}
+
+impl ModAction {
+    pub fn as_str(&self) -> &'static str {
+        use ModAction::*;
+        match self {
+            BanUser => "ban_user",
+            UnbanUser => "unban_user",
+            ProtectArticle => "protect_article",
+            UnprotectArticle => "unprotect_article",
+            DeleteArticle => "delete_article",
+            RestoreArticle => "restore_article",
+            ApproveArticle => "approve_article",
+            RejectArticle => "reject_article",
+            ResolveReport => "resolve_report",
+            DismissReport => "dismiss_report",
+            ApproveRegistration => "approve_registration",
+            DenyRegistration => "deny_registration",
+            DeleteUser => "delete_user",
+        }
+    }
+
+    pub fn from_name(action: &str) -> Option<Self> {
+        use ModAction::*;
+        [
+            BanUser,
+            UnbanUser,
+            ProtectArticle,
+            UnprotectArticle,
+            DeleteArticle,
+            RestoreArticle,
+            ApproveArticle,
+            RejectArticle,
+            ResolveReport,
+            DismissReport,
+            ApproveRegistration,
+            DenyRegistration,
+            DeleteUser,
+        ]
+        .into_iter()
+        .find(|a| a.as_str() == action)
+    }
+
+    /// Human readable description, used in the frontend.
+    pub fn description(&self) -> &'static str {
+        use ModAction::*;
+        match self {
+            BanUser => "banned",
+            UnbanUser => "unbanned",
+            ProtectArticle => "protected",
+            UnprotectArticle => "unprotected",
+            DeleteArticle => "deleted",
+            RestoreArticle => "restored",
+            ApproveArticle => "approved",
+            RejectArticle => "rejected",
+            ResolveReport => "resolved report for",
+            DismissReport => "dismissed report for",
+            ApproveRegistration => "approved registration of",
+            DenyRegistration => "denied registration of",
+            // the target is removed together with the account
+            DeleteUser => "deleted a user account",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = mod_log, check_for_backend(diesel::pg::Pg)))]
+pub struct DbModLog {
+    pub id: ModLogId,
+    /// Empty if the moderator account was deleted
+    pub mod_id: Option<PersonId>,
+    pub action: ModAction,
+    pub target_person_id: Option<PersonId>,
+    pub target_article_id: Option<ArticleId>,
+    pub reason: Option<String>,
+    /// Expiration time for bans
+    pub expires: Option<DateTime<Utc>>,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct ModLogView {
+    pub mod_log: DbModLog,
+    /// Empty if the moderator account was deleted
+    pub moderator: Option<DbPerson>,
+    pub target_person: Option<DbPerson>,
+    /// Empty if the article was rejected, as it gets deleted in that case
+    pub target_article: Option<DbArticle>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct BanUserParams {
+    pub person_id: PersonId,
+    /// Set to false to lift an existing ban
+    pub ban: bool,
+    pub reason: Option<String>,
+    /// Ban is permanent if this is not set
+    pub expires: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ListModLogParams {
+    /// Continue listing after the entry with this id
+    pub cursor: Option<ModLogId>,
+    pub limit: Option<i64>,
+}
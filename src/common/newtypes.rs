@@ -25,3 +25,31 @@ pub struct ConflictId(pub i32);
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(DieselNewType))]
 pub struct CommentId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct NotificationId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct ModLogId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct FederationListId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct ReportId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct RegistrationApplicationId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct ApiTokenId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+pub struct LoginSessionId(pub i32);
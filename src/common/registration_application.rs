@@ -0,0 +1,41 @@
+use super::{
+    newtypes::{PersonId, RegistrationApplicationId},
+    user::DbPerson,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    crate::backend::database::schema::registration_application,
+    diesel::{Identifiable, Queryable, Selectable},
+};
+
+/// Answer to the registration question, written when a user registers while registration mode
+/// is `require_application`. The user can only login after an admin accepted it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = registration_application, check_for_backend(diesel::pg::Pg)))]
+pub struct DbRegistrationApplication {
+    pub id: RegistrationApplicationId,
+    pub person_id: PersonId,
+    pub answer: String,
+    pub accepted: bool,
+    /// Admin who accepted or denied the application
+    pub admin_id: Option<PersonId>,
+    pub deny_reason: Option<String>,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RegistrationApplicationView {
+    pub application: DbRegistrationApplication,
+    pub person: DbPerson,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ApproveRegistrationParams {
+    pub application_id: RegistrationApplicationId,
+    pub approve: bool,
+    /// Shown to the user when trying to login
+    pub deny_reason: Option<String>,
+}
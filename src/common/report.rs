@@ -0,0 +1,57 @@
+use super::{
+    article::{DbArticle, DbEdit},
+    comment::DbComment,
+    newtypes::{ArticleId, CommentId, EditId, PersonId, ReportId},
+    user::DbPerson,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    crate::backend::database::schema::report,
+    diesel::{Identifiable, Queryable, Selectable},
+};
+
+/// Report about an article, or a specific edit or comment of the article. It is stored both on
+/// the instance of the reporter, and on the instance where the article is hosted.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = report, check_for_backend(diesel::pg::Pg)))]
+pub struct DbReport {
+    pub id: ReportId,
+    pub creator_id: PersonId,
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+    #[serde(skip)]
+    pub ap_id: String,
+    pub local: bool,
+    pub resolved: bool,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReportView {
+    pub report: DbReport,
+    pub article: DbArticle,
+    pub edit: Option<DbEdit>,
+    pub comment: Option<DbComment>,
+    /// Author of the reported edit or comment
+    pub creator: Option<DbPerson>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CreateReportParams {
+    pub article_id: ArticleId,
+    /// Set this to report a specific edit of the article
+    pub edit_id: Option<EditId>,
+    /// Set this to report a comment on the article
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ResolveReportParams {
+    pub report_id: ReportId,
+}
@@ -1,27 +1,46 @@
 use super::{
     instance::DbInstance,
-    newtypes::{InstanceId, PersonId},
+    newtypes::{InstanceId, LoginSessionId, PersonId},
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use url::Url;
+use uuid::Uuid;
 #[cfg(feature = "ssr")]
 use {
-    crate::backend::database::schema::{local_user, person},
+    crate::backend::database::schema::{local_user, login_session, person},
     activitypub_federation::fetch::object_id::ObjectId,
     diesel::{Identifiable, Queryable, Selectable},
 };
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct RegisterUserParams {
     pub username: String,
     pub password: String,
+    /// Answer to the registration question, required if registration mode is
+    /// `require_application`
+    pub application_answer: Option<String>,
+    /// Required if registration captcha is enabled, get it from `/api/v1/account/captcha`
+    pub captcha_uuid: Option<Uuid>,
+    pub captcha_answer: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CaptchaResponse {
+    /// The image is available at `/api/v1/account/captcha/{uuid}`
+    pub uuid: Uuid,
+}
+
+/// Returned by login if the user has two-factor authentication enabled, but didn't pass
+/// `totp_token`.
+pub const TOTP_REQUIRED: &str = "Two-factor authentication token required";
+
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct LoginUserParams {
     pub username: String,
     pub password: String,
+    /// Current totp token or a recovery code, required if two-factor authentication is enabled
+    pub totp_token: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -43,6 +62,25 @@ pub struct DbLocalUser {
     pub password_encrypted: String,
     pub person_id: PersonId,
     pub admin: bool,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    /// Send a daily email with unread notifications
+    pub email_notifications: bool,
+    /// Login tokens which were issued before this time are invalid
+    #[serde(skip)]
+    pub password_changed: Option<DateTime<Utc>>,
+    /// Base32 encoded secret for two-factor authentication. Only used for login if
+    /// `totp_enabled` is true.
+    #[serde(skip)]
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    /// Sha256 hashes of one-time codes which can be used for login instead of totp
+    #[serde(skip)]
+    pub totp_recovery_codes: Vec<String>,
+    /// Time step of the last totp token which was used for login. Tokens can only be used once,
+    /// so older ones are rejected.
+    #[serde(skip)]
+    pub totp_last_step: Option<i64>,
 }
 
 /// Federation related data from a local or remote user.
@@ -66,9 +104,22 @@ pub struct DbPerson {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    /// Url of the profile picture
+    pub avatar: Option<String>,
+    /// Url of the header image on the profile page
+    pub banner: Option<String>,
+    /// Banned users can't edit articles or write comments on this instance
+    pub banned: bool,
+    /// If set, the ban is lifted automatically at this time
+    pub ban_expires: Option<DateTime<Utc>>,
 }
 
 impl DbPerson {
+    /// Check if the user is currently banned, taking into account the expiration time.
+    pub fn is_banned(&self) -> bool {
+        self.banned && self.ban_expires.is_none_or(|e| e > Utc::now())
+    }
+
     pub fn inbox_url(&self) -> Url {
         Url::parse(&self.inbox_url).expect("can parse inbox url")
     }
@@ -82,7 +133,132 @@ pub struct GetUserParams {
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct UpdateUserParams {
-    pub person_id: PersonId,
     pub display_name: Option<String>,
     pub bio: Option<String>,
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct UpdateEmailParams {
+    pub email: Option<String>,
+    pub email_notifications: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct VerifyEmailParams {
+    pub token: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RequestPasswordResetParams {
+    pub email: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ResetPasswordParams {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ChangePasswordParams {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DeleteAccountParams {
+    pub password: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AdminDeleteUserParams {
+    pub person_id: PersonId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AdminResetPasswordParams {
+    pub person_id: PersonId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AdminResetPasswordResponse {
+    /// Link where the user can set a new password, valid for one hour
+    pub reset_link: String,
+}
+
+/// Created on each login, so that users can see where they are logged in and revoke sessions.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "ssr", diesel(table_name = login_session, check_for_backend(diesel::pg::Pg)))]
+pub struct DbLoginSession {
+    pub id: LoginSessionId,
+    pub person_id: PersonId,
+    pub user_agent: Option<String>,
+    pub published: DateTime<Utc>,
+    pub last_used: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LoginSessionView {
+    pub session: DbLoginSession,
+    /// Session which is used for the current request
+    pub current: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RevokeSessionParams {
+    pub id: LoginSessionId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GenerateTotpSecretResponse {
+    /// Base32 encoded secret, for manual entry in the authenticator app
+    pub secret: String,
+    /// Url with `otpauth://` scheme which can be shown as QR code
+    pub totp_url: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct EnableTotpParams {
+    /// Token from the authenticator app, to confirm that it was setup correctly
+    pub totp_token: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct EnableTotpResponse {
+    /// Codes which can be used once each for login if the authenticator is lost. They are only
+    /// shown once.
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DisableTotpParams {
+    pub password: String,
+    /// Current totp token or a recovery code
+    pub totp_token: String,
+}
+
+/// OpenID Connect provider which is shown on the login page.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct OAuthProvider {
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OAuthAuthorizeParams {
+    pub provider: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OAuthAuthorizeResponse {
+    /// Url of the provider where the user needs to be redirected for login
+    pub url: String,
+}
+
+/// Sent after the provider redirected back to `/account/oauth_callback`.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OAuthLoginParams {
+    pub code: String,
+    pub state: String,
+}
@@ -2,6 +2,11 @@ use crate::common::article::DbArticle;
 use leptos::server_fn::error::ServerFnErrorErr;
 
 pub fn can_edit_article(article: &DbArticle, is_admin: bool) -> Result<(), ServerFnErrorErr> {
+    if article.deleted {
+        return Err(ServerFnErrorErr::ServerError(
+            "Article is deleted and can't be edited".to_string(),
+        ));
+    }
     let err = ServerFnErrorErr::ServerError(
         "Article is protected, only admins on origin instance can edit".to_string(),
     );
@@ -0,0 +1,34 @@
+use super::ApiClient;
+use crate::{
+    common::{
+        api_token::{
+            CreateApiTokenParams,
+            CreateApiTokenResponse,
+            DbApiToken,
+            DeleteApiTokenParams,
+        },
+        newtypes::ApiTokenId,
+        SuccessResponse,
+    },
+    frontend::utils::errors::FrontendResult,
+};
+use http::Method;
+
+impl ApiClient {
+    pub async fn create_api_token(
+        &self,
+        params: &CreateApiTokenParams,
+    ) -> FrontendResult<CreateApiTokenResponse> {
+        self.post("/api/v1/account/api_token", Some(params)).await
+    }
+
+    pub async fn list_api_tokens(&self) -> FrontendResult<Vec<DbApiToken>> {
+        self.get("/api/v1/account/api_token", None::<()>).await
+    }
+
+    pub async fn delete_api_token(&self, id: ApiTokenId) -> FrontendResult<SuccessResponse> {
+        let params = DeleteApiTokenParams { id };
+        self.send(Method::DELETE, "/api/v1/account/api_token", Some(params))
+            .await
+    }
+}
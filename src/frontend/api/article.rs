@@ -1,4 +1,4 @@
-use super::ApiClient;
+use super::{ApiClient, UploadFile};
 use crate::{
     common::{
         article::{
@@ -7,6 +7,7 @@ use crate::{
             CreateArticleParams,
             DbArticle,
             DbArticleView,
+            DeleteArticleParams,
             DeleteConflictParams,
             EditArticleParams,
             EditView,
@@ -15,10 +16,15 @@ use crate::{
             GetConflictParams,
             GetEditList,
             ListArticlesParams,
+            MoveArticleParams,
             ProtectArticleParams,
+            RevertArticleParams,
+            UploadImageResponse,
+            WatchArticleParams,
         },
         newtypes::{ArticleId, ConflictId},
         ResolveObjectParams,
+        SuccessResponse,
     },
     frontend::utils::errors::FrontendResult,
 };
@@ -26,6 +32,9 @@ use http::Method;
 use log::error;
 use url::Url;
 
+/// Maximum page size allowed by the backend.
+const EDIT_PAGE_LIMIT: i64 = 100;
+
 impl ApiClient {
     pub async fn create_article(
         &self,
@@ -53,6 +62,17 @@ impl ApiClient {
         self.post("/api/v1/article/fork", Some(params)).await
     }
 
+    pub async fn move_article(&self, params: &MoveArticleParams) -> FrontendResult<DbArticleView> {
+        self.post("/api/v1/article/move", Some(params)).await
+    }
+
+    pub async fn revert_article(
+        &self,
+        params: &RevertArticleParams,
+    ) -> FrontendResult<DbArticleView> {
+        self.post("/api/v1/article/revert", Some(params)).await
+    }
+
     pub async fn protect_article(
         &self,
         params: &ProtectArticleParams,
@@ -60,19 +80,50 @@ impl ApiClient {
         self.post("/api/v1/article/protect", Some(params)).await
     }
 
+    pub async fn delete_article(&self, params: &DeleteArticleParams) -> FrontendResult<DbArticle> {
+        self.post("/api/v1/article/delete", Some(params)).await
+    }
+
+    pub async fn watch_article(
+        &self,
+        article_id: ArticleId,
+        watch: bool,
+    ) -> FrontendResult<SuccessResponse> {
+        let params = WatchArticleParams { article_id, watch };
+        self.post("/api/v1/article/watch", Some(&params)).await
+    }
+
+    pub async fn upload_image(&self, file: UploadFile) -> FrontendResult<UploadImageResponse> {
+        self.upload("/api/v1/article/image", file).await
+    }
+
     pub async fn resolve_article(&self, id: Url) -> FrontendResult<DbArticleView> {
         let resolve_object = ResolveObjectParams { id };
         self.send(Method::GET, "/api/v1/article/resolve", Some(resolve_object))
             .await
     }
 
+    pub async fn list_edits(&self, params: &GetEditList) -> FrontendResult<Vec<EditView>> {
+        self.get("/api/v1/edit/list", Some(params)).await
+    }
+
+    /// Fetch all edits of an article, oldest first, following pagination.
     pub async fn get_article_edits(&self, article_id: ArticleId) -> FrontendResult<Vec<EditView>> {
-        let data = GetEditList {
+        let mut params = GetEditList {
             article_id: Some(article_id),
+            limit: Some(EDIT_PAGE_LIMIT),
             ..Default::default()
         };
-        self.send(Method::GET, "/api/v1/edit/list", Some(data))
-            .await
+        let mut edits = vec![];
+        loop {
+            let page = self.list_edits(&params).await?;
+            let is_last = (page.len() as i64) < EDIT_PAGE_LIMIT;
+            params.cursor = page.last().map(|e| e.edit.id);
+            edits.extend(page);
+            if is_last {
+                return Ok(edits);
+            }
+        }
     }
 
     pub async fn approve_article(
@@ -20,6 +20,7 @@ impl ApiClient {
         self.patch("/api/v1/comment", Some(&params)).await
     }
 
+    // TODO: deprecated, remove in 0.3
     pub async fn mark_comment_as_read(&self, id: CommentId) -> FrontendResult<SuccessResponse> {
         self.post(
             "/api/v1/comment/mark_as_read",
@@ -1,17 +1,26 @@
 use super::ApiClient;
 use crate::{
     common::{
-        article::{DbArticle, SearchArticleParams},
+        article::{SearchArticleParams, SearchArticleResult},
         instance::{
+            AddFederationListParams,
+            DbFederationListEntry,
             DbInstance,
             FollowInstanceParams,
             GetInstanceParams,
             InstanceView,
             InstanceView2,
+            ListInstancesParams,
+            RemoveFederationListParams,
             SiteView,
             UpdateInstanceParams,
         },
-        Notification,
+        mod_log::{ListModLogParams, ModLogView},
+        newtypes::NotificationId,
+        ApiNotification,
+        DeleteNotificationParams,
+        ListNotificationsParams,
+        MarkNotificationAsReadParams,
         ResolveObjectParams,
         SuccessResponse,
     },
@@ -29,8 +38,11 @@ impl ApiClient {
         self.get("/api/v1/instance", Some(&params)).await
     }
 
-    pub async fn list_instances(&self) -> FrontendResult<Vec<InstanceView>> {
-        self.get("/api/v1/instance/list_views", None::<i32>).await
+    pub async fn list_instances(
+        &self,
+        params: &ListInstancesParams,
+    ) -> FrontendResult<Vec<InstanceView>> {
+        self.get("/api/v1/instance/list_views", Some(params)).await
     }
 
     pub async fn update_local_instance(
@@ -40,8 +52,11 @@ impl ApiClient {
         self.patch("/api/v1/instance", Some(params)).await
     }
 
-    pub async fn notifications_list(&self) -> FrontendResult<Vec<Notification>> {
-        self.get("/api/v1/user/notifications/list", None::<()>)
+    pub async fn notifications_list(
+        &self,
+        params: &ListNotificationsParams,
+    ) -> FrontendResult<Vec<ApiNotification>> {
+        self.get("/api/v1/user/notifications/list", Some(params))
             .await
     }
 
@@ -49,7 +64,31 @@ impl ApiClient {
         self.get("/api/v1/user/notifications/count", None::<()>)
             .await
     }
-    pub async fn search(&self, params: &SearchArticleParams) -> FrontendResult<Vec<DbArticle>> {
+
+    pub async fn mark_notification_as_read(
+        &self,
+        id: NotificationId,
+    ) -> FrontendResult<SuccessResponse> {
+        let params = MarkNotificationAsReadParams { id };
+        self.post("/api/v1/user/notifications/mark_as_read", Some(&params))
+            .await
+    }
+
+    pub async fn mark_all_notifications_as_read(&self) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/user/notifications/mark_all_as_read", None::<()>)
+            .await
+    }
+
+    pub async fn delete_notification(&self, id: NotificationId) -> FrontendResult<SuccessResponse> {
+        let params = DeleteNotificationParams { id };
+        self.post("/api/v1/user/notifications/delete", Some(&params))
+            .await
+    }
+
+    pub async fn search(
+        &self,
+        params: &SearchArticleParams,
+    ) -> FrontendResult<Vec<SearchArticleResult>> {
         self.send(Method::GET, "/api/v1/search", Some(params)).await
     }
 
@@ -70,6 +109,36 @@ impl ApiClient {
         self.post("/api/v1/instance/follow", Some(params)).await
     }
 
+    pub async fn mod_log(&self, params: &ListModLogParams) -> FrontendResult<Vec<ModLogView>> {
+        self.get("/api/v1/mod_log", Some(params)).await
+    }
+
+    pub async fn federation_list(&self) -> FrontendResult<Vec<DbFederationListEntry>> {
+        self.get("/api/v1/instance/federation_list", None::<()>)
+            .await
+    }
+
+    pub async fn add_to_federation_list(
+        &self,
+        params: &AddFederationListParams,
+    ) -> FrontendResult<DbFederationListEntry> {
+        self.post("/api/v1/instance/federation_list", Some(params))
+            .await
+    }
+
+    pub async fn remove_from_federation_list(
+        &self,
+        domain: String,
+    ) -> FrontendResult<SuccessResponse> {
+        let params = RemoveFederationListParams { domain };
+        self.send(
+            Method::DELETE,
+            "/api/v1/instance/federation_list",
+            Some(params),
+        )
+        .await
+    }
+
     pub async fn site(&self) -> FrontendResult<SiteView> {
         self.get("/api/v1/site", None::<()>).await
     }
@@ -4,11 +4,20 @@ use log::info;
 use serde::{Deserialize, Serialize};
 use std::{fmt::Debug, sync::LazyLock};
 
+pub mod api_token;
 pub mod article;
 pub mod comment;
 pub mod instance;
+pub mod report;
 pub mod user;
 
+/// File contents for uploads. In the browser this is the file which the user selected, for
+/// tests it is the raw file data.
+#[cfg(feature = "ssr")]
+pub type UploadFile = Vec<u8>;
+#[cfg(not(feature = "ssr"))]
+pub type UploadFile = send_wrapper::SendWrapper<web_sys::File>;
+
 pub static CLIENT: LazyLock<ApiClient> = LazyLock::new(|| ApiClient::new(None));
 
 #[derive(Clone, Debug)]
@@ -39,6 +48,24 @@ impl ApiClient {
         }
     }
 
+    /// Client which authenticates with an api token instead of the login cookie, used by
+    /// bots and in tests.
+    #[cfg(feature = "ssr")]
+    pub fn with_api_token(test_hostname: Option<String>, token: &str) -> Self {
+        use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+        let mut headers = HeaderMap::new();
+        let value = HeaderValue::from_str(&format!("Bearer {token}")).expect("valid token");
+        headers.insert(AUTHORIZATION, value);
+        let client = reqwest::ClientBuilder::new()
+            .default_headers(headers)
+            .build()
+            .expect("init reqwest");
+        Self {
+            client,
+            test_hostname,
+        }
+    }
+
     async fn get<T, R>(&self, endpoint: &str, query: Option<R>) -> FrontendResult<T>
     where
         T: for<'de> Deserialize<'de>,
@@ -147,6 +174,53 @@ impl ApiClient {
         })
     }
 
+    /// Send the file as raw request body, used for image uploads.
+    #[cfg(feature = "ssr")]
+    async fn upload<T>(&self, path: &str, file: UploadFile) -> FrontendResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        use crate::common::{Auth, AUTH_COOKIE};
+        use leptos::prelude::use_context;
+        use reqwest::header::HeaderName;
+
+        let mut req = self.client.post(self.request_endpoint(path)?).body(file);
+        let auth = use_context::<Auth>();
+        if let Some(Auth(Some(auth))) = auth {
+            req = req.header(HeaderName::from_static(AUTH_COOKIE), auth);
+        }
+        let res = req.send().await?;
+        let status = res.status();
+        let url = res.url().to_string();
+        let text = res.text().await?.to_string();
+        Self::response(status.into(), text, &url)
+    }
+
+    #[cfg(not(feature = "ssr"))]
+    fn upload<'a, T>(
+        &'a self,
+        path: &'a str,
+        file: UploadFile,
+    ) -> impl std::future::Future<Output = FrontendResult<T>> + Send + 'a
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        use gloo_net::http::*;
+        use send_wrapper::SendWrapper;
+        use web_sys::RequestCredentials;
+
+        SendWrapper::new(async move {
+            let req = RequestBuilder::new(&self.request_endpoint(path)?)
+                .method(Method::POST)
+                .credentials(RequestCredentials::Include)
+                .body(file.take())?;
+            let res = req.send().await?;
+            let status = res.status();
+            let text = res.text().await?;
+            Self::response(status, text, &res.url())
+        })
+    }
+
     fn response<T>(status: u16, text: String, url: &str) -> FrontendResult<T>
     where
         T: for<'de> Deserialize<'de>,
@@ -0,0 +1,24 @@
+use super::ApiClient;
+use crate::{
+    common::{
+        newtypes::ReportId,
+        report::{CreateReportParams, DbReport, ResolveReportParams},
+    },
+    frontend::utils::errors::FrontendResult,
+};
+
+impl ApiClient {
+    pub async fn create_report(&self, params: &CreateReportParams) -> FrontendResult<DbReport> {
+        self.post("/api/v1/report", Some(&params)).await
+    }
+
+    pub async fn resolve_report(&self, report_id: ReportId) -> FrontendResult<DbReport> {
+        let params = ResolveReportParams { report_id };
+        self.post("/api/v1/report/resolve", Some(params)).await
+    }
+
+    pub async fn dismiss_report(&self, report_id: ReportId) -> FrontendResult<DbReport> {
+        let params = ResolveReportParams { report_id };
+        self.post("/api/v1/report/dismiss", Some(params)).await
+    }
+}
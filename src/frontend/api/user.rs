@@ -1,15 +1,35 @@
-use super::ApiClient;
+use super::{ApiClient, UploadFile};
 use crate::{
     common::{
-        article::{EditView, GetEditList},
-        newtypes::PersonId,
+        mod_log::BanUserParams,
+        newtypes::{LoginSessionId, RegistrationApplicationId},
+        registration_application::{ApproveRegistrationParams, DbRegistrationApplication},
         user::{
+            AdminDeleteUserParams,
+            AdminResetPasswordParams,
+            AdminResetPasswordResponse,
+            CaptchaResponse,
+            ChangePasswordParams,
             DbPerson,
+            DeleteAccountParams,
+            DisableTotpParams,
+            EnableTotpParams,
+            EnableTotpResponse,
+            GenerateTotpSecretResponse,
             GetUserParams,
             LocalUserView,
+            LoginSessionView,
             LoginUserParams,
+            OAuthAuthorizeParams,
+            OAuthAuthorizeResponse,
+            OAuthLoginParams,
             RegisterUserParams,
+            RequestPasswordResetParams,
+            ResetPasswordParams,
+            RevokeSessionParams,
+            UpdateEmailParams,
             UpdateUserParams,
+            VerifyEmailParams,
         },
         SuccessResponse,
     },
@@ -21,6 +41,25 @@ impl ApiClient {
         self.post("/api/v1/account/register", Some(&params)).await
     }
 
+    pub async fn get_captcha(&self) -> FrontendResult<CaptchaResponse> {
+        self.get("/api/v1/account/captcha", None::<()>).await
+    }
+
+    pub async fn approve_registration(
+        &self,
+        application_id: RegistrationApplicationId,
+        approve: bool,
+        deny_reason: Option<String>,
+    ) -> FrontendResult<DbRegistrationApplication> {
+        let params = ApproveRegistrationParams {
+            application_id,
+            approve,
+            deny_reason,
+        };
+        self.post("/api/v1/registration_application/approve", Some(params))
+            .await
+    }
+
     pub async fn login(&self, params: LoginUserParams) -> FrontendResult<LocalUserView> {
         self.post("/api/v1/account/login", Some(&params)).await
     }
@@ -29,10 +68,46 @@ impl ApiClient {
         self.post("/api/v1/account/logout", None::<()>).await
     }
 
+    pub async fn oauth_authorize(
+        &self,
+        provider: String,
+    ) -> FrontendResult<OAuthAuthorizeResponse> {
+        let params = OAuthAuthorizeParams { provider };
+        self.get("/api/v1/account/oauth/authorize", Some(params))
+            .await
+    }
+
+    pub async fn oauth_login(&self, params: OAuthLoginParams) -> FrontendResult<LocalUserView> {
+        self.post("/api/v1/account/oauth/login", Some(params)).await
+    }
+
+    pub async fn list_sessions(&self) -> FrontendResult<Vec<LoginSessionView>> {
+        self.get("/api/v1/account/session/list", None::<()>).await
+    }
+
+    pub async fn revoke_session(&self, id: LoginSessionId) -> FrontendResult<SuccessResponse> {
+        let params = RevokeSessionParams { id };
+        self.post("/api/v1/account/session/revoke", Some(params))
+            .await
+    }
+
+    pub async fn rotate_jwt_secret(&self) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/instance/jwt_secret/rotate", None::<()>)
+            .await
+    }
+
     pub async fn get_user(&self, data: GetUserParams) -> FrontendResult<DbPerson> {
         self.get("/api/v1/user", Some(data)).await
     }
 
+    pub async fn upload_avatar(&self, file: UploadFile) -> FrontendResult<DbPerson> {
+        self.upload("/api/v1/account/avatar", file).await
+    }
+
+    pub async fn upload_banner(&self, file: UploadFile) -> FrontendResult<DbPerson> {
+        self.upload("/api/v1/account/banner", file).await
+    }
+
     pub async fn update_user_profile(
         &self,
         data: UpdateUserParams,
@@ -40,11 +115,82 @@ impl ApiClient {
         self.post("/api/v1/account/update", Some(data)).await
     }
 
-    pub async fn get_person_edits(&self, person_id: PersonId) -> FrontendResult<Vec<EditView>> {
-        let data = GetEditList {
-            person_id: Some(person_id),
-            ..Default::default()
+    pub async fn update_email(&self, data: UpdateEmailParams) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/email", Some(data)).await
+    }
+
+    pub async fn verify_email(&self, data: VerifyEmailParams) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/verify_email", Some(data)).await
+    }
+
+    pub async fn request_password_reset(
+        &self,
+        data: RequestPasswordResetParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/password_reset/request", Some(data))
+            .await
+    }
+
+    pub async fn reset_password(
+        &self,
+        data: ResetPasswordParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/password_reset/confirm", Some(data))
+            .await
+    }
+
+    pub async fn change_password(
+        &self,
+        data: ChangePasswordParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/change_password", Some(data))
+            .await
+    }
+
+    pub async fn generate_totp(&self) -> FrontendResult<GenerateTotpSecretResponse> {
+        self.post("/api/v1/account/totp/generate", None::<()>).await
+    }
+
+    pub async fn enable_totp(&self, totp_token: String) -> FrontendResult<EnableTotpResponse> {
+        let params = EnableTotpParams { totp_token };
+        self.post("/api/v1/account/totp/enable", Some(params)).await
+    }
+
+    pub async fn disable_totp(
+        &self,
+        password: String,
+        totp_token: String,
+    ) -> FrontendResult<SuccessResponse> {
+        let params = DisableTotpParams {
+            password,
+            totp_token,
         };
-        self.get("/api/v1/edit/list", Some(data)).await
+        self.post("/api/v1/account/totp/disable", Some(params))
+            .await
+    }
+
+    pub async fn admin_reset_password(
+        &self,
+        data: AdminResetPasswordParams,
+    ) -> FrontendResult<AdminResetPasswordResponse> {
+        self.post("/api/v1/user/reset_password", Some(data)).await
+    }
+
+    pub async fn delete_account(
+        &self,
+        data: DeleteAccountParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/account/delete", Some(data)).await
+    }
+
+    pub async fn admin_delete_user(
+        &self,
+        data: AdminDeleteUserParams,
+    ) -> FrontendResult<SuccessResponse> {
+        self.post("/api/v1/user/delete", Some(data)).await
+    }
+
+    pub async fn ban_user(&self, params: &BanUserParams) -> FrontendResult<DbPerson> {
+        self.post("/api/v1/user/ban", Some(params)).await
     }
 }
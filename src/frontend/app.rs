@@ -12,13 +12,21 @@ use crate::frontend::{
             read::ReadArticle,
         },
         explore::Explore,
-        instance::{details::InstanceDetails, search::Search, settings::InstanceSettings},
+        instance::{
+            details::InstanceDetails,
+            mod_log::ModLog,
+            search::Search,
+            settings::InstanceSettings,
+        },
         user::{
             edit_profile::UserEditProfile,
             login::Login,
             notifications::Notifications,
+            oauth_callback::OAuthCallback,
             profile::UserProfile,
             register::Register,
+            reset_password::ResetPassword,
+            verify_email::VerifyEmail,
         },
     },
     utils::{dark_mode::DarkMode, errors::ErrorPopup, formatting::instance_title},
@@ -107,7 +115,11 @@ pub fn App() -> impl IntoView {
                         <Route path=path!("/user/:name") view=UserProfile />
                         <Route path=path!("/login") view=Login />
                         <Route path=path!("/register") view=Register />
+                        <Route path=path!("/account/verify_email") view=VerifyEmail />
+                        <Route path=path!("/account/reset_password") view=ResetPassword />
+                        <Route path=path!("/account/oauth_callback") view=OAuthCallback />
                         <Route path=path!("/search") view=Search />
+                        <Route path=path!("/mod_log") view=ModLog />
                         <IbisProtectedRoute path=path!("/edit_profile") view=UserEditProfile />
                         <IbisProtectedRoute path=path!("/notifications") view=Notifications />
                         <IbisProtectedRoute path=path!("/settings") view=InstanceSettings />
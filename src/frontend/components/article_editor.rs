@@ -1,4 +1,8 @@
-use crate::frontend::{markdown::render_article_markdown, utils::use_cookie};
+use crate::frontend::{
+    api::{UploadFile, CLIENT},
+    markdown::render_article_markdown,
+    utils::{errors::FrontendResultExt, selected_file, use_cookie},
+};
 use leptos::{ev::beforeunload, html::Textarea, prelude::*};
 use leptos_use::{use_event_listener, use_window};
 
@@ -20,6 +24,18 @@ pub fn EditorView(
         evt.prevent_default();
     });
 
+    // Upload image and insert it at the end of the article
+    let upload_action = Action::new(move |file: &UploadFile| {
+        let file = file.clone();
+        async move {
+            CLIENT.upload_image(file).await.error_popup(|res| {
+                let val = format!("{}\n![]({})\n", content.get_untracked(), res.path);
+                set_preview.set(render_article_markdown(&val));
+                set_content.set(val);
+            });
+        }
+    });
+
     view! {
         <div>
             <div class="flex my-4 w-full max-sm:flex-col">
@@ -51,6 +67,19 @@ pub fn EditorView(
                 >
                     Preview
                 </button>
+                <label class="ml-2 btn btn-secondary btn-sm">
+                    Upload image
+                    <input
+                        type="file"
+                        accept="image/*"
+                        class="hidden"
+                        on:change=move |ev| {
+                            if let Some(file) = selected_file(&ev) {
+                                upload_action.dispatch(file);
+                            }
+                        }
+                    />
+                </label>
                 <p class="mx-4">
                     <a
                         class="link link-secondary"
@@ -1,9 +1,12 @@
 use crate::{
     common::{article::DbArticleView, validation::can_edit_article},
-    frontend::utils::{
-        errors::FrontendResult,
-        formatting::{article_path, article_title},
-        resources::{is_admin, is_logged_in},
+    frontend::{
+        api::CLIENT,
+        utils::{
+            errors::{FrontendResult, FrontendResultExt},
+            formatting::{article_path, article_title},
+            resources::{is_admin, is_logged_in},
+        },
     },
 };
 use leptos::prelude::*;
@@ -36,6 +39,14 @@ pub fn ArticleNav(
                         let article_link = article_path(&article_.article);
                         let article_link_ = article_link.clone();
                         let protected = article_.article.protected;
+                        let article_id = article_.article.id;
+                        let watched = article_.watched;
+                        let watch_action = Action::new(move |_: &()| async move {
+                            CLIENT
+                                .watch_article(article_id, !watched)
+                                .await
+                                .error_popup(|_| article.refetch());
+                        });
                         view! {
                             <Title text=page_title(active_tab, &title) />
                             <div role="tablist" class="tabs tabs-lifted">
@@ -84,6 +95,17 @@ pub fn ArticleNav(
                                 <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
                                     {title}
                                 </h1>
+                                <Show when=is_logged_in>
+                                    <button
+                                        class="place-self-center mx-2 btn btn-sm"
+                                        title="Get notified about new edits to this article"
+                                        on:click=move |_| {
+                                            watch_action.dispatch(());
+                                        }
+                                    >
+                                        {if watched { "Unwatch" } else { "Watch" }}
+                                    </button>
+                                </Show>
                                 <Show when=move || protected>
                                     <span
                                         class="place-self-center"
@@ -6,12 +6,15 @@ use crate::{
     },
     frontend::{
         api::CLIENT,
-        components::comment_editor::{CommentEditorView, EditParams},
+        components::{
+            comment_editor::{CommentEditorView, EditParams},
+            report_form::ReportForm,
+        },
         markdown::render_comment_markdown,
         utils::{
             errors::{FrontendResult, FrontendResultExt},
-            formatting::{comment_path, time_ago, user_link},
-            resources::my_profile,
+            formatting::{comment_path, time_ago, user_avatar, user_link},
+            resources::{is_logged_in, my_profile},
         },
     },
 };
@@ -66,7 +69,10 @@ pub fn CommentView(
         <div style=style_ id=comment_id>
             <div class="py-2">
                 <div class="flex text-xs">
-                    <span class="grow">{user_link(&comment.creator)}</span>
+                    <span class="grow">
+                        {user_avatar(&comment.creator)}
+                        {user_link(&comment.creator)}
+                    </span>
                     <a href=comment_link class="link">
                         {time_ago(comment.comment.published)}
                     </a>
@@ -96,6 +102,12 @@ pub fn CommentView(
                             Fedilink
                         </a>
                         " | "
+                        <Show when=move || is_logged_in() && !is_creator>
+                            <ReportForm
+                                article_id=comment.comment.article_id
+                                comment_id=comment.comment.id
+                            />
+                        </Show>
                         <Show when=move || is_creator && !comment_change_signal.0.get().deleted>
                             <a
                                 class="link"
@@ -1,19 +1,24 @@
 use crate::{
     common::{article::EditView, utils::extract_domain},
-    frontend::utils::formatting::{article_link, render_date_time, user_link},
+    frontend::{
+        components::pagination::NextPage,
+        utils::formatting::{article_link, render_date_time, user_avatar, user_link},
+    },
 };
 use leptos::{either::Either, prelude::*};
 
 // If `for_article` is true, edit entries link to the respective user account. Otherwise
-// if edits for a user is rendered, entries link to the respective article.
+// if edits for a user is rendered, entries link to the respective article. Edits should be
+// passed newest first, with a link to the next page of older edits.
 #[component]
 pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
+    let page_len = edits.len();
+    let last_id = edits.last().map(|e| e.edit.id.0);
     view! {
         <div>
             <ul class="list-disc">
                 {edits
                     .into_iter()
-                    .rev()
                     .map(|edit: EditView| {
                         let path = format!(
                             "/article/{}@{}/diff/{}",
@@ -27,6 +32,7 @@ pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
                                 view! {
                                     {date}
                                     " by "
+                                    {user_avatar(&edit.creator)}
                                     {user_link(&edit.creator)}
                                 },
                             )
@@ -59,6 +65,7 @@ pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
                     })
                     .collect::<Vec<_>>()}
             </ul>
+            <NextPage last_id=last_id page_len=page_len />
         </div>
     }
 }
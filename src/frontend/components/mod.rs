@@ -6,5 +6,8 @@ pub mod credentials;
 pub mod edit_list;
 pub mod instance_follow_button;
 pub mod nav;
+pub mod oauth_buttons;
+pub mod pagination;
 pub mod protected_route;
+pub mod report_form;
 pub mod suspense_error;
@@ -1,10 +1,14 @@
-use crate::frontend::{
-    api::CLIENT,
-    utils::{
-        dark_mode::DarkMode,
-        errors::FrontendResultExt,
-        formatting::instance_title,
-        resources::{config, is_admin, is_logged_in, my_profile, site},
+use crate::{
+    common::instance::RegistrationMode,
+    frontend::{
+        api::CLIENT,
+        pages::instance::search::search_path,
+        utils::{
+            dark_mode::DarkMode,
+            errors::FrontendResultExt,
+            formatting::instance_title,
+            resources::{config, is_admin, is_logged_in, my_profile, site},
+        },
     },
 };
 use leptos::{component, prelude::*, view, IntoView, *};
@@ -49,6 +53,9 @@ pub fn Nav() -> impl IntoView {
                             <li>
                                 <a href="/explore">"Explore"</a>
                             </li>
+                            <li>
+                                <a href="/mod_log">"Moderation Log"</a>
+                            </li>
                             <Show when=is_logged_in>
                                 <li>
                                     <a href="/create-article">"Create Article"</a>
@@ -75,10 +82,7 @@ pub fn Nav() -> impl IntoView {
                                         let navigate = use_navigate();
                                         let query = search_query.get();
                                         if !query.is_empty() {
-                                            navigate(
-                                                &format!("/search?query={query}"),
-                                                Default::default(),
-                                            );
+                                            navigate(&search_path(&query, "", None), Default::default());
                                         }
                                     }
                                 >
@@ -105,7 +109,9 @@ pub fn Nav() -> impl IntoView {
                                     <li>
                                         <a href="/login">"Login"</a>
                                     </li>
-                                    <Show when=move || config().registration_open>
+                                    <Show when=move || {
+                                        config().registration_mode != RegistrationMode::Closed
+                                    }>
                                         <li>
                                             <a href="/register">"Register"</a>
                                         </li>
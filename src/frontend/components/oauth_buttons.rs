@@ -0,0 +1,64 @@
+use crate::frontend::{
+    api::CLIENT,
+    utils::{errors::FrontendResultExt, resources::site},
+};
+use leptos::prelude::*;
+
+/// Buttons for the configured OpenID Connect providers. They redirect to the provider, which
+/// sends the user back to `/account/oauth_callback` after login. Nothing is shown if no
+/// providers are configured.
+#[component]
+pub fn OAuthButtons(
+    label: &'static str,
+    /// Shown above the buttons
+    #[prop(optional)]
+    heading: Option<&'static str>,
+) -> impl IntoView {
+    let authorize_action = Action::new(move |provider: &String| {
+        let provider = provider.clone();
+        async move {
+            CLIENT.oauth_authorize(provider).await.error_popup(|res| {
+                window().location().set_href(&res.url).ok();
+            });
+        }
+    });
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                site()
+                    .await
+                    .ok()
+                    .filter(|site| !site.oauth_providers.is_empty())
+                    .map(|site| {
+                        let buttons = site
+                            .oauth_providers
+                            .into_iter()
+                            .map(|provider| {
+                                let name = provider.name;
+                                view! {
+                                    <button
+                                        class="my-2 mr-2 btn btn-outline"
+                                        on:click=move |_| {
+                                            authorize_action.dispatch(name.clone());
+                                        }
+                                    >
+                                        {format!("{label} {}", provider.display_name)}
+                                    </button>
+                                }
+                            })
+                            .collect::<Vec<_>>();
+                        view! {
+                            {heading
+                                .map(|heading| {
+                                    view! {
+                                        <h2 class="my-4 font-serif text-xl font-bold">{heading}</h2>
+                                    }
+                                })}
+                            <div>{buttons}</div>
+                        }
+                    })
+            })}
+        </Suspense>
+    }
+}
@@ -0,0 +1,26 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+
+/// Number of items per page for paginated listings.
+pub const PAGE_LIMIT: i64 = 50;
+
+/// Read the `cursor` url param for paginated listings.
+pub fn cursor_param() -> Option<i32> {
+    use_query_map()
+        .get()
+        .get("cursor")
+        .and_then(|c| c.parse().ok())
+}
+
+/// Link to the next page of a listing, if the current page is full.
+#[component]
+pub fn NextPage(last_id: Option<i32>, page_len: usize) -> impl IntoView {
+    let next = last_id.filter(|_| page_len as i64 >= PAGE_LIMIT);
+    view! {
+        <Show when=move || next.is_some()>
+            <a class="btn btn-sm" href=format!("?cursor={}", next.unwrap_or_default())>
+                "Next page"
+            </a>
+        </Show>
+    }
+}
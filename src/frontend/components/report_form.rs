@@ -0,0 +1,66 @@
+use crate::{
+    common::{
+        newtypes::{ArticleId, CommentId, EditId},
+        report::CreateReportParams,
+    },
+    frontend::{api::CLIENT, utils::errors::FrontendResultExt},
+};
+use leptos::prelude::*;
+
+/// Link which expands into a form to report an article, or a specific edit or comment of it.
+#[component]
+pub fn ReportForm(
+    article_id: ArticleId,
+    #[prop(optional)] edit_id: Option<EditId>,
+    #[prop(optional)] comment_id: Option<CommentId>,
+) -> impl IntoView {
+    let (show_form, set_show_form) = signal(false);
+    let (reported, set_reported) = signal(false);
+    let (reason, set_reason) = signal(String::new());
+    let report_action = Action::new(move |_: &()| {
+        let params = CreateReportParams {
+            article_id,
+            edit_id,
+            comment_id,
+            reason: reason.get_untracked(),
+        };
+        async move {
+            CLIENT.create_report(&params).await.error_popup(|_| {
+                set_show_form.set(false);
+                set_reported.set(true);
+            });
+        }
+    });
+    view! {
+        <Show
+            when=move || show_form.get()
+            fallback=move || {
+                view! {
+                    <Show when=move || !reported.get() fallback=|| "Reported">
+                        <a class="link" on:click=move |_| set_show_form.set(true)>
+                            Report
+                        </a>
+                    </Show>
+                }
+            }
+        >
+            <div class="flex flex-row gap-2 my-2">
+                <input
+                    type="text"
+                    class="input input-sm input-bordered"
+                    placeholder="Reason"
+                    bind:value=(reason, set_reason)
+                />
+                <button
+                    class="btn btn-sm btn-outline"
+                    disabled=move || reason.get().is_empty()
+                    on:click=move |_| {
+                        report_action.dispatch(());
+                    }
+                >
+                    Send report
+                </button>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,87 @@
+use markdown_it::{
+    parser::{core::CoreRule, inline::Text},
+    plugins::cmark::inline::image::Image,
+    MarkdownIt,
+    Node,
+    NodeValue,
+    Renderer,
+};
+
+/// Image which was uploaded to the instance. Shows the thumbnail, with a link to the full image.
+#[derive(Debug)]
+struct ArticleImage {
+    filename: String,
+    alt: String,
+}
+
+impl NodeValue for ArticleImage {
+    fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+        let mut attrs = node.attrs.clone();
+        attrs.push(("href", format!("/media/{}", self.filename)));
+        fmt.open("a", &attrs);
+        fmt.self_close(
+            "img",
+            &[
+                ("src", format!("/media/thumbnail/{}", self.filename)),
+                ("alt", self.alt.clone()),
+            ],
+        );
+        fmt.close("a");
+    }
+}
+
+/// Only allow images which are hosted on the instance of the article, so that readers don't
+/// load content from other servers. External images are replaced by their alt text.
+pub struct ArticleImageScanner;
+
+impl CoreRule for ArticleImageScanner {
+    fn run(root: &mut Node, _: &MarkdownIt) {
+        root.walk_mut(|node, _| {
+            let Some(url) = node.cast::<Image>().map(|i| i.url.clone()) else {
+                return;
+            };
+            let alt = node.collect_text();
+            node.children.clear();
+            match url.strip_prefix("/media/").filter(|f| is_image_filename(f)) {
+                Some(filename) => node.replace(ArticleImage {
+                    filename: filename.to_string(),
+                    alt,
+                }),
+                None => node.replace(Text { content: alt }),
+            }
+        });
+    }
+}
+
+/// Uploaded images are named after the sha256 hash of their content.
+fn is_image_filename(filename: &str) -> bool {
+    let Some((hash, extension)) = filename.split_once('.') else {
+        return false;
+    };
+    hash.len() == 64
+        && hash.chars().all(|c| c.is_ascii_hexdigit())
+        && ["jpg", "png", "webp"].contains(&extension)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frontend::markdown::render_article_markdown;
+
+    #[test]
+    fn test_markdown_image() {
+        let hash = "a".repeat(64);
+        let local = render_article_markdown(&format!("![Ibis](/media/{hash}.png)"));
+        assert_eq!(
+            format!(
+                "<p><a href=\"/media/{hash}.png\"><img src=\"/media/thumbnail/{hash}.png\" alt=\"Ibis\"></a></p>\n"
+            ),
+            local
+        );
+
+        let external = render_article_markdown("![Ibis](https://example.com/ibis.png)");
+        assert_eq!("<p>Ibis</p>\n", external);
+
+        let invalid = render_article_markdown("![Ibis](/media/../config.toml)");
+        assert_eq!("<p>Ibis</p>\n", invalid);
+    }
+}
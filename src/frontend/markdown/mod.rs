@@ -1,3 +1,4 @@
+use article_image::ArticleImageScanner;
 use article_link::ArticleLinkScanner;
 use markdown_it::{
     plugins::cmark::block::{heading::ATXHeading, lheading::SetextHeader},
@@ -7,6 +8,7 @@ use math_equation::MathEquationScanner;
 use std::sync::OnceLock;
 use table_of_contents::{TocMarkerScanner, TocScanner};
 
+pub mod article_image;
 pub mod article_link;
 pub mod math_equation;
 pub mod table_of_contents;
@@ -50,6 +52,10 @@ fn article_markdown() -> MarkdownIt {
     parser.inline.add_rule::<TocMarkerScanner>();
     parser.add_rule::<TocScanner>();
 
+    // Images are only enabled for articles, and restricted to uploads on the local instance
+    markdown_it::plugins::cmark::inline::image::add(&mut parser);
+    parser.add_rule::<ArticleImageScanner>();
+
     parser
 }
 
@@ -57,8 +63,8 @@ fn common_markdown() -> MarkdownIt {
     let mut parser = MarkdownIt::new();
     let p = &mut parser;
     {
-        // Markdown-it inline core features. Image is disabled to prevent embedding external
-        // images. Later we need to add proper image support using pictrs.
+        // Markdown-it inline core features. Image is only enabled for articles, see
+        // `article_markdown()`.
         use markdown_it::plugins::cmark::inline::*;
         newline::add(p);
         escape::add(p);
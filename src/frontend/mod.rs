@@ -5,7 +5,7 @@ pub mod app;
 mod components;
 mod markdown;
 mod pages;
-mod utils;
+pub mod utils;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
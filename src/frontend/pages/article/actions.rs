@@ -1,16 +1,25 @@
 use crate::{
     common::{
-        article::{ForkArticleParams, ProtectArticleParams},
+        article::{
+            DeleteArticleParams,
+            ForkArticleParams,
+            MoveArticleParams,
+            ProtectArticleParams,
+        },
         newtypes::ArticleId,
     },
     frontend::{
         api::CLIENT,
         components::{
             article_nav::{ActiveTab, ArticleNav},
+            report_form::ReportForm,
             suspense_error::SuspenseError,
         },
         pages::article_resource,
-        utils::{formatting::article_path, resources::is_admin},
+        utils::{
+            formatting::article_path,
+            resources::{is_admin, is_logged_in},
+        },
         DbArticle,
     },
 };
@@ -21,7 +30,7 @@ use leptos_router::components::Redirect;
 pub fn ArticleActions() -> impl IntoView {
     let article = article_resource();
     let (new_title, set_new_title) = signal(String::new());
-    let (fork_response, set_fork_response) = signal(Option::<DbArticle>::None);
+    let (redirect, set_redirect) = signal(Option::<DbArticle>::None);
     let (error, set_error) = signal(None::<String>);
     let fork_action = Action::new(move |(article_id, new_title): &(ArticleId, String)| {
         let params = ForkArticleParams {
@@ -32,7 +41,23 @@ pub fn ArticleActions() -> impl IntoView {
             set_error.update(|e| *e = None);
             let result = CLIENT.fork_article(&params).await;
             match result {
-                Ok(res) => set_fork_response.set(Some(res.article)),
+                Ok(res) => set_redirect.set(Some(res.article)),
+                Err(err) => {
+                    set_error.update(|e| *e = Some(err.to_string()));
+                }
+            }
+        }
+    });
+    let move_action = Action::new(move |(article_id, new_title): &(ArticleId, String)| {
+        let params = MoveArticleParams {
+            article_id: *article_id,
+            new_title: new_title.to_string(),
+        };
+        async move {
+            set_error.update(|e| *e = None);
+            let result = CLIENT.move_article(&params).await;
+            match result {
+                Ok(res) => set_redirect.set(Some(res.article)),
                 Err(err) => {
                     set_error.update(|e| *e = Some(err.to_string()));
                 }
@@ -55,6 +80,22 @@ pub fn ArticleActions() -> impl IntoView {
             }
         }
     });
+    let delete_action = Action::new(move |(id, deleted): &(ArticleId, bool)| {
+        let params = DeleteArticleParams {
+            article_id: *id,
+            deleted: !deleted,
+        };
+        async move {
+            set_error.update(|e| *e = None);
+            let result = CLIENT.delete_article(&params).await;
+            match result {
+                Ok(_res) => article.refetch(),
+                Err(err) => {
+                    set_error.update(|e| *e = Some(err.to_string()));
+                }
+            }
+        }
+    });
     view! {
         <ArticleNav article=article active_tab=ActiveTab::Actions />
         <SuspenseError result=article>
@@ -81,6 +122,46 @@ pub fn ArticleActions() -> impl IntoView {
                                         Toggle Article Protection
                                     </button>
                                     <p>"Protect a local article so that only admins can edit it"</p>
+                                </Show>
+                                <Show when=move || { is_logged_in() && article.article.local }>
+                                    <input
+                                        class="input"
+                                        placeholder="New Title"
+                                        on:keyup=move |ev: KeyboardEvent| {
+                                            let val = event_target_value(&ev);
+                                            set_new_title.update(|v| *v = val);
+                                        }
+                                    />
+                                    <button
+                                        class="btn"
+                                        disabled=move || new_title.get().is_empty()
+                                        on:click=move |_| {
+                                            move_action.dispatch((article.article.id, new_title.get()));
+                                        }
+                                    >
+                                        Move Article
+                                    </button>
+                                    <p>
+                                        "Change the title of the article. Links to the old title will redirect to the new one."
+                                    </p>
+                                </Show> <Show when=is_admin>
+                                    <button
+                                        class="btn btn-secondary"
+                                        on:click=move |_| {
+                                            delete_action
+                                                .dispatch((article.article.id, article.article.deleted));
+                                        }
+                                    >
+                                        {if article.article.deleted {
+                                            "Restore Article"
+                                        } else {
+                                            "Delete Article"
+                                        }}
+                                    </button>
+                                    <p>
+                                        "Deleted articles are hidden from listings and search, and can't be edited.
+                                        Deletion of local articles is federated to other instances."
+                                    </p>
                                 </Show> <Show when=move || !article.article.local>
                                     <input
                                         class="input"
@@ -106,12 +187,16 @@ pub fn ArticleActions() -> impl IntoView {
                                         instance is dead, or if there are disagreements how the article should be written."
                                     </p>
                                 </Show>
+                                <Show when=is_logged_in>
+                                    <div>
+                                        <ReportForm article_id=article.article.id />
+                                    </div>
+                                    <p>"Report this article to the admins of its instance."</p>
+                                </Show>
                             </div>
                         }
                     })
-            })}
-            {fork_response.get().map(|article| view! { <Redirect path=article_path(&article) /> })}
+            })} {redirect.get().map(|article| view! { <Redirect path=article_path(&article) /> })}
         </SuspenseError>
-        <p>"TODO: add option for admin to delete article etc"</p>
     }
 }
@@ -1,20 +1,49 @@
-use crate::frontend::{
-    components::{
-        article_nav::{ActiveTab, ArticleNav},
-        suspense_error::SuspenseError,
+use crate::{
+    common::{
+        article::{DbArticle, EditVersion, RevertArticleParams},
+        newtypes::ArticleId,
+    },
+    frontend::{
+        api::CLIENT,
+        components::{
+            article_nav::{ActiveTab, ArticleNav},
+            report_form::ReportForm,
+            suspense_error::SuspenseError,
+        },
+        pages::{article_edits_resource, article_resource},
+        utils::{
+            formatting::{article_path, article_title, render_date_time, user_link},
+            resources::is_logged_in,
+        },
     },
-    pages::{article_edits_resource, article_resource},
-    utils::formatting::{article_title, render_date_time, user_link},
 };
 use leptos::{either::Either, prelude::*};
 use leptos_meta::Title;
-use leptos_router::hooks::use_params_map;
+use leptos_router::{components::Redirect, hooks::use_params_map};
 
 #[component]
 pub fn EditDiff() -> impl IntoView {
     let params = use_params_map();
     let article = article_resource();
     let edits = article_edits_resource(article);
+    let (reverted, set_reverted) = signal(Option::<DbArticle>::None);
+    let (error, set_error) = signal(None::<String>);
+    let revert_action = Action::new(move |(article_id, version): &(ArticleId, EditVersion)| {
+        let params = RevertArticleParams {
+            article_id: *article_id,
+            version: version.clone(),
+        };
+        async move {
+            set_error.update(|e| *e = None);
+            let result = CLIENT.revert_article(&params).await;
+            match result {
+                Ok(res) => set_reverted.set(Some(res.article)),
+                Err(err) => {
+                    set_error.update(|e| *e = Some(err.to_string()));
+                }
+            }
+        }
+    });
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
@@ -36,6 +65,9 @@ pub fn EditDiff() -> impl IntoView {
                                 render_date_time(edit.edit.published),
                             );
                             let pending = edit.edit.pending;
+                            let article_id = edit.article.id;
+                            let version = edit.edit.hash.clone();
+                            let edit_id = edit.edit.id;
                             let title = format!("Diff {} — {}", edit.edit.summary, article_title);
                             Either::Left(
                                 view! {
@@ -51,6 +83,29 @@ pub fn EditDiff() -> impl IntoView {
                                         </Show>
                                     </div>
                                     <p>"by " {user_link(&edit.creator)}</p>
+                                    <Show when=is_logged_in>
+                                        <ReportForm article_id edit_id />
+                                    </Show>
+                                    {move || {
+                                        error
+                                            .get()
+                                            .map(|err| {
+                                                view! { <p class="alert">{err}</p> }
+                                            })
+                                    }}
+                                    <Show when=move || is_logged_in() && !pending>
+                                        <button
+                                            class="my-2 btn btn-secondary"
+                                            on:click={
+                                                let version = version.clone();
+                                                move |_| {
+                                                    revert_action.dispatch((article_id, version.clone()));
+                                                }
+                                            }
+                                        >
+                                            Revert to this version
+                                        </button>
+                                    </Show>
                                     <div class="max-w-full prose prose-slate">
                                         <pre class="text-wrap">
                                             <code>{edit.edit.diff.clone()}</code>
@@ -69,7 +124,15 @@ pub fn EditDiff() -> impl IntoView {
                         }
                     })
             })}
-
+            {move || {
+                reverted
+                    .get()
+                    .map(|article| {
+                        view! {
+                            <Redirect path=format!("{}?edit_successful", article_path(&article)) />
+                        }
+                    })
+            }}
         </SuspenseError>
     }
 }
@@ -1,17 +1,37 @@
-use crate::frontend::{
-    components::{
-        article_nav::{ActiveTab, ArticleNav},
-        edit_list::EditList,
-        suspense_error::SuspenseError,
+use crate::{
+    common::{
+        article::{EditSort, GetEditList},
+        newtypes::EditId,
+    },
+    frontend::{
+        api::CLIENT,
+        components::{
+            article_nav::{ActiveTab, ArticleNav},
+            edit_list::EditList,
+            pagination::{cursor_param, PAGE_LIMIT},
+            suspense_error::SuspenseError,
+        },
+        pages::article_resource,
     },
-    pages::{article_edits_resource, article_resource},
 };
 use leptos::prelude::*;
 
 #[component]
 pub fn ArticleHistory() -> impl IntoView {
     let article = article_resource();
-    let edits = article_edits_resource(article);
+    let edits = Resource::new(
+        move || (article.get(), cursor_param()),
+        move |(_, cursor)| async move {
+            let params = GetEditList {
+                article_id: Some(article.await?.article.id),
+                sort: Some(EditSort::Newest),
+                cursor: cursor.map(EditId),
+                limit: Some(PAGE_LIMIT),
+                ..Default::default()
+            };
+            CLIENT.list_edits(&params).await
+        },
+    );
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
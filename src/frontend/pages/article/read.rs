@@ -5,14 +5,19 @@ use crate::frontend::{
     },
     markdown::render_article_markdown,
     pages::article_resource,
+    utils::formatting::article_path,
 };
 use leptos::{either::Either, prelude::*};
-use leptos_router::hooks::use_query_map;
+use leptos_router::{
+    components::Redirect,
+    hooks::{use_params_map, use_query_map},
+};
 
 #[component]
 pub fn ReadArticle() -> impl IntoView {
     let article = article_resource();
     let query = use_query_map();
+    let params = use_params_map();
     let edit_successful = query.get_untracked().get("edit_successful").is_some();
 
     view! {
@@ -20,10 +25,26 @@ pub fn ReadArticle() -> impl IntoView {
         <SuspenseError result=article>
             {move || Suspend::new(async move {
                 let article = article.await;
+                let deleted = article.as_ref().map(|a| a.article.deleted).unwrap_or_default();
+                let moved = article
+                    .as_ref()
+                    .ok()
+                    .map(|a| article_path(&a.article))
+                    .filter(|path| {
+                        params
+                            .get_untracked()
+                            .get("title")
+                            .is_some_and(|t| *path != format!("/article/{t}"))
+                    });
                 let markdown = article.map(|a| render_article_markdown(&a.article.text));
                 if let Ok(markdown) = markdown {
                     Either::Right(
+                        // article was opened with an old title, redirect to the current one
                         view! {
+                            {moved.map(|path| view! { <Redirect path=path /> })}
+                            <Show when=move || deleted>
+                                <div class="alert alert-warning">"This article was deleted"</div>
+                            </Show>
                             <div class="max-w-full prose prose-slate" inner_html=markdown></div>
                         },
                     )
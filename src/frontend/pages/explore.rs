@@ -1,8 +1,15 @@
 use crate::{
-    common::{article::DbArticle, instance::InstanceView},
+    common::{
+        article::DbArticle,
+        instance::{InstanceView, ListInstancesParams},
+        newtypes::InstanceId,
+    },
     frontend::{
         api::CLIENT,
-        components::suspense_error::SuspenseError,
+        components::{
+            pagination::{cursor_param, NextPage, PAGE_LIMIT},
+            suspense_error::SuspenseError,
+        },
         utils::{
             errors::{FrontendResult, FrontendResultExt},
             formatting::{article_link, instance_title_with_domain, instance_updated},
@@ -16,7 +23,13 @@ use url::Url;
 
 #[component]
 pub fn Explore() -> impl IntoView {
-    let instances = Resource::new(move || (), |_| async move { CLIENT.list_instances().await });
+    let instances = Resource::new(cursor_param, |cursor| async move {
+        let params = ListInstancesParams {
+            cursor: cursor.map(InstanceId),
+            limit: Some(PAGE_LIMIT),
+        };
+        CLIENT.list_instances(&params).await
+    });
 
     view! {
         <Title text="Explore" />
@@ -25,6 +38,12 @@ pub fn Explore() -> impl IntoView {
             {move || Suspend::new(async move {
                 let instances_ = instances.await;
                 let is_empty = instances_.as_ref().map(|i| i.is_empty()).unwrap_or(true);
+                let page_len = instances_.as_ref().map(|i| i.len()).unwrap_or_default();
+                let last_id = instances_
+                    .as_ref()
+                    .ok()
+                    .and_then(|i| i.last())
+                    .map(|i| i.instance.id.0);
                 view! {
                     <Show
                         when=move || !is_empty
@@ -39,6 +58,7 @@ pub fn Explore() -> impl IntoView {
                                 .map(instance_card)
                                 .collect::<Vec<_>>()}
                         </ul>
+                        <NextPage last_id=last_id page_len=page_len />
                     </Show>
                 }
             })}
@@ -1,8 +1,17 @@
 use crate::{
-    common::{article::ListArticlesParams, instance::DbInstance, utils::http_protocol_str},
+    common::{
+        article::ListArticlesParams,
+        instance::DbInstance,
+        newtypes::ArticleId,
+        utils::http_protocol_str,
+    },
     frontend::{
         api::CLIENT,
-        components::{instance_follow_button::InstanceFollowButton, suspense_error::SuspenseError},
+        components::{
+            instance_follow_button::InstanceFollowButton,
+            pagination::{cursor_param, NextPage, PAGE_LIMIT},
+            suspense_error::SuspenseError,
+        },
         utils::{
             errors::FrontendError,
             formatting::{
@@ -36,12 +45,14 @@ pub fn InstanceDetails() -> impl IntoView {
                     .await
                     .map(|instance: DbInstance| {
                         let articles = Resource::new(
-                            move || instance.id,
-                            |instance_id| async move {
+                            move || (instance.id, cursor_param()),
+                            |(instance_id, cursor)| async move {
                                 CLIENT
                                     .list_articles(ListArticlesParams {
-                                        only_local: None,
                                         instance_id: Some(instance_id),
+                                        cursor: cursor.map(ArticleId),
+                                        limit: Some(PAGE_LIMIT),
+                                        ..Default::default()
                                     })
                                     .await
                             },
@@ -66,17 +77,23 @@ pub fn InstanceDetails() -> impl IntoView {
                                             articles
                                                 .await
                                                 .map(|a| {
-                                                    a.into_iter()
-                                                        .map(|a| {
-                                                            view! {
-                                                                <li>
-                                                                    <a class="text-lg link" href=article_path(&a)>
-                                                                        {article_title(&a)}
-                                                                    </a>
-                                                                </li>
-                                                            }
-                                                        })
-                                                        .collect::<Vec<_>>()
+                                                    let page_len = a.len();
+                                                    let last_id = a.last().map(|a| a.id.0);
+                                                    view! {
+                                                        {a
+                                                            .into_iter()
+                                                            .map(|a| {
+                                                                view! {
+                                                                    <li>
+                                                                        <a class="text-lg link" href=article_path(&a)>
+                                                                            {article_title(&a)}
+                                                                        </a>
+                                                                    </li>
+                                                                }
+                                                            })
+                                                            .collect::<Vec<_>>()}
+                                                        <NextPage last_id=last_id page_len=page_len />
+                                                    }
                                                 })
                                         })}
                                     </SuspenseError>
@@ -1,3 +1,4 @@
 pub mod details;
+pub mod mod_log;
 pub mod search;
 pub mod settings;
@@ -0,0 +1,73 @@
+use crate::{
+    common::{
+        mod_log::{ListModLogParams, ModLogView},
+        newtypes::ModLogId,
+    },
+    frontend::{
+        api::CLIENT,
+        components::{
+            pagination::{cursor_param, NextPage, PAGE_LIMIT},
+            suspense_error::SuspenseError,
+        },
+        utils::formatting::{article_link, render_date_time, time_ago, user_link},
+    },
+};
+use leptos::{either::Either, prelude::*};
+use leptos_meta::Title;
+
+#[component]
+pub fn ModLog() -> impl IntoView {
+    let mod_log = Resource::new(cursor_param, |cursor| async move {
+        let params = ListModLogParams {
+            cursor: cursor.map(ModLogId),
+            limit: Some(PAGE_LIMIT),
+        };
+        CLIENT.mod_log(&params).await
+    });
+
+    view! {
+        <Title text="Moderation Log" />
+        <h1 class="my-6 font-serif text-4xl font-bold">Moderation Log</h1>
+        <SuspenseError result=mod_log>
+            {move || Suspend::new(async move {
+                mod_log
+                    .await
+                    .map(|entries| {
+                        let page_len = entries.len();
+                        let last_id = entries.last().map(|e| e.mod_log.id.0);
+                        view! {
+                            <ul class="divide-y divide-solid">
+                                {entries.into_iter().map(mod_log_entry).collect::<Vec<_>>()}
+                            </ul>
+                            <NextPage last_id page_len />
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+fn mod_log_entry(entry: ModLogView) -> impl IntoView {
+    let moderator = match &entry.moderator {
+        Some(moderator) => Either::Left(user_link(moderator)),
+        None => Either::Right("Deleted user"),
+    };
+    let target_person = entry.target_person.as_ref().map(user_link);
+    let target_article = entry.target_article.as_ref().map(article_link);
+    let expires = entry
+        .mod_log
+        .expires
+        .map(|e| format!(" until {}", render_date_time(e)));
+    view! {
+        <li class="py-2">
+            <div class="flex text-s">
+                <span class="grow">
+                    {moderator} " " {entry.mod_log.action.description()} " "
+                    {target_person} {target_article} {expires}
+                </span>
+                <span>{time_ago(entry.mod_log.published)}</span>
+            </div>
+            {entry.mod_log.reason.map(|r| view! { <div>{format!("Reason: {r}")}</div> })}
+        </li>
+    }
+}
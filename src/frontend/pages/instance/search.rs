@@ -1,28 +1,69 @@
 use crate::{
     common::{
-        article::{DbArticle, SearchArticleParams},
+        article::{DbArticle, SearchArticleParams, SearchArticleResult},
         instance::DbInstance,
+        newtypes::{ArticleId, InstanceId},
+        utils::extract_domain,
     },
     frontend::{
         api::CLIENT,
+        components::pagination::{cursor_param, PAGE_LIMIT},
         utils::formatting::{article_path, article_title},
     },
 };
-use leptos::prelude::*;
+use leptos::{ev, prelude::*};
 use leptos_meta::Title;
-use leptos_router::hooks::use_query_map;
+use leptos_router::hooks::{use_navigate, use_query_map};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 #[derive(Default, Clone, Deserialize, Serialize, Debug)]
 struct SearchResults {
-    articles: Vec<DbArticle>,
+    articles: Vec<SearchArticleResult>,
+    resolved_article: Option<DbArticle>,
     instance: Option<DbInstance>,
 }
 
 impl SearchResults {
     pub fn is_empty(&self) -> bool {
-        self.articles.is_empty() && self.instance.is_none()
+        self.articles.is_empty() && self.resolved_article.is_none() && self.instance.is_none()
+    }
+}
+
+/// Which articles to include in search, stored in url as `filter` param.
+fn filter_to_params(filter: &str) -> (Option<bool>, Option<InstanceId>) {
+    match filter {
+        "local" => (Some(true), None),
+        "remote" => (Some(false), None),
+        id => (None, id.parse().ok().map(InstanceId)),
+    }
+}
+
+pub fn search_path(query: &str, filter: &str, cursor: Option<ArticleId>) -> String {
+    let cursor = cursor.map(|c| c.0.to_string());
+    let mut params = vec![("query", query), ("filter", filter)];
+    if let Some(cursor) = &cursor {
+        params.push(("cursor", cursor));
+    }
+    // encoding a list of string pairs can't fail
+    let params = serde_urlencoded::to_string(params).unwrap_or_default();
+    format!("/search?{params}")
+}
+
+fn search_result_path(a: &SearchArticleResult) -> String {
+    if a.local {
+        format!("/article/{}", a.title)
+    } else {
+        format!("/article/{}@{}", a.title, extract_domain(&a.ap_id))
+    }
+}
+
+fn search_result_title(a: &SearchArticleResult) -> String {
+    let title = a.title.replace('_', " ");
+    if a.local {
+        title
+    } else {
+        format!("{}@{}", title, extract_domain(&a.ap_id))
     }
 }
 
@@ -30,38 +71,89 @@ impl SearchResults {
 pub fn Search() -> impl IntoView {
     let params = use_query_map();
     let query = move || params.get().get("query").clone().unwrap_or_default();
+    let filter = move || params.get().get("filter").clone().unwrap_or_default();
     let (error, set_error) = signal(None::<String>);
-    let search_results = Resource::new(query, move |query| async move {
-        set_error.set(None);
-        let mut search_results = SearchResults::default();
-        let url = Url::parse(&query);
-        let search_data = SearchArticleParams { query };
-        let search = CLIENT.search(&search_data);
+    let instances = Resource::new(
+        || (),
+        |_| async move { CLIENT.list_instances(&Default::default()).await },
+    );
+    let search_results = Resource::new(
+        move || (query(), filter(), cursor_param()),
+        move |(query, filter, cursor)| async move {
+            set_error.set(None);
+            let mut search_results = SearchResults::default();
+            let url = Url::parse(&query);
+            let (local, instance_id) = filter_to_params(&filter);
+            let search_data = SearchArticleParams {
+                query,
+                instance_id,
+                local,
+                cursor: cursor.map(ArticleId),
+                limit: Some(PAGE_LIMIT),
+            };
+            let search = CLIENT.search(&search_data);
 
-        match search.await {
-            Ok(mut a) => search_results.articles.append(&mut a),
-            Err(e) => set_error.set(Some(e.to_string())),
-        }
-
-        // If its a valid url, also attempt to resolve as federation object
-        if let Ok(url) = url {
-            match CLIENT.resolve_article(url.clone()).await {
-                Ok(a) => search_results.articles.push(a.article),
+            match search.await {
+                Ok(mut a) => search_results.articles.append(&mut a),
                 Err(e) => set_error.set(Some(e.to_string())),
             }
-            match CLIENT.resolve_instance(url).await {
-                Ok(a) => search_results.instance = Some(a),
-                Err(e) => set_error.set(Some(e.to_string())),
+
+            // If its a valid url, also attempt to resolve as federation object
+            if let Ok(url) = url {
+                match CLIENT.resolve_article(url.clone()).await {
+                    Ok(a) => search_results.resolved_article = Some(a.article),
+                    Err(e) => set_error.set(Some(e.to_string())),
+                }
+                match CLIENT.resolve_instance(url).await {
+                    Ok(a) => search_results.instance = Some(a),
+                    Err(e) => set_error.set(Some(e.to_string())),
+                }
             }
-        }
-        search_results
-    });
+            search_results
+        },
+    );
 
     view! {
         <Title text=format!("Search - {}", query()) />
         <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
             "Search results for " {query}
         </h1>
+        <select
+            class="mb-4 select select-bordered select-sm"
+            on:change=move |ev: ev::Event| {
+                let navigate = use_navigate();
+                navigate(&search_path(&query(), &event_target_value(&ev), None), Default::default());
+            }
+        >
+            <option value="" selected=move || filter().is_empty()>
+                "All instances"
+            </option>
+            <option value="local" selected=move || filter() == "local">
+                "Local instance"
+            </option>
+            <option value="remote" selected=move || filter() == "remote">
+                "Remote instances"
+            </option>
+            <Suspense>
+                {move || Suspend::new(async move {
+                    instances
+                        .await
+                        .ok()
+                        .into_iter()
+                        .flatten()
+                        .map(|i| {
+                            let id = i.instance.id.0.to_string();
+                            let id_ = id.clone();
+                            view! {
+                                <option value=id selected=move || filter() == id_>
+                                    {i.instance.domain}
+                                </option>
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })}
+            </Suspense>
+        </select>
         <Suspense fallback=|| {
             view! { "Loading..." }
         }>
@@ -70,6 +162,11 @@ pub fn Search() -> impl IntoView {
                     .get()
                     .map(move |search_results| {
                         let is_empty = search_results.is_empty();
+                        let next_cursor = search_results
+                            .articles
+                            .last()
+                            .map(|a| a.id)
+                            .filter(|_| search_results.articles.len() as i64 >= PAGE_LIMIT);
                         view! {
                             <Show
                                 when=move || !is_empty
@@ -91,23 +188,22 @@ pub fn Search() -> impl IntoView {
                                 <ul>
 
                                     // render resolved instance
-                                    {if let Some(instance) = &search_results.instance {
-                                        let domain = &instance.domain;
-                                        vec![
+                                    {search_results
+                                        .instance
+                                        .as_ref()
+                                        .map(|instance| {
+                                            let domain = &instance.domain;
                                             view! {
                                                 <li>
                                                     <a class="text-lg link" href=format!("/instance/{domain}")>
                                                         {domain.to_string()}
                                                     </a>
                                                 </li>
-                                            },
-                                        ]
-                                    } else {
-                                        vec![]
-                                    }} // render articles from resolve/search
+                                            }
+                                        })} // render resolved article
                                     {search_results
-                                        .articles
-                                        .iter()
+                                        .resolved_article
+                                        .as_ref()
                                         .map(|a| {
                                             view! {
                                                 <li>
@@ -116,11 +212,32 @@ pub fn Search() -> impl IntoView {
                                                     </a>
                                                 </li>
                                             }
+                                        })} // render articles from search
+                                    {search_results
+                                        .articles
+                                        .iter()
+                                        .map(|a| {
+                                            view! {
+                                                <li class="mb-4">
+                                                    <a class="text-lg link" href=search_result_path(a)>
+                                                        {search_result_title(a)}
+                                                    </a>
+                                                    <p inner_html=a.snippet.clone()></p>
+                                                </li>
+                                            }
                                         })
                                         .collect::<Vec<_>>()}
 
                                 </ul>
                             </Show>
+                            <Show when=move || next_cursor.is_some()>
+                                <a
+                                    class="btn btn-sm"
+                                    href=move || search_path(&query(), &filter(), next_cursor)
+                                >
+                                    "Next page"
+                                </a>
+                            </Show>
                         }
                     })
             }}
@@ -1,12 +1,12 @@
 use crate::{
-    common::instance::UpdateInstanceParams,
+    common::instance::{AddFederationListParams, UpdateInstanceParams},
     frontend::{
         api::CLIENT,
         components::suspense_error::SuspenseError,
-        utils::errors::FrontendResultExt,
+        utils::{errors::FrontendResultExt, resources::site},
     },
 };
-use leptos::prelude::*;
+use leptos::{ev, prelude::*};
 use leptos_meta::Title;
 
 #[component]
@@ -91,5 +91,139 @@ pub fn InstanceSettings() -> impl IntoView {
             })}
 
         </SuspenseError>
+        <FederationList />
+        <RotateJwtSecret />
+    }
+}
+
+/// Domains which are allowed or blocked for federation.
+#[component]
+fn FederationList() -> impl IntoView {
+    let list = Resource::new(|| (), |_| async move { CLIENT.federation_list().await });
+    let (domain, set_domain) = signal(String::new());
+    let (blocked, set_blocked) = signal(true);
+    let (purge, set_purge) = signal(false);
+
+    let add_action = Action::new(move |params: &AddFederationListParams| {
+        let params = params.clone();
+        async move {
+            CLIENT
+                .add_to_federation_list(&params)
+                .await
+                .error_popup(|_| {
+                    set_domain.set(String::new());
+                    list.refetch();
+                });
+        }
+    });
+    let remove_action = Action::new(move |domain: &String| {
+        let domain = domain.clone();
+        async move {
+            CLIENT
+                .remove_from_federation_list(domain)
+                .await
+                .error_popup(|_| list.refetch());
+        }
+    });
+
+    view! {
+        <h2 class="my-4 font-serif text-xl font-bold">"Federation"</h2>
+        <p class="mb-2">
+            "Blocked domains can't federate with this instance. If any domain is allowed, federation with all other domains is blocked."
+        </p>
+        <SuspenseError result=list>
+            {move || Suspend::new(async move {
+                list.await
+                    .map(|list| {
+                        view! {
+                            <ul class="mb-4 list-disc list-inside">
+                                {list
+                                    .into_iter()
+                                    .map(|entry| {
+                                        let kind = if entry.blocked { "blocked" } else { "allowed" };
+                                        let domain = entry.domain.clone();
+                                        view! {
+                                            <li>
+                                                {format!("{} ({kind}) ", entry.domain)}
+                                                <button
+                                                    class="btn btn-xs btn-outline"
+                                                    on:click=move |_| {
+                                                        remove_action.dispatch(domain.clone());
+                                                    }
+                                                >
+                                                    Remove
+                                                </button>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                        }
+                    })
+            })}
+        </SuspenseError>
+        <div class="flex flex-row gap-2 items-center mb-2">
+            <input
+                type="text"
+                class="w-80 input input-secondary input-bordered"
+                placeholder="example.com"
+                bind:value=(domain, set_domain)
+            />
+            <select
+                class="select select-bordered"
+                on:change=move |ev: ev::Event| set_blocked.set(event_target_value(&ev) == "block")
+            >
+                <option value="block">Block</option>
+                <option value="allow">Allow</option>
+            </select>
+            <Show when=move || blocked.get()>
+                <label class="label">
+                    <input
+                        type="checkbox"
+                        class="mr-2 checkbox"
+                        bind:checked=(purge, set_purge)
+                    />
+                    "Purge existing content"
+                </label>
+            </Show>
+            <button
+                class="btn btn-primary"
+                on:click=move |_| {
+                    let params = AddFederationListParams {
+                        domain: domain.get(),
+                        blocked: blocked.get(),
+                        purge: blocked.get() && purge.get(),
+                    };
+                    add_action.dispatch(params);
+                }
+            >
+                Add
+            </button>
+        </div>
+    }
+}
+
+/// Invalidate all login tokens, in case the secret was leaked.
+#[component]
+fn RotateJwtSecret() -> impl IntoView {
+    let rotate_action = Action::new(move |_: &()| async move {
+        CLIENT
+            .rotate_jwt_secret()
+            .await
+            .error_popup(|_| site().refetch());
+    });
+    view! {
+        <h2 class="my-4 font-serif text-xl font-bold">"Login Secret"</h2>
+        <p class="mb-2">
+            "Generate a new secret for signing login tokens. All users including you will be logged out."
+        </p>
+        <button
+            class="btn btn-error"
+            on:click=move |_| {
+                rotate_action.dispatch(());
+            }
+        >
+            "Rotate secret"
+        </button>
     }
 }
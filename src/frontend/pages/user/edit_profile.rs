@@ -1,13 +1,29 @@
 use crate::{
-    common::user::UpdateUserParams,
+    common::{
+        api_token::CreateApiTokenParams,
+        newtypes::{ApiTokenId, LoginSessionId},
+        user::{
+            ChangePasswordParams,
+            DeleteAccountParams,
+            GenerateTotpSecretResponse,
+            UpdateEmailParams,
+            UpdateUserParams,
+        },
+    },
     frontend::{
-        api::CLIENT,
-        components::suspense_error::SuspenseError,
-        utils::{errors::FrontendResultExt, resources::site},
+        api::{UploadFile, CLIENT},
+        components::{oauth_buttons::OAuthButtons, suspense_error::SuspenseError},
+        utils::{
+            errors::FrontendResultExt,
+            formatting::time_ago,
+            resources::{is_admin, site},
+            selected_file,
+        },
     },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
+use leptos_router::components::Redirect;
 
 #[component]
 pub fn UserEditProfile() -> impl IntoView {
@@ -23,6 +39,57 @@ pub fn UserEditProfile() -> impl IntoView {
         }
     });
 
+    let email_action = Action::new(move |params: &UpdateEmailParams| {
+        let params = params.clone();
+        async move {
+            CLIENT.update_email(params).await.error_popup(|_| {
+                set_saved.set(true);
+                site().refetch();
+            });
+        }
+    });
+
+    let password_action = Action::new(move |params: &ChangePasswordParams| {
+        let params = params.clone();
+        async move {
+            CLIENT
+                .change_password(params)
+                .await
+                .error_popup(|_| set_saved.set(true));
+        }
+    });
+
+    let avatar_action = Action::new(move |file: &UploadFile| {
+        let file = file.clone();
+        async move {
+            CLIENT.upload_avatar(file).await.error_popup(|_| {
+                set_saved.set(true);
+                site().refetch();
+            });
+        }
+    });
+
+    let banner_action = Action::new(move |file: &UploadFile| {
+        let file = file.clone();
+        async move {
+            CLIENT.upload_banner(file).await.error_popup(|_| {
+                set_saved.set(true);
+                site().refetch();
+            });
+        }
+    });
+
+    let (deleted, set_deleted) = signal(false);
+    let delete_action = Action::new(move |params: &DeleteAccountParams| {
+        let params = params.clone();
+        async move {
+            CLIENT.delete_account(params).await.error_popup(|_| {
+                set_deleted.set(true);
+                site().refetch();
+            });
+        }
+    });
+
     // TODO: It would make sense to use a table for the labels and inputs, but for some reason
     //       that completely breaks reactivity.
     view! {
@@ -40,6 +107,16 @@ pub fn UserEditProfile() -> impl IntoView {
                         let (bio, set_bio) = signal(
                             my_profile.person.bio.clone().unwrap_or_default(),
                         );
+                        let (email, set_email) = signal(
+                            my_profile.local_user.email.clone().unwrap_or_default(),
+                        );
+                        let (email_notifications, set_email_notifications) = signal(
+                            my_profile.local_user.email_notifications,
+                        );
+                        let email_verified = my_profile.local_user.email_verified;
+                        let (old_password, set_old_password) = signal(String::new());
+                        let (new_password, set_new_password) = signal(String::new());
+                        let (delete_password, set_delete_password) = signal(String::new());
                         view! {
                             <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
                                 Edit Profile
@@ -69,7 +146,6 @@ pub fn UserEditProfile() -> impl IntoView {
                                 class="btn btn-primary"
                                 on:click=move |_| {
                                     let form = UpdateUserParams {
-                                        person_id: my_profile.person.id,
                                         display_name: Some(display_name.get()),
                                         bio: Some(bio.get()),
                                     };
@@ -79,6 +155,156 @@ pub fn UserEditProfile() -> impl IntoView {
                                 Submit
                             </button>
 
+                            <h2 class="my-4 font-serif text-xl font-bold">Images</h2>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="avatar">
+                                    Avatar
+                                </label>
+                                <input
+                                    type="file"
+                                    id="avatar"
+                                    accept="image/*"
+                                    class="w-80 file-input file-input-secondary file-input-bordered"
+                                    on:change=move |ev| {
+                                        if let Some(file) = selected_file(&ev) {
+                                            avatar_action.dispatch(file);
+                                        }
+                                    }
+                                />
+                            </div>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="banner">
+                                    Banner
+                                </label>
+                                <input
+                                    type="file"
+                                    id="banner"
+                                    accept="image/*"
+                                    class="w-80 file-input file-input-secondary file-input-bordered"
+                                    on:change=move |ev| {
+                                        if let Some(file) = selected_file(&ev) {
+                                            banner_action.dispatch(file);
+                                        }
+                                    }
+                                />
+                            </div>
+
+                            <h2 class="my-4 font-serif text-xl font-bold">Email</h2>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="email">
+                                    Email address
+                                </label>
+                                <input
+                                    type="email"
+                                    id="email"
+                                    class="w-80 input input-secondary input-bordered"
+                                    bind:value=(email, set_email)
+                                />
+                            </div>
+                            <Show when=move || !email_verified && !email.get().is_empty()>
+                                <p class="mb-2">
+                                    "Email address is not verified, check your inbox for the verification link."
+                                </p>
+                            </Show>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="email_notifications">
+                                    Email notifications
+                                </label>
+                                <input
+                                    type="checkbox"
+                                    id="email_notifications"
+                                    class="checkbox"
+                                    bind:checked=(email_notifications, set_email_notifications)
+                                />
+                            </div>
+                            <button
+                                class="btn btn-primary"
+                                on:click=move |_| {
+                                    let form = UpdateEmailParams {
+                                        email: Some(email.get()),
+                                        email_notifications: email_notifications.get(),
+                                    };
+                                    email_action.dispatch(form);
+                                }
+                            >
+                                Save email
+                            </button>
+
+                            <h2 class="my-4 font-serif text-xl font-bold">Change Password</h2>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="old_password">
+                                    Old password
+                                </label>
+                                <input
+                                    type="password"
+                                    id="old_password"
+                                    class="w-80 input input-secondary input-bordered"
+                                    bind:value=(old_password, set_old_password)
+                                />
+                            </div>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="new_password">
+                                    New password
+                                </label>
+                                <input
+                                    type="password"
+                                    id="new_password"
+                                    class="w-80 input input-secondary input-bordered"
+                                    bind:value=(new_password, set_new_password)
+                                />
+                            </div>
+                            <button
+                                class="btn btn-primary"
+                                prop:disabled=move || {
+                                    old_password.get().is_empty() || new_password.get().is_empty()
+                                }
+                                on:click=move |_| {
+                                    let form = ChangePasswordParams {
+                                        old_password: old_password.get(),
+                                        new_password: new_password.get(),
+                                    };
+                                    password_action.dispatch(form);
+                                }
+                            >
+                                Change password
+                            </button>
+
+                            <TwoFactorAuth enabled=my_profile.local_user.totp_enabled />
+                            <Sessions />
+                            <OAuthButtons label="Link account at" heading="Login Providers" />
+                            <ApiTokens />
+
+                            <h2 class="my-4 font-serif text-xl font-bold">Delete Account</h2>
+                            <p class="mb-2">
+                                "Your edits and comments will be kept, but they won't be associated with your account anymore. This cannot be undone."
+                            </p>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="delete_password">
+                                    Password
+                                </label>
+                                <input
+                                    type="password"
+                                    id="delete_password"
+                                    class="w-80 input input-secondary input-bordered"
+                                    bind:value=(delete_password, set_delete_password)
+                                />
+                            </div>
+                            <button
+                                class="btn btn-error"
+                                prop:disabled=move || delete_password.get().is_empty()
+                                on:click=move |_| {
+                                    let form = DeleteAccountParams {
+                                        password: delete_password.get(),
+                                    };
+                                    delete_action.dispatch(form);
+                                }
+                            >
+                                Delete account
+                            </button>
+
+                            <Show when=move || deleted.get()>
+                                <Redirect path="/" />
+                            </Show>
                             <Show when=move || saved.get()>
                                 <div class="toast">
                                     <div class="alert alert-info">
@@ -93,3 +319,370 @@ pub fn UserEditProfile() -> impl IntoView {
         </SuspenseError>
     }
 }
+
+/// Enrolment for two-factor authentication. After generating a secret it needs to be confirmed
+/// with a token from the authenticator app, only then it is required for login.
+#[component]
+fn TwoFactorAuth(enabled: bool) -> impl IntoView {
+    let (enabled, set_enabled) = signal(enabled);
+    let (secret, set_secret) = signal(None::<GenerateTotpSecretResponse>);
+    let (totp_token, set_totp_token) = signal(String::new());
+    let (recovery_codes, set_recovery_codes) = signal(None::<Vec<String>>);
+    let (password, set_password) = signal(String::new());
+
+    let generate_action = Action::new(move |_: &()| async move {
+        CLIENT
+            .generate_totp()
+            .await
+            .error_popup(|res| set_secret.set(Some(res)));
+    });
+    let enable_action = Action::new(move |totp_token: &String| {
+        let totp_token = totp_token.clone();
+        async move {
+            CLIENT.enable_totp(totp_token).await.error_popup(|res| {
+                set_recovery_codes.set(Some(res.recovery_codes));
+                set_secret.set(None);
+                set_totp_token.set(String::new());
+                set_enabled.set(true);
+            });
+        }
+    });
+    let disable_action = Action::new(move |(password, totp_token): &(String, String)| {
+        let password = password.clone();
+        let totp_token = totp_token.clone();
+        async move {
+            CLIENT
+                .disable_totp(password, totp_token)
+                .await
+                .error_popup(|_| {
+                    set_recovery_codes.set(None);
+                    set_password.set(String::new());
+                    set_totp_token.set(String::new());
+                    set_enabled.set(false);
+                });
+        }
+    });
+
+    view! {
+        <h2 class="my-4 font-serif text-xl font-bold">Two-Factor Authentication</h2>
+        <Show when=move || recovery_codes.get().is_some()>
+            <div class="mb-2 alert alert-info">
+                <p>
+                    "Recovery codes, store them in a safe place. Each of them can be used once instead of a token if you lose access to your authenticator app."
+                </p>
+                <ul class="font-mono">
+                    {move || {
+                        recovery_codes
+                            .get()
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|c| view! { <li>{c}</li> })
+                            .collect::<Vec<_>>()
+                    }}
+                </ul>
+            </div>
+        </Show>
+        <Show
+            when=move || enabled.get()
+            fallback=move || {
+                view! {
+                    <Show
+                        when=move || secret.get().is_some()
+                        fallback=move || {
+                            view! {
+                                <p class="mb-2">
+                                    "Require a token from an authenticator app in addition to the password for login."
+                                </p>
+                                <button
+                                    class="btn btn-primary"
+                                    on:click=move |_| {
+                                        generate_action.dispatch(());
+                                    }
+                                >
+                                    Setup two-factor authentication
+                                </button>
+                            }
+                        }
+                    >
+                        <p class="mb-2">
+                            "Add this secret to your authenticator app, then enter the current token to confirm: "
+                            <code>{move || secret.get().map(|s| s.secret)}</code>
+                        </p>
+                        <p class="mb-2">
+                            <a
+                                class="link"
+                                href=move || secret.get().map(|s| s.totp_url).unwrap_or_default()
+                            >
+                                Open in authenticator app
+                            </a>
+                        </p>
+                        <div class="flex flex-row mb-2">
+                            <input
+                                type="text"
+                                class="w-80 input input-secondary input-bordered"
+                                autocomplete="one-time-code"
+                                placeholder="Token"
+                                bind:value=(totp_token, set_totp_token)
+                            />
+                        </div>
+                        <button
+                            class="btn btn-primary"
+                            prop:disabled=move || totp_token.get().is_empty()
+                            on:click=move |_| {
+                                enable_action.dispatch(totp_token.get());
+                            }
+                        >
+                            Enable
+                        </button>
+                    </Show>
+                }
+            }
+        >
+            <p class="mb-2">"Two-factor authentication is enabled."</p>
+            <div class="flex flex-row mb-2">
+                <label class="block w-40" for="totp_password">
+                    Password
+                </label>
+                <input
+                    type="password"
+                    id="totp_password"
+                    class="w-80 input input-secondary input-bordered"
+                    bind:value=(password, set_password)
+                />
+            </div>
+            <div class="flex flex-row mb-2">
+                <label class="block w-40" for="totp_disable_token">
+                    Token or recovery code
+                </label>
+                <input
+                    type="text"
+                    id="totp_disable_token"
+                    class="w-80 input input-secondary input-bordered"
+                    autocomplete="one-time-code"
+                    bind:value=(totp_token, set_totp_token)
+                />
+            </div>
+            <button
+                class="btn btn-warning"
+                prop:disabled=move || password.get().is_empty() || totp_token.get().is_empty()
+                on:click=move |_| {
+                    disable_action.dispatch((password.get(), totp_token.get()));
+                }
+            >
+                Disable two-factor authentication
+            </button>
+        </Show>
+    }
+}
+
+/// Devices where the user is logged in.
+#[component]
+fn Sessions() -> impl IntoView {
+    let sessions = Resource::new(|| (), |_| async move { CLIENT.list_sessions().await });
+    let revoke_action = Action::new(move |id: &LoginSessionId| {
+        let id = *id;
+        async move {
+            CLIENT
+                .revoke_session(id)
+                .await
+                .error_popup(|_| sessions.refetch());
+        }
+    });
+
+    view! {
+        <h2 class="my-4 font-serif text-xl font-bold">Sessions</h2>
+        <SuspenseError result=sessions>
+            {move || Suspend::new(async move {
+                sessions
+                    .await
+                    .map(|sessions| {
+                        view! {
+                            <ul class="mb-4 list-disc list-inside">
+                                {sessions
+                                    .into_iter()
+                                    .map(|s| {
+                                        let id = s.session.id;
+                                        let user_agent = s
+                                            .session
+                                            .user_agent
+                                            .unwrap_or_else(|| "Unknown device".to_string());
+                                        let is_current = s.current;
+                                        let current = if is_current { " (current)" } else { "" };
+                                        view! {
+                                            <li>
+                                                {format!(
+                                                    "{user_agent}{current}, last used {} ",
+                                                    time_ago(s.session.last_used),
+                                                )}
+                                                <Show when=move || !is_current>
+                                                    <button
+                                                        class="btn btn-xs btn-outline"
+                                                        on:click=move |_| {
+                                                            revoke_action.dispatch(id);
+                                                        }
+                                                    >
+                                                        Revoke
+                                                    </button>
+                                                </Show>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                        }
+                    })
+            })}
+        </SuspenseError>
+    }
+}
+
+/// Personal api tokens which bots and scripts can use instead of the password.
+#[component]
+fn ApiTokens() -> impl IntoView {
+    let tokens = Resource::new(|| (), |_| async move { CLIENT.list_api_tokens().await });
+    let (name, set_name) = signal(String::new());
+    let (scope_read, set_scope_read) = signal(true);
+    let (scope_edit, set_scope_edit) = signal(false);
+    let (scope_comment, set_scope_comment) = signal(false);
+    let (scope_admin, set_scope_admin) = signal(false);
+    let (new_token, set_new_token) = signal(None::<String>);
+
+    let create_action = Action::new(move |params: &CreateApiTokenParams| {
+        let params = params.clone();
+        async move {
+            CLIENT.create_api_token(&params).await.error_popup(|res| {
+                set_new_token.set(Some(res.token));
+                set_name.set(String::new());
+                tokens.refetch();
+            });
+        }
+    });
+    let delete_action = Action::new(move |id: &ApiTokenId| {
+        let id = *id;
+        async move {
+            CLIENT
+                .delete_api_token(id)
+                .await
+                .error_popup(|_| tokens.refetch());
+        }
+    });
+
+    view! {
+        <h2 class="my-4 font-serif text-xl font-bold">Api Tokens</h2>
+        <p class="mb-2">
+            "Api tokens allow bots and scripts to use your account without knowing the password. Pass them in the Authorization header as Bearer token."
+        </p>
+        <SuspenseError result=tokens>
+            {move || Suspend::new(async move {
+                tokens
+                    .await
+                    .map(|tokens| {
+                        view! {
+                            <ul class="mb-4 list-disc list-inside">
+                                {tokens
+                                    .into_iter()
+                                    .map(|token| {
+                                        let scopes = [
+                                            (token.scope_read, "read"),
+                                            (token.scope_edit, "edit"),
+                                            (token.scope_comment, "comment"),
+                                            (token.scope_admin, "admin"),
+                                        ]
+                                        .into_iter()
+                                        .filter(|(enabled, _)| *enabled)
+                                        .map(|(_, scope)| scope)
+                                        .collect::<Vec<_>>()
+                                        .join(", ");
+                                        let last_used = token
+                                            .last_used
+                                            .map(time_ago)
+                                            .unwrap_or_else(|| "never".to_string());
+                                        view! {
+                                            <li>
+                                                {format!(
+                                                    "{} ({scopes}), last used {last_used} ",
+                                                    token.name,
+                                                )}
+                                                <button
+                                                    class="btn btn-xs btn-outline"
+                                                    on:click=move |_| {
+                                                        delete_action.dispatch(token.id);
+                                                    }
+                                                >
+                                                    Revoke
+                                                </button>
+                                            </li>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                        }
+                    })
+            })}
+        </SuspenseError>
+        <Show when=move || new_token.get().is_some()>
+            <p class="mb-2 alert alert-info">
+                "New token, copy it now as it won't be shown again: "
+                <code>{move || new_token.get()}</code>
+            </p>
+        </Show>
+        <div class="flex flex-row gap-2 items-center mb-2">
+            <input
+                type="text"
+                class="w-80 input input-secondary input-bordered"
+                placeholder="Token name"
+                bind:value=(name, set_name)
+            />
+            <label class="label">
+                <input
+                    type="checkbox"
+                    class="mr-2 checkbox"
+                    bind:checked=(scope_read, set_scope_read)
+                />
+                Read
+            </label>
+            <label class="label">
+                <input
+                    type="checkbox"
+                    class="mr-2 checkbox"
+                    bind:checked=(scope_edit, set_scope_edit)
+                />
+                Edit
+            </label>
+            <label class="label">
+                <input
+                    type="checkbox"
+                    class="mr-2 checkbox"
+                    bind:checked=(scope_comment, set_scope_comment)
+                />
+                Comment
+            </label>
+            <Show when=is_admin>
+                <label class="label">
+                    <input
+                        type="checkbox"
+                        class="mr-2 checkbox"
+                        bind:checked=(scope_admin, set_scope_admin)
+                    />
+                    Admin
+                </label>
+            </Show>
+            <button
+                class="btn btn-primary"
+                prop:disabled=move || name.get().is_empty()
+                on:click=move |_| {
+                    let params = CreateApiTokenParams {
+                        name: name.get(),
+                        scope_read: scope_read.get(),
+                        scope_edit: scope_edit.get(),
+                        scope_comment: scope_comment.get(),
+                        scope_admin: scope_admin.get(),
+                    };
+                    create_action.dispatch(params);
+                }
+            >
+                Create token
+            </button>
+        </div>
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    common::user::LoginUserParams,
-    frontend::{api::CLIENT, components::credentials::*, utils::resources::site},
+    common::user::{LoginUserParams, TOTP_REQUIRED},
+    frontend::{
+        api::CLIENT,
+        components::{credentials::*, oauth_buttons::OAuthButtons},
+        utils::resources::site,
+    },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
@@ -11,11 +15,17 @@ pub fn Login() -> impl IntoView {
     let (login_response, set_login_response) = signal(false);
     let (login_error, set_login_error) = signal(None::<String>);
     let (wait_for_response, set_wait_for_response) = signal(false);
+    let (totp_required, set_totp_required) = signal(false);
+    let (totp_token, set_totp_token) = signal(String::new());
 
     let login_action = Action::new(move |(email, password): &(String, String)| {
         let username = email.to_string();
         let password = password.to_string();
-        let params = LoginUserParams { username, password };
+        let params = LoginUserParams {
+            username,
+            password,
+            totp_token: Some(totp_token.get_untracked()).filter(|_| totp_required.get_untracked()),
+        };
         async move {
             set_wait_for_response.update(|w| *w = true);
             let result = CLIENT.login(params).await;
@@ -29,6 +39,10 @@ pub fn Login() -> impl IntoView {
                 Err(err) => {
                     let msg = err.to_string();
                     log::warn!("Unable to login: {msg}");
+                    // two-factor authentication is enabled, show input for the token
+                    if msg.contains(TOTP_REQUIRED) {
+                        set_totp_required.set(true);
+                    }
                     set_login_error.update(|e| *e = Some(msg));
                 }
             }
@@ -49,7 +63,24 @@ pub fn Login() -> impl IntoView {
                         action=login_action
                         error=login_error.into()
                         disabled
-                    />
+                    >
+                        <Show when=move || totp_required.get()>
+                            <div class="h-2"></div>
+                            <input
+                                type="text"
+                                class="input input-primary input-bordered"
+                                required
+                                autocomplete="one-time-code"
+                                placeholder="Two-factor token or recovery code"
+                                bind:value=(totp_token, set_totp_token)
+                                prop:disabled=move || disabled.get()
+                            />
+                        </Show>
+                    </CredentialsForm>
+                    <OAuthButtons label="Login with" />
+                    <a class="link" href="/account/reset_password">
+                        Forgot password?
+                    </a>
                 }
             }
         >
@@ -1,5 +1,8 @@
 pub mod edit_profile;
 pub mod login;
 pub mod notifications;
+pub mod oauth_callback;
 pub mod profile;
 pub mod register;
+pub mod reset_password;
+pub mod verify_email;
@@ -1,12 +1,20 @@
 use crate::{
     common::{
-        article::{ApiConflict, DbArticle},
+        article::{DbArticle, EditView},
         comment::CommentViewWithArticle,
+        newtypes::{ConflictId, NotificationId},
+        registration_application::RegistrationApplicationView,
+        report::ReportView,
+        ApiNotification,
+        ListNotificationsParams,
         Notification,
     },
     frontend::{
         api::CLIENT,
-        components::suspense_error::SuspenseError,
+        components::{
+            pagination::{cursor_param, NextPage, PAGE_LIMIT},
+            suspense_error::SuspenseError,
+        },
         utils::{
             errors::{FrontendError, FrontendResultExt},
             formatting::{
@@ -20,77 +28,164 @@ use crate::{
         },
     },
 };
-use leptos::{either::EitherOf3, prelude::*};
+use leptos::{either::EitherOf6, prelude::*};
 use leptos_meta::Title;
 
-type NotificationsResource = Resource<Result<Vec<Notification>, FrontendError>>;
+type NotificationsResource = Resource<Result<Vec<ApiNotification>, FrontendError>>;
 
 #[component]
 pub fn Notifications() -> impl IntoView {
-    let notifications = Resource::new(
-        move || {},
-        |_| async move { CLIENT.notifications_list().await },
-    );
+    let notifications = Resource::new(cursor_param, |cursor| async move {
+        let params = ListNotificationsParams {
+            cursor: cursor.map(NotificationId),
+            limit: Some(PAGE_LIMIT),
+            ..Default::default()
+        };
+        CLIENT.notifications_list(&params).await
+    });
+    let click_mark_all_as_read = Action::new(move |_: &()| async move {
+        CLIENT
+            .mark_all_notifications_as_read()
+            .await
+            .error_popup(|_| notifications.refetch());
+    });
 
     view! {
         <Title text="Notifications" />
-        <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">Notifications</h1>
+        <div class="flex flex-row">
+            <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">Notifications</h1>
+            <button
+                class="place-self-center btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_mark_all_as_read.dispatch(());
+                }
+            >
+                Mark all as read
+            </button>
+        </div>
         <SuspenseError result=notifications>
-            <ul class="divide-y divide-solid">
-                {move || Suspend::new(async move {
-                    notifications
-                        .await
-                        .map(|n| {
-                            n.into_iter()
-                                .map(|ref notif| {
-                                    use Notification::*;
-                                    match notif {
-                                        EditConflict(c) => {
-                                            EitherOf3::A(edit_conflict_view(c, notifications))
-                                        }
-                                        ArticleApprovalRequired(a) => {
-                                            EitherOf3::B(article_approval_view(a, notifications))
-                                        }
-                                        Reply(c) => EitherOf3::C(reply_view(c, notifications)),
-                                    }
-                                })
-                                .collect::<Vec<_>>()
-                        })
-                })}
+            {move || Suspend::new(async move {
+                notifications
+                    .await
+                    .map(|n| {
+                        let page_len = n.len();
+                        let last_id = n.last().map(|n| n.id.0);
+                        view! {
+                            <ul class="divide-y divide-solid">
+                                {n
+                                    .into_iter()
+                                    .map(|notif| notification_view(notif, notifications))
+                                    .collect::<Vec<_>>()}
+                            </ul>
+                            <NextPage last_id page_len />
+                        }
+                    })
+            })}
 
-            </ul>
         </SuspenseError>
     }
 }
 
-fn edit_conflict_view(c: &ApiConflict, notifications: NotificationsResource) -> impl IntoView {
-    let link = format!("{}/edit?conflict_id={}", article_path(&c.article), c.id.0,);
-    let id = c.id;
-    let click_dismiss = Action::new(move |_: &()| async move {
+fn notification_view(
+    notif: ApiNotification,
+    notifications: NotificationsResource,
+) -> impl IntoView {
+    use Notification::*;
+    let id = notif.id;
+    let content = match notif.data {
+        EditConflict {
+            conflict_id,
+            summary,
+            article,
+        } => EitherOf6::A(edit_conflict_view(
+            conflict_id,
+            &summary,
+            &article,
+            notifications,
+        )),
+        ArticleApprovalRequired(a) => EitherOf6::B(article_approval_view(&a, notifications)),
+        Reply(c) => EitherOf6::C(reply_view(&c)),
+        ArticleEdited(e) => EitherOf6::D(article_edited_view(&e)),
+        Report(r) => EitherOf6::E(report_view(&r, notifications)),
+        RegistrationApplication(r) => {
+            EitherOf6::F(registration_application_view(&r, notifications))
+        }
+    };
+    let click_mark_as_read = Action::new(move |_: &()| async move {
         CLIENT
-            .delete_conflict(id)
+            .mark_notification_as_read(id)
             .await
             .error_popup(|_| notifications.refetch());
     });
+    let click_delete = Action::new(move |_: &()| async move {
+        CLIENT
+            .delete_notification(id)
+            .await
+            .error_popup(|_| notifications.refetch());
+    });
+    let read = notif.read;
+    let class_ = if read { "py-2 opacity-60" } else { "py-2" };
     view! {
-        <li class="py-2">
-            <a class="text-lg link" href=link>
-                {format!("Conflict: {} - {}", article_title(&c.article), c.summary)}
-            </a>
+        <li class=class_>
+            {content}
             <div class="mt-2 card-actions">
+                <Show when=move || !read>
+                    <button
+                        class="btn btn-sm btn-outline"
+                        on:click=move |_| {
+                            click_mark_as_read.dispatch(());
+                        }
+                    >
+                        Mark as read
+                    </button>
+                </Show>
                 <button
                     class="btn btn-sm btn-outline"
                     on:click=move |_| {
-                        click_dismiss.dispatch(());
+                        click_delete.dispatch(());
                     }
                 >
-                    Dismiss
+                    Delete
                 </button>
             </div>
         </li>
     }
 }
 
+fn edit_conflict_view(
+    conflict_id: ConflictId,
+    summary: &str,
+    article: &DbArticle,
+    notifications: NotificationsResource,
+) -> impl IntoView {
+    let link = format!(
+        "{}/edit?conflict_id={}",
+        article_path(article),
+        conflict_id.0
+    );
+    let click_dismiss = Action::new(move |_: &()| async move {
+        CLIENT
+            .delete_conflict(conflict_id)
+            .await
+            .error_popup(|_| notifications.refetch());
+    });
+    view! {
+        <a class="text-lg link" href=link>
+            {format!("Conflict: {} - {}", article_title(article), summary)}
+        </a>
+        <div class="mt-2 card-actions">
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_dismiss.dispatch(());
+                }
+            >
+                Dismiss conflict
+            </button>
+        </div>
+    }
+}
+
 fn article_approval_view(a: &DbArticle, notifications: NotificationsResource) -> impl IntoView {
     let id = a.id;
     let click_approve = Action::new(move |_: &()| async move {
@@ -106,62 +201,172 @@ fn article_approval_view(a: &DbArticle, notifications: NotificationsResource) ->
             .error_popup(|_| notifications.refetch());
     });
     view! {
-        <li class="py-2">
-            <a class="text-lg link" href=article_path(a)>
-                {format!("Approval required: {}", a.title)}
+        <a class="text-lg link" href=article_path(a)>
+            {format!("Approval required: {}", a.title)}
+        </a>
+        <div class="mt-2 card-actions">
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_approve.dispatch(());
+                }
+            >
+                Approve
+            </button>
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_reject.dispatch(());
+                }
+            >
+                Reject
+            </button>
+        </div>
+    }
+}
+
+fn reply_view(c: &CommentViewWithArticle) -> impl IntoView {
+    view! {
+        <div class="flex text-s">
+            <span class="grow">{user_link(&c.creator)}" - "{article_link(&c.article)}</span>
+            <a href=comment_path(&c.comment, &c.article) class="link">
+                {time_ago(c.comment.published)}
             </a>
-            <div class="mt-2 card-actions">
-                <button
-                    class="btn btn-sm btn-outline"
-                    on:click=move |_| {
-                        click_approve.dispatch(());
-                    }
-                >
-                    Approve
-                </button>
-                <button
-                    class="btn btn-sm btn-outline"
-                    on:click=move |_| {
-                        click_reject.dispatch(());
-                    }
-                >
-                    Reject
-                </button>
-            </div>
-        </li>
+        </div>
+        <div>{c.comment.content.clone()}</div>
+        <div class="mt-2 card-actions">
+            <a class="btn btn-sm btn-outline" href=comment_path(&c.comment, &c.article)>
+                View
+            </a>
+        </div>
     }
 }
 
-fn reply_view(c: &CommentViewWithArticle, notifications: NotificationsResource) -> impl IntoView {
-    let id = c.comment.id;
-    let click_mark_as_read = Action::new(move |_: &()| async move {
+fn article_edited_view(e: &EditView) -> impl IntoView {
+    let diff_link = format!("{}/diff/{}", article_path(&e.article), e.edit.hash.0);
+    view! {
+        <div class="flex text-s">
+            <span class="grow">{user_link(&e.creator)}" - "{article_link(&e.article)}</span>
+            <a href=diff_link.clone() class="link">
+                {time_ago(e.edit.published)}
+            </a>
+        </div>
+        <div>{format!("Edit: {}", e.edit.summary)}</div>
+        <div class="mt-2 card-actions">
+            <a class="btn btn-sm btn-outline" href=diff_link>
+                View
+            </a>
+        </div>
+    }
+}
+
+fn report_view(r: &ReportView, notifications: NotificationsResource) -> impl IntoView {
+    let id = r.report.id;
+    let link = match (&r.comment, &r.edit) {
+        (Some(c), _) => comment_path(c, &r.article),
+        (_, Some(e)) => format!("{}/diff/{}", article_path(&r.article), e.hash.0),
+        _ => article_path(&r.article),
+    };
+    let kind = match (&r.comment, &r.edit) {
+        (Some(_), _) => "comment",
+        (_, Some(_)) => "edit",
+        _ => "article",
+    };
+    let click_resolve = Action::new(move |_: &()| async move {
         CLIENT
-            .mark_comment_as_read(id)
+            .resolve_report(id)
+            .await
+            .error_popup(|_| notifications.refetch());
+    });
+    let click_dismiss = Action::new(move |_: &()| async move {
+        CLIENT
+            .dismiss_report(id)
             .await
             .error_popup(|_| notifications.refetch());
     });
     view! {
-        <li class="py-2">
-            <div class="flex text-s">
-                <span class="grow">{user_link(&c.creator)}" - "{article_link(&c.article)}</span>
-                <a href=comment_path(&c.comment, &c.article) class="link">
-                    {time_ago(c.comment.published)}
-                </a>
-            </div>
-            <div>{c.comment.content.clone()}</div>
-            <div class="mt-2 card-actions">
-                <a class="btn btn-sm btn-outline" href=comment_path(&c.comment, &c.article)>
-                    View
-                </a>
-                <button
-                    class="btn btn-sm btn-outline"
-                    on:click=move |_| {
-                        click_mark_as_read.dispatch(());
-                    }
-                >
-                    Mark as read
-                </button>
-            </div>
-        </li>
+        <div class="flex text-s">
+            <span class="grow">
+                {format!("Reported {kind}")}
+                {r.creator.as_ref().map(|c| view! { " by " {user_link(c)} })} " - "
+                {article_link(&r.article)}
+            </span>
+            <a href=link.clone() class="link">
+                {time_ago(r.report.published)}
+            </a>
+        </div>
+        <div>{format!("Reason: {}", r.report.reason)}</div>
+        <div class="mt-2 card-actions">
+            <a class="btn btn-sm btn-outline" href=link>
+                View
+            </a>
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_resolve.dispatch(());
+                }
+            >
+                Resolve
+            </button>
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_dismiss.dispatch(());
+                }
+            >
+                Dismiss
+            </button>
+        </div>
+    }
+}
+
+fn registration_application_view(
+    r: &RegistrationApplicationView,
+    notifications: NotificationsResource,
+) -> impl IntoView {
+    let id = r.application.id;
+    let (deny_reason, set_deny_reason) = signal(String::new());
+    let click_approve = Action::new(move |_: &()| async move {
+        CLIENT
+            .approve_registration(id, true, None)
+            .await
+            .error_popup(|_| notifications.refetch());
+    });
+    let click_deny = Action::new(move |_: &()| async move {
+        CLIENT
+            .approve_registration(id, false, Some(deny_reason.get_untracked()))
+            .await
+            .error_popup(|_| notifications.refetch());
+    });
+    view! {
+        <div class="flex text-s">
+            <span class="grow">"Registration application from "{user_link(&r.person)}</span>
+            <span>{time_ago(r.application.published)}</span>
+        </div>
+        <div>{format!("Answer: {}", r.application.answer)}</div>
+        <div class="mt-2 card-actions">
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_approve.dispatch(());
+                }
+            >
+                Approve
+            </button>
+            <input
+                type="text"
+                class="input input-sm input-bordered"
+                placeholder="Deny reason"
+                bind:value=(deny_reason, set_deny_reason)
+            />
+            <button
+                class="btn btn-sm btn-outline"
+                on:click=move |_| {
+                    click_deny.dispatch(());
+                }
+            >
+                Deny
+            </button>
+        </div>
     }
 }
@@ -0,0 +1,42 @@
+use crate::{
+    common::user::OAuthLoginParams,
+    frontend::{api::CLIENT, utils::resources::site},
+};
+use leptos::{either::EitherOf3, prelude::*};
+use leptos_meta::Title;
+use leptos_router::{components::Redirect, hooks::use_query_map};
+
+/// The OpenID Connect provider redirects here after login. The request needs to be made from
+/// the browser, as the oauth state is stored in a cookie.
+#[component]
+pub fn OAuthCallback() -> impl IntoView {
+    let query = use_query_map();
+    let login_action = Action::new(move |params: &OAuthLoginParams| {
+        let params = params.clone();
+        async move {
+            let result = CLIENT.oauth_login(params).await;
+            if result.is_ok() {
+                site().refetch();
+            }
+            result.map_err(|e| e.to_string())
+        }
+    });
+    Effect::new(move || {
+        let query = query.get_untracked();
+        let params = OAuthLoginParams {
+            code: query.get("code").unwrap_or_default(),
+            state: query.get("state").unwrap_or_default(),
+        };
+        login_action.dispatch(params);
+    });
+
+    view! {
+        <Title text="Login" />
+        <h1 class="my-4 font-serif text-4xl font-bold">Login</h1>
+        {move || match login_action.value().get() {
+            None => EitherOf3::A(view! { <p>"Logging in..."</p> }),
+            Some(Ok(_)) => EitherOf3::B(view! { <Redirect path="/" /> }),
+            Some(Err(err)) => EitherOf3::C(view! { <p class="alert alert-error">{err}</p> }),
+        }}
+    }
+}
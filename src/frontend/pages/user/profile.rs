@@ -1,15 +1,25 @@
 use crate::{
-    common::user::GetUserParams,
+    common::{
+        article::{EditSort, GetEditList},
+        mod_log::BanUserParams,
+        newtypes::{EditId, PersonId},
+        user::{AdminDeleteUserParams, AdminResetPasswordParams, GetUserParams},
+    },
     frontend::{
         api::CLIENT,
-        components::{edit_list::EditList, suspense_error::SuspenseError},
+        components::{
+            edit_list::EditList,
+            pagination::{cursor_param, PAGE_LIMIT},
+            suspense_error::SuspenseError,
+        },
         markdown::render_article_markdown,
-        utils::formatting::user_title,
+        utils::{errors::FrontendResultExt, formatting::user_title, resources::is_admin},
     },
 };
+use chrono::{TimeDelta, Utc};
 use leptos::prelude::*;
 use leptos_meta::Title;
-use leptos_router::hooks::use_params_map;
+use leptos_router::{components::Redirect, hooks::use_params_map};
 
 #[component]
 pub fn UserProfile() -> impl IntoView {
@@ -29,22 +39,67 @@ pub fn UserProfile() -> impl IntoView {
         <SuspenseError result=user_profile>
             {move || Suspend::new(async move {
                 let edits = Resource::new(
-                    move || user_profile.get(),
-                    move |_| async move { CLIENT.get_person_edits(user_profile.await?.id).await },
+                    move || (user_profile.get(), cursor_param()),
+                    move |(_, cursor)| async move {
+                        let params = GetEditList {
+                            person_id: Some(user_profile.await?.id),
+                            sort: Some(EditSort::Newest),
+                            cursor: cursor.map(EditId),
+                            limit: Some(PAGE_LIMIT),
+                            ..Default::default()
+                        };
+                        CLIENT.list_edits(&params).await
+                    },
                 );
                 user_profile
                     .await
                     .map(|person| {
+                        let (person_id, local, banned) = (
+                            person.id,
+                            person.local,
+                            person.is_banned(),
+                        );
                         view! {
                             <Title text=user_title(&person) />
-                            <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
-                                {user_title(&person)}
-                            </h1>
+                            {person
+                                .banner
+                                .clone()
+                                .map(|banner| {
+                                    view! {
+                                        <img
+                                            class="object-cover mt-4 w-full h-48 rounded-lg"
+                                            src=banner
+                                        />
+                                    }
+                                })}
+                            <div class="flex flex-row items-center my-6">
+                                {person
+                                    .avatar
+                                    .clone()
+                                    .map(|avatar| {
+                                        view! {
+                                            <img class="mr-4 w-24 h-24 rounded-full" src=avatar />
+                                        }
+                                    })}
+                                <h1 class="flex-auto font-serif text-4xl font-bold grow">
+                                    {user_title(&person)}
+                                </h1>
+                                <Show when=move || banned>
+                                    <span class="badge badge-error">Banned</span>
+                                </Show>
+                            </div>
 
                             <div
                                 class="mb-2 max-w-full prose prose-slate"
                                 inner_html=render_article_markdown(&person.bio.unwrap_or_default())
                             ></div>
+                            <Show when=move || is_admin() && local>
+                                <AdminResetPassword person_id />
+                                <AdminDeleteUser person_id />
+                            </Show>
+                            <Show when=is_admin>
+                                <AdminBanUser person_id banned />
+                            </Show>
 
                             <SuspenseError result=user_profile>
                                 {move || Suspend::new(async move {
@@ -64,3 +119,126 @@ pub fn UserProfile() -> impl IntoView {
         </SuspenseError>
     }
 }
+
+#[component]
+fn AdminResetPassword(person_id: PersonId) -> impl IntoView {
+    let (reset_link, set_reset_link) = signal(None::<String>);
+    let reset_action = Action::new(move |_: &()| async move {
+        CLIENT
+            .admin_reset_password(AdminResetPasswordParams { person_id })
+            .await
+            .error_popup(|res| set_reset_link.set(Some(res.reset_link)));
+    });
+    view! {
+        <Show
+            when=move || reset_link.get().is_some()
+            fallback=move || {
+                view! {
+                    <button
+                        class="mb-2 btn btn-sm btn-outline"
+                        on:click=move |_| {
+                            reset_action.dispatch(());
+                        }
+                    >
+                        Reset password
+                    </button>
+                }
+            }
+        >
+            <p class="mb-2">
+                "Password was reset. Send this link to the user to set a new password: "
+                <code>{move || reset_link.get()}</code>
+            </p>
+        </Show>
+    }
+}
+
+#[component]
+fn AdminDeleteUser(person_id: PersonId) -> impl IntoView {
+    let (deleted, set_deleted) = signal(false);
+    let delete_action = Action::new(move |_: &()| async move {
+        CLIENT
+            .admin_delete_user(AdminDeleteUserParams { person_id })
+            .await
+            .error_popup(|_| set_deleted.set(true));
+    });
+    view! {
+        <Show when=move || deleted.get()>
+            <Redirect path="/" />
+        </Show>
+        <button
+            class="mb-2 ml-2 btn btn-sm btn-error"
+            on:click=move |_| {
+                delete_action.dispatch(());
+            }
+        >
+            Delete account
+        </button>
+    }
+}
+
+#[component]
+fn AdminBanUser(person_id: PersonId, banned: bool) -> impl IntoView {
+    let (banned, set_banned) = signal(banned);
+    let (reason, set_reason) = signal(String::new());
+    let (days, set_days) = signal(String::new());
+    let ban_action = Action::new(move |ban: &bool| {
+        let params = BanUserParams {
+            person_id,
+            ban: *ban,
+            reason: Some(reason.get_untracked()).filter(|r| !r.is_empty()),
+            expires: days
+                .get_untracked()
+                .parse()
+                .ok()
+                .map(|d| Utc::now() + TimeDelta::days(d)),
+        };
+        async move {
+            CLIENT
+                .ban_user(&params)
+                .await
+                .error_popup(|p| set_banned.set(p.is_banned()));
+        }
+    });
+    view! {
+        <Show
+            when=move || banned.get()
+            fallback=move || {
+                view! {
+                    <div class="flex flex-row gap-2 mb-2">
+                        <input
+                            type="text"
+                            class="input input-sm input-bordered"
+                            placeholder="Reason"
+                            bind:value=(reason, set_reason)
+                        />
+                        <input
+                            type="number"
+                            min="1"
+                            class="w-40 input input-sm input-bordered"
+                            placeholder="Days (permanent)"
+                            bind:value=(days, set_days)
+                        />
+                        <button
+                            class="btn btn-sm btn-error"
+                            on:click=move |_| {
+                                ban_action.dispatch(true);
+                            }
+                        >
+                            Ban user
+                        </button>
+                    </div>
+                }
+            }
+        >
+            <button
+                class="mb-2 btn btn-sm btn-outline"
+                on:click=move |_| {
+                    ban_action.dispatch(false);
+                }
+            >
+                Unban user
+            </button>
+        </Show>
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    common::user::RegisterUserParams,
-    frontend::{api::CLIENT, components::credentials::*, utils::resources::site},
+    common::{instance::RegistrationMode, user::RegisterUserParams},
+    frontend::{
+        api::CLIENT,
+        components::credentials::*,
+        utils::resources::{config, site},
+    },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
@@ -11,11 +15,32 @@ pub fn Register() -> impl IntoView {
     let (register_response, set_register_response) = signal(false);
     let (register_error, set_register_error) = signal(None::<String>);
     let (wait_for_response, set_wait_for_response) = signal(false);
+    let (application_answer, set_application_answer) = signal(String::new());
+    let (captcha_answer, set_captcha_answer) = signal(String::new());
+    let require_application =
+        move || config().registration_mode == RegistrationMode::RequireApplication;
+    let captcha = Resource::new(
+        move || config().registration_captcha,
+        |enabled| async move {
+            if enabled {
+                CLIENT.get_captcha().await.ok()
+            } else {
+                None
+            }
+        },
+    );
 
     let register_action = Action::new(move |(email, password): &(String, String)| {
         let username = email.to_string();
         let password = password.to_string();
-        let params = RegisterUserParams { username, password };
+        let params = RegisterUserParams {
+            username,
+            password,
+            application_answer: Some(application_answer.get_untracked())
+                .filter(|_| require_application()),
+            captcha_uuid: captcha.get_untracked().flatten().map(|c| c.uuid),
+            captcha_answer: Some(captcha_answer.get_untracked()),
+        };
         info!("Try to register new account for {}", params.username);
         async move {
             set_wait_for_response.set(true);
@@ -31,6 +56,9 @@ pub fn Register() -> impl IntoView {
                     let msg = err.to_string();
                     log::warn!("Unable to register new account: {msg}");
                     set_register_error.set(Some(msg));
+                    // each captcha can only be used once
+                    set_captcha_answer.set(String::new());
+                    captcha.refetch();
                 }
             }
         }
@@ -50,12 +78,49 @@ pub fn Register() -> impl IntoView {
                         action=register_action
                         error=register_error.into()
                         disabled
-                    />
+                    >
+                        <Show when=require_application>
+                            <p class="mt-2">{move || config().registration_question}</p>
+                            <textarea
+                                class="textarea textarea-primary"
+                                required
+                                placeholder="Answer"
+                                bind:value=(application_answer, set_application_answer)
+                                prop:disabled=move || disabled.get()
+                            ></textarea>
+                        </Show>
+                        <Show when=move || config().registration_captcha>
+                            <Suspense>
+                                {move || Suspend::new(async move {
+                                    captcha
+                                        .await
+                                        .map(|c| {
+                                            let src = format!("/api/v1/account/captcha/{}", c.uuid);
+                                            view! { <img class="my-2" src=src /> }
+                                        })
+                                })}
+                            </Suspense>
+                            <input
+                                type="text"
+                                class="input input-primary input-bordered"
+                                required
+                                placeholder="Captcha"
+                                bind:value=(captcha_answer, set_captcha_answer)
+                                prop:disabled=move || disabled.get()
+                            />
+                        </Show>
+                    </CredentialsForm>
                 }
             }
         >
-
-            <p>"You have successfully registered."</p>
+            <Show
+                when=require_application
+                fallback=|| view! { <p>"You have successfully registered."</p> }
+            >
+                <p>
+                    "Your registration application was submitted. You can login after an admin approved it."
+                </p>
+            </Show>
         </Show>
     }
 }
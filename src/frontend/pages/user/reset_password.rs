@@ -0,0 +1,127 @@
+use crate::{
+    common::user::{RequestPasswordResetParams, ResetPasswordParams},
+    frontend::{api::CLIENT, utils::errors::FrontendResultExt},
+};
+use leptos::{either::Either, prelude::*};
+use leptos_meta::Title;
+use leptos_router::hooks::use_query_map;
+
+/// Without token param, shows a form to request a password reset email. The link in that email
+/// leads back here with token, to set a new password.
+#[component]
+pub fn ResetPassword() -> impl IntoView {
+    let token = use_query_map().get_untracked().get("token");
+    view! {
+        <Title text="Reset Password" />
+        <h1 class="my-4 font-serif text-4xl font-bold">Reset Password</h1>
+        {match token {
+            Some(token) => Either::Left(view! { <NewPasswordForm token /> }),
+            None => Either::Right(view! { <RequestResetForm /> }),
+        }}
+    }
+}
+
+#[component]
+fn RequestResetForm() -> impl IntoView {
+    let (email, set_email) = signal(String::new());
+    let (sent, set_sent) = signal(false);
+    let request_action = Action::new(move |email: &String| {
+        let params = RequestPasswordResetParams {
+            email: email.clone(),
+        };
+        async move {
+            CLIENT
+                .request_password_reset(params)
+                .await
+                .error_popup(|_| set_sent.set(true));
+        }
+    });
+    view! {
+        <Show
+            when=move || sent.get()
+            fallback=move || {
+                view! {
+                    <form class="form-control max-w-80" on:submit=|ev| ev.prevent_default()>
+                        <input
+                            type="email"
+                            class="input input-primary input-bordered"
+                            required
+                            placeholder="Email"
+                            bind:value=(email, set_email)
+                        />
+                        <div>
+                            <button
+                                class="my-2 btn btn-primary"
+                                prop:disabled=move || email.get().is_empty()
+                                on:click=move |_| {
+                                    request_action.dispatch(email.get());
+                                }
+                            >
+                                Send reset link
+                            </button>
+                        </div>
+                    </form>
+                }
+            }
+        >
+            <p>
+                "If an account with this verified email address exists, you will receive an email with a link to reset your password."
+            </p>
+        </Show>
+    }
+}
+
+#[component]
+fn NewPasswordForm(token: String) -> impl IntoView {
+    let (password, set_password) = signal(String::new());
+    let (done, set_done) = signal(false);
+    let reset_action = Action::new(move |new_password: &String| {
+        let params = ResetPasswordParams {
+            token: token.clone(),
+            new_password: new_password.clone(),
+        };
+        async move {
+            CLIENT
+                .reset_password(params)
+                .await
+                .error_popup(|_| set_done.set(true));
+        }
+    });
+    view! {
+        <Show
+            when=move || done.get()
+            fallback=move || {
+                view! {
+                    <form class="form-control max-w-80" on:submit=|ev| ev.prevent_default()>
+                        <input
+                            type="password"
+                            class="input input-primary input-bordered"
+                            required
+                            placeholder="New password"
+                            bind:value=(password, set_password)
+                        />
+                        <div>
+                            <button
+                                class="my-2 btn btn-primary"
+                                prop:disabled=move || password.get().is_empty()
+                                on:click=move |_| {
+                                    reset_action.dispatch(password.get());
+                                }
+                            >
+                                Set new password
+                            </button>
+                        </div>
+                    </form>
+                }
+            }
+        >
+            <p>
+                "Your password was changed. You can now "
+                <a class="link" href="/login">
+                    login
+                </a>
+                " with the new password."
+            </p>
+        </Show>
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{
+    common::user::VerifyEmailParams,
+    frontend::{api::CLIENT, components::suspense_error::SuspenseError},
+};
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_query_map;
+
+#[component]
+pub fn VerifyEmail() -> impl IntoView {
+    let token = move || use_query_map().get().get("token").unwrap_or_default();
+    let verified = Resource::new(token, |token| async move {
+        CLIENT.verify_email(VerifyEmailParams { token }).await
+    });
+
+    view! {
+        <Title text="Verify Email" />
+        <h1 class="my-4 font-serif text-4xl font-bold">Verify Email</h1>
+        <SuspenseError result=verified>
+            {move || Suspend::new(async move {
+                verified.await.map(|_| view! { <p>"Your email address was verified."</p> })
+            })}
+        </SuspenseError>
+    }
+}
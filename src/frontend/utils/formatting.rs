@@ -69,6 +69,14 @@ pub fn user_link(person: &DbPerson) -> impl IntoView {
     }
 }
 
+/// Small profile picture which is shown next to the username. Renders nothing if the user has no
+/// avatar.
+pub fn user_avatar(person: &DbPerson) -> impl IntoView {
+    person.avatar.clone().map(|avatar| {
+        view! { <img class="inline-block mr-1 w-6 h-6 align-middle rounded-full" src=avatar /> }
+    })
+}
+
 pub fn render_date_time(date_time: DateTime<Utc>) -> String {
     date_time
         .with_timezone(&Local)
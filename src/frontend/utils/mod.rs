@@ -1,6 +1,7 @@
+use crate::frontend::api::UploadFile;
 use chrono::{Duration, Local};
 use codee::string::FromToStringCodec;
-use leptos::prelude::*;
+use leptos::{ev, prelude::*};
 use leptos_use::{use_cookie_with_options, SameSite, UseCookieOptions};
 
 pub mod dark_mode;
@@ -16,3 +17,19 @@ pub fn use_cookie(name: &str) -> (Signal<Option<bool>>, WriteSignal<Option<bool>
         .same_site(SameSite::Strict);
     use_cookie_with_options::<bool, FromToStringCodec>(name, cookie_options)
 }
+
+/// File which was selected in a file input. Event handlers only run in the browser, so this is
+/// always none during server side rendering.
+pub fn selected_file(ev: &ev::Event) -> Option<UploadFile> {
+    #[cfg(not(feature = "ssr"))]
+    {
+        use web_sys::HtmlInputElement;
+        let file = event_target::<HtmlInputElement>(ev).files()?.get(0)?;
+        Some(send_wrapper::SendWrapper::new(file))
+    }
+    #[cfg(feature = "ssr")]
+    {
+        let _ = ev;
+        None
+    }
+}
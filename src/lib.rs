@@ -1,3 +1,6 @@
+// Needed for the deeply nested views which include the report form
+#![recursion_limit = "256"]
+
 #[cfg(feature = "ssr")]
 pub mod backend;
 pub mod common;
@@ -1,21 +1,41 @@
 #![expect(clippy::unwrap_used)]
 
 use anyhow::Result;
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::{get, post},
+    Form,
+    Json,
+    Router,
+};
 use ibis::{
     backend::{
         start,
-        utils::config::{IbisConfig, IbisConfigDatabase, IbisConfigFederation},
+        utils::config::{
+            IbisConfig,
+            IbisConfigDatabase,
+            IbisConfigEmail,
+            IbisConfigFederation,
+            IbisConfigMedia,
+            IbisConfigOAuthProvider,
+        },
     },
     common::{instance::Options, user::RegisterUserParams},
     frontend::api::ApiClient,
 };
+use serde_json::json;
 use std::{
+    collections::HashMap,
     env::current_dir,
     fs::{create_dir_all, remove_dir_all},
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
     ops::Deref,
     process::{Command, Stdio},
     sync::{
         atomic::{AtomicI32, Ordering},
+        Arc,
+        Mutex,
         Once,
     },
     thread::spawn,
@@ -27,6 +47,14 @@ pub struct TestData(pub IbisInstance, pub IbisInstance, pub IbisInstance);
 
 impl TestData {
     pub async fn start(article_approval: bool) -> Self {
+        let options = Options {
+            article_approval,
+            ..Default::default()
+        };
+        Self::start_with_options(options).await
+    }
+
+    pub async fn start_with_options(options: Options) -> Self {
         static INIT: Once = Once::new();
         INIT.call_once(|| {
             env_logger::builder()
@@ -59,9 +87,9 @@ impl TestData {
         }
 
         let (alpha, beta, gamma) = join!(
-            IbisInstance::start(alpha_db_path, port_alpha, "alpha", article_approval),
-            IbisInstance::start(beta_db_path, port_beta, "beta", article_approval),
-            IbisInstance::start(gamma_db_path, port_gamma, "gamma", article_approval)
+            IbisInstance::start(alpha_db_path, port_alpha, "alpha", options.clone()),
+            IbisInstance::start(beta_db_path, port_beta, "beta", options.clone()),
+            IbisInstance::start(gamma_db_path, port_gamma, "gamma", options)
         );
 
         Self(alpha, beta, gamma)
@@ -90,6 +118,8 @@ pub struct IbisInstance {
     db_path: String,
     db_handle: JoinHandle<()>,
     pub hostname: String,
+    /// Emails which were sent by this instance
+    pub emails: Arc<Mutex<Vec<String>>>,
 }
 
 impl IbisInstance {
@@ -108,10 +138,11 @@ impl IbisInstance {
         })
     }
 
-    async fn start(db_path: String, port: i32, username: &str, article_approval: bool) -> Self {
+    async fn start(db_path: String, port: i32, username: &str, options: Options) -> Self {
         let connection_url = format!("postgresql://ibis:password@/ibis?host={db_path}");
 
         let hostname = format!("localhost:{port}");
+        let (smtp_server, emails) = start_smtp_server();
         let config = IbisConfig {
             database: IbisConfigDatabase {
                 connection_url,
@@ -121,10 +152,23 @@ impl IbisInstance {
                 domain: hostname.clone(),
                 ..Default::default()
             },
-            options: Options {
-                registration_open: true,
-                article_approval,
+            options,
+            media: IbisConfigMedia {
+                directory: format!("{db_path}/media"),
+                allow_private_addresses: true,
             },
+            email: Some(IbisConfigEmail {
+                smtp_server,
+                smtp_from_address: format!("{username}@example.com"),
+                ..Default::default()
+            }),
+            oauth_providers: vec![IbisConfigOAuthProvider {
+                name: "mock".to_string(),
+                display_name: "Mock".to_string(),
+                issuer: start_oauth_server().await,
+                client_id: "ibis".to_string(),
+                client_secret: "secret".to_string(),
+            }],
             ..Default::default()
         };
         let api_client = ApiClient::new(Some(hostname.clone()));
@@ -140,6 +184,8 @@ impl IbisInstance {
         let params = RegisterUserParams {
             username: username.to_string(),
             password: "hunter2".to_string(),
+            application_answer: Some("test".to_string()),
+            ..Default::default()
         };
         api_client.register(params).await.unwrap();
         Self {
@@ -147,9 +193,24 @@ impl IbisInstance {
             db_path,
             db_handle,
             hostname,
+            emails,
         }
     }
 
+    /// Read the token from the link in the last email that was sent.
+    pub fn last_email_token(&self) -> String {
+        let email = self.emails.lock().unwrap().last().unwrap().clone();
+        // undo quoted-printable encoding
+        let email = email.replace("=\n", "").replace("=3D", "=");
+        email
+            .split("token=")
+            .nth(1)
+            .unwrap()
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect()
+    }
+
     fn stop(self) -> std::thread::JoinHandle<()> {
         self.db_handle.abort();
         Self::stop_internal(self.db_path)
@@ -167,6 +228,81 @@ impl IbisInstance {
     }
 }
 
+/// Minimal smtp server which keeps all received emails in memory, so that tests can read them.
+fn start_smtp_server() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let emails = Arc::new(Mutex::new(vec![]));
+    let emails_ = emails.clone();
+    spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            stream.write_all(b"220 localhost\r\n").unwrap();
+            let mut data: Option<String> = None;
+            for line in reader.lines() {
+                let line = line.unwrap();
+                if let Some(d) = &mut data {
+                    if line == "." {
+                        emails_.lock().unwrap().push(data.take().unwrap());
+                        stream.write_all(b"250 OK\r\n").unwrap();
+                    } else {
+                        d.push_str(&line);
+                        d.push('\n');
+                    }
+                    continue;
+                }
+                let command = line.to_uppercase();
+                if command.starts_with("DATA") {
+                    data = Some(String::new());
+                    stream.write_all(b"354 Start mail input\r\n").unwrap();
+                } else if command.starts_with("QUIT") {
+                    stream.write_all(b"221 Bye\r\n").unwrap();
+                    break;
+                } else {
+                    stream.write_all(b"250 OK\r\n").unwrap();
+                }
+            }
+        }
+    });
+    (address, emails)
+}
+
+/// Minimal OpenID Connect provider. The authorization code is used as access token, and as
+/// subject and username of the user, so that tests can login as any user.
+async fn start_oauth_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+    let issuer_ = issuer.clone();
+    let metadata = move || {
+        let issuer = issuer_.clone();
+        async move {
+            Json(json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{issuer}/authorize"),
+                "token_endpoint": format!("{issuer}/token"),
+                "userinfo_endpoint": format!("{issuer}/userinfo"),
+            }))
+        }
+    };
+    let token = |Form(params): Form<HashMap<String, String>>| async move {
+        assert_eq!("secret", params["client_secret"]);
+        assert_eq!(64, params["code_verifier"].len());
+        Json(json!({ "access_token": params["code"], "token_type": "Bearer" }))
+    };
+    let userinfo = |headers: HeaderMap| async move {
+        let auth = headers[AUTHORIZATION].to_str().unwrap();
+        let token = auth.trim_start_matches("Bearer ");
+        Json(json!({ "sub": token, "preferred_username": token }))
+    };
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(metadata))
+        .route("/token", post(token))
+        .route("/userinfo", get(userinfo));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    issuer
+}
+
 impl Deref for IbisInstance {
     type Target = ApiClient;
 
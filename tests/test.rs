@@ -4,26 +4,75 @@ mod common;
 
 use crate::common::{TestData, TEST_ARTICLE_DEFAULT_TEXT};
 use anyhow::Result;
-use ibis::common::{
-    article::{
-        CreateArticleParams,
-        DbArticleView,
-        EditArticleParams,
-        ForkArticleParams,
-        GetArticleParams,
-        ListArticlesParams,
-        ProtectArticleParams,
-        SearchArticleParams,
+use chrono::{TimeDelta, Utc};
+use ibis::{
+    common::{
+        api_token::CreateApiTokenParams,
+        article::{
+            ArticleSort,
+            CreateArticleParams,
+            DbArticleView,
+            DeleteArticleParams,
+            EditArticleParams,
+            EditSort,
+            ForkArticleParams,
+            GetArticleParams,
+            GetEditList,
+            ListArticlesParams,
+            MoveArticleParams,
+            ProtectArticleParams,
+            RevertArticleParams,
+            SearchArticleParams,
+        },
+        comment::{CreateCommentParams, EditCommentParams},
+        instance::{
+            AddFederationListParams,
+            FollowInstanceParams,
+            ListInstancesParams,
+            Options,
+            RegistrationMode,
+            UpdateInstanceParams,
+        },
+        mod_log::{BanUserParams, ListModLogParams, ModAction},
+        newtypes::{
+            CommentId,
+            ConflictId,
+            InstanceId,
+            NotificationId,
+            RegistrationApplicationId,
+            ReportId,
+        },
+        report::CreateReportParams,
+        user::{
+            AdminDeleteUserParams,
+            AdminResetPasswordParams,
+            ChangePasswordParams,
+            DeleteAccountParams,
+            GetUserParams,
+            LocalUserView,
+            LoginUserParams,
+            OAuthLoginParams,
+            RegisterUserParams,
+            RequestPasswordResetParams,
+            ResetPasswordParams,
+            UpdateEmailParams,
+            UpdateUserParams,
+            VerifyEmailParams,
+            TOTP_REQUIRED,
+        },
+        utils::extract_domain,
+        ListNotificationsParams,
+        Notification,
     },
-    comment::{CreateCommentParams, EditCommentParams},
-    user::{GetUserParams, LoginUserParams, RegisterUserParams},
-    utils::extract_domain,
-    Notification,
+    frontend::{api::ApiClient, utils::errors::FrontendResult},
 };
+use image::{ImageFormat, RgbImage};
 use pretty_assertions::{assert_eq, assert_ne};
+use reqwest::StatusCode;
 use retry_future::{LinearRetryStrategy, RetryFuture, RetryPolicy};
-use std::time::Duration;
+use std::{collections::HashMap, io::Cursor, time::Duration};
 use tokio::time::sleep;
+use totp_rs::TOTP;
 use url::Url;
 
 #[tokio::test]
@@ -75,15 +124,17 @@ async fn test_create_read_and_edit_local_article() -> Result<()> {
 
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        ..Default::default()
     };
     let search_res = alpha.search(&search_params).await.unwrap();
     assert_eq!(1, search_res.len());
-    assert_eq!(edit_res.article, search_res[0]);
+    assert_eq!(edit_res.article.id, search_res[0].id);
+    assert_eq!(edit_res.article.title, search_res[0].title);
 
     let list_articles = alpha
         .list_articles(ListArticlesParams {
             only_local: Some(false),
-            instance_id: None,
+            ..Default::default()
         })
         .await
         .unwrap();
@@ -408,12 +459,13 @@ async fn test_local_edit_conflict() -> Result<()> {
     let edit_res = alpha.edit_article(&edit_params).await.unwrap().unwrap();
     assert_eq!("<<<<<<< ours\nIpsum Lorem\n||||||| original\nsome example text\n=======\nLorem Ipsum\n>>>>>>> theirs\n", edit_res.three_way_merge);
 
-    let notifications = alpha.notifications_list().await.unwrap();
+    let notifications = alpha.notifications_list(&Default::default()).await.unwrap();
     assert_eq!(1, notifications.len());
-    let Notification::EditConflict(conflict) = &notifications[0] else {
+    let Notification::EditConflict { conflict_id, .. } = &notifications[0].data else {
         panic!()
     };
-    assert_eq!(conflict, &edit_res);
+    let conflict = alpha.get_conflict(*conflict_id).await.unwrap();
+    assert_eq!(conflict, edit_res);
 
     let edit_params = EditArticleParams {
         article_id: create_res.article.id,
@@ -510,12 +562,21 @@ async fn test_federated_edit_conflict() -> Result<()> {
     assert!(gamma_edits[2].edit.pending);
     assert!(!edit_res.article.local);
 
+    // pending edit can't be used as revert target
+    let revert_params = RevertArticleParams {
+        article_id: resolve_res.article.id,
+        version: gamma_edits[2].edit.hash.clone(),
+    };
+    let err = gamma.revert_article(&revert_params).await.unwrap_err();
+    assert!(err.to_string().contains("pending edit"));
+
     assert_eq!(1, gamma.notifications_count().await.unwrap());
-    let notifications = gamma.notifications_list().await.unwrap();
+    let notifications = gamma.notifications_list(&Default::default()).await.unwrap();
     assert_eq!(1, notifications.len());
-    let Notification::EditConflict(conflict) = &notifications[0] else {
+    let Notification::EditConflict { conflict_id, .. } = &notifications[0].data else {
         panic!()
     };
+    let conflict = gamma.get_conflict(*conflict_id).await.unwrap();
 
     // resolve the conflict
     let edit_params = EditArticleParams {
@@ -535,7 +596,7 @@ async fn test_federated_edit_conflict() -> Result<()> {
     assert!(gamma_edits.iter().all(|e| !e.edit.pending));
 
     assert_eq!(0, gamma.notifications_count().await.unwrap());
-    let notifications = gamma.notifications_list().await.unwrap();
+    let notifications = gamma.notifications_list(&Default::default()).await.unwrap();
     assert_eq!(0, notifications.len());
 
     TestData::stop(alpha, beta, gamma)
@@ -666,6 +727,7 @@ async fn test_fork_article() -> Result<()> {
     // now search returns two articles for this title (original and forked)
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        ..Default::default()
     };
     let search_res = beta.search(&search_params).await.unwrap();
     assert_eq!(2, search_res.len());
@@ -681,12 +743,14 @@ async fn test_user_registration_login() -> Result<()> {
     let register_data = RegisterUserParams {
         username: username.to_string(),
         password: password.to_string(),
+        ..Default::default()
     };
     alpha.register(register_data).await.unwrap();
 
     let login_data = LoginUserParams {
         username: username.to_string(),
         password: "asd123".to_string(),
+        ..Default::default()
     };
     let invalid_login = alpha.login(login_data).await;
     assert!(invalid_login.is_err());
@@ -694,6 +758,7 @@ async fn test_user_registration_login() -> Result<()> {
     let login_data = LoginUserParams {
         username: username.to_string(),
         password: password.to_string(),
+        ..Default::default()
     };
     alpha.login(login_data).await.unwrap();
 
@@ -708,6 +773,108 @@ async fn test_user_registration_login() -> Result<()> {
     TestData::stop(alpha, beta, gamma)
 }
 
+#[tokio::test]
+async fn test_registration_application() -> Result<()> {
+    let options = Options {
+        registration_mode: RegistrationMode::RequireApplication,
+        ..Default::default()
+    };
+    let TestData(alpha, beta, gamma) = TestData::start_with_options(options).await;
+    let register = |username: &str, answer: Option<&str>| RegisterUserParams {
+        username: username.to_string(),
+        password: "hunter2".to_string(),
+        application_answer: answer.map(str::to_string),
+        ..Default::default()
+    };
+    let login = |username: &str| LoginUserParams {
+        username: username.to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+
+    // answer to registration question is required
+    let user = ApiClient::new(Some(alpha.hostname.clone()));
+    assert!(user.register(register("my_user", None)).await.is_err());
+    user.register(register("my_user", Some("I like wikis")))
+        .await
+        .unwrap();
+    user.register(register("spammer", Some("buy now")))
+        .await
+        .unwrap();
+
+    // cant login before application is approved
+    let err = user.login(login("my_user")).await.unwrap_err();
+    assert!(err.to_string().contains("pending approval"));
+
+    // admin gets notified about applications
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let notifications = admin
+        .notifications_list(&ListNotificationsParams::default())
+        .await
+        .unwrap();
+    let application = |username: &str| {
+        notifications
+            .iter()
+            .find_map(|n| match &n.data {
+                Notification::RegistrationApplication(a) if a.person.username == username => {
+                    Some(a.clone())
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+    let my_user_application = application("my_user");
+    assert_eq!("I like wikis", my_user_application.application.answer);
+    let spammer_application = application("spammer");
+
+    // approve one application and deny the other
+    admin
+        .approve_registration(my_user_application.application.id, true, None)
+        .await
+        .unwrap();
+    admin
+        .approve_registration(
+            spammer_application.application.id,
+            false,
+            Some("spam".to_string()),
+        )
+        .await
+        .unwrap();
+    assert!(admin
+        .approve_registration(my_user_application.application.id, false, None)
+        .await
+        .is_err());
+
+    user.login(login("my_user")).await.unwrap();
+    let err = user.login(login("spammer")).await.unwrap_err();
+    assert!(err.to_string().contains("denied: spam"));
+
+    // both actions are shown in mod log
+    let mod_log = alpha.mod_log(&ListModLogParams::default()).await.unwrap();
+    assert_eq!(ModAction::DenyRegistration, mod_log[0].mod_log.action);
+    assert_eq!(ModAction::ApproveRegistration, mod_log[1].mod_log.action);
+
+    // accounts created with oauth login also need to be approved
+    let user = ApiClient::new(Some(alpha.hostname.clone()));
+    let authorize = user.oauth_authorize("mock".to_string()).await.unwrap();
+    let url = Url::parse(&authorize.url).unwrap();
+    let (_, state) = url.query_pairs().find(|(k, _)| k == "state").unwrap();
+    let params = OAuthLoginParams {
+        code: "oauth_user".to_string(),
+        state: state.to_string(),
+    };
+    let err = user.oauth_login(params).await.unwrap_err();
+    assert!(err.to_string().contains("pending approval"));
+
+    TestData::stop(alpha, beta, gamma)
+}
+
 #[tokio::test]
 async fn test_user_profile() -> Result<()> {
     let TestData(alpha, beta, gamma) = TestData::start(false).await;
@@ -737,209 +904,2173 @@ async fn test_user_profile() -> Result<()> {
 }
 
 #[tokio::test]
-async fn test_lock_article() -> Result<()> {
+async fn test_federate_user_profile_update() -> Result<()> {
     let TestData(alpha, beta, gamma) = TestData::start(false).await;
 
-    // create article
+    let beta_id_on_alpha = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // create article on beta, which is federated to alpha
     let create_params = CreateArticleParams {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
     };
-    let create_res = alpha.create_article(&create_params).await.unwrap();
-    assert!(!create_res.article.protected);
-
-    // lock from normal user fails
-    let lock_params = ProtectArticleParams {
-        article_id: create_res.article.id,
-        protected: true,
+    beta.create_article(&create_params).await.unwrap();
+    let get_params = GetArticleParams {
+        title: Some(create_params.title.clone()),
+        domain: Some(beta_id_on_alpha.domain.clone()),
+        id: None,
     };
-    let lock_res = alpha.protect_article(&lock_params).await;
-    assert!(lock_res.is_err());
+    let get_res = alpha.get_article(get_params).await.unwrap();
 
-    // login as admin to lock article
-    let params = LoginUserParams {
-        username: "ibis".to_string(),
-        password: "ibis".to_string(),
+    // edit the article from alpha, so that beta has content from alpha user
+    let edit_params = EditArticleParams {
+        article_id: get_res.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: get_res.latest_version,
+        resolve_conflict_id: None,
     };
-    alpha.login(params).await.unwrap();
-    let lock_res = alpha.protect_article(&lock_params).await.unwrap();
-    assert!(lock_res.protected);
-
-    let resolve_res: DbArticleView = gamma
-        .resolve_article(create_res.article.ap_id.inner().clone())
+    alpha
+        .edit_article_without_conflict(&edit_params)
         .await
         .unwrap();
-    let edit_params = EditArticleParams {
-        article_id: resolve_res.article.id,
-        new_text: "test".to_string(),
-        summary: "test".to_string(),
-        previous_version_id: resolve_res.latest_version,
-        resolve_conflict_id: None,
+
+    // profile update from beta is sent to alpha as follower
+    let params = UpdateUserParams {
+        display_name: Some("Beta User".to_string()),
+        bio: Some("about beta".to_string()),
     };
-    let edit_res = gamma.edit_article_without_conflict(&edit_params).await;
-    assert!(edit_res.is_none());
+    beta.update_user_profile(params).await.unwrap();
+    let params = GetUserParams {
+        name: "beta".to_string(),
+        domain: Some(beta_id_on_alpha.domain),
+    };
+    let beta_user = alpha.get_user(params).await.unwrap();
+    assert_eq!(Some("Beta User".to_string()), beta_user.display_name);
+    assert_eq!(Some("about beta".to_string()), beta_user.bio);
+
+    // profile update from alpha is sent to beta, which hosts an article edited by alpha
+    let params = UpdateUserParams {
+        display_name: Some("Alpha User".to_string()),
+        bio: Some("about alpha".to_string()),
+    };
+    alpha.update_user_profile(params).await.unwrap();
+    let alpha_person = alpha.site().await.unwrap().my_profile.unwrap().person;
+    let params = GetUserParams {
+        name: "alpha".to_string(),
+        domain: Some(extract_domain(&alpha_person.ap_id)),
+    };
+    let alpha_user = beta.get_user(params).await.unwrap();
+    assert_eq!(Some("Alpha User".to_string()), alpha_user.display_name);
+    assert_eq!(Some("about alpha".to_string()), alpha_user.bio);
 
     TestData::stop(alpha, beta, gamma)
 }
 
 #[tokio::test]
-async fn test_synchronize_instances() -> Result<()> {
+async fn test_federate_instance_update() -> Result<()> {
     let TestData(alpha, beta, gamma) = TestData::start(false).await;
 
-    // fetch alpha instance on beta
-    beta.resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
+    let beta_id_on_alpha = alpha
+        .follow_instance_with_resolve(&beta.hostname)
         .await
         .unwrap();
-    let beta_instances = beta.list_instances().await.unwrap();
-    assert_eq!(2, beta_instances.len());
 
-    // fetch beta instance on gamma
-    gamma
-        .resolve_instance(Url::parse(&format!("http://{}", &beta.hostname))?)
+    // admin of beta changes instance name and topic
+    let admin = ApiClient::new(Some(beta.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let instance_params = UpdateInstanceParams {
+        name: Some("Beta".to_string()),
+        topic: Some("All about beta".to_string()),
+    };
+    admin.update_local_instance(&instance_params).await.unwrap();
+
+    // update is sent to alpha as follower
+    let instances = alpha
+        .list_instances(&ListInstancesParams::default())
         .await
         .unwrap();
-
-    // wait until instance collection is fetched
-    let gamma_instances = RetryFuture::new(
-        || async {
-            let res = gamma.list_instances().await;
-            match res {
-                Err(_) => Err(RetryPolicy::<String>::Retry(None)),
-                Ok(i) if i.len() < 3 => Err(RetryPolicy::Retry(None)),
-                Ok(i) => Ok(i),
-            }
-        },
-        LinearRetryStrategy::new(),
-    )
-    .await?;
-
-    // now gamma also knows about alpha
-    assert_eq!(3, gamma_instances.len());
-    assert!(gamma_instances
-        .iter()
-        .any(|i| i.instance.domain == alpha.hostname));
+    let beta_instance = instances
+        .into_iter()
+        .find(|i| i.instance.id == beta_id_on_alpha.id)
+        .unwrap()
+        .instance;
+    assert_eq!(Some("Beta".to_string()), beta_instance.name);
+    assert_eq!(Some("All about beta".to_string()), beta_instance.topic);
 
     TestData::stop(alpha, beta, gamma)
 }
 
 #[tokio::test]
-async fn test_article_approval_required() -> Result<()> {
-    let TestData(alpha, beta, gamma) = TestData::start(true).await;
+async fn test_federation_blocklist() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
 
-    // create article
-    let create_params = CreateArticleParams {
+    // create article on beta and fetch it from alpha
+    let params = CreateArticleParams {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
     };
-    let create_res = alpha.create_article(&create_params).await.unwrap();
-    assert!(!create_res.article.approved);
-
-    let list_all = alpha.list_articles(Default::default()).await.unwrap();
-    assert_eq!(1, list_all.len());
-    assert!(list_all.iter().all(|a| a.id != create_res.article.id));
+    let beta_article = beta.create_article(&params).await.unwrap();
+    let ap_id = beta_article.article.ap_id.inner().clone();
+    alpha.resolve_article(ap_id.clone()).await.unwrap();
+
+    // normal user cant change the list
+    let params = AddFederationListParams {
+        domain: beta.hostname.clone(),
+        blocked: true,
+        purge: true,
+    };
+    assert!(alpha.add_to_federation_list(&params).await.is_err());
 
-    // login as admin to handle approvals
-    let params = LoginUserParams {
+    // admin blocks beta and purges its content
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
         username: "ibis".to_string(),
         password: "ibis".to_string(),
+        ..Default::default()
     };
-    alpha.login(params).await.unwrap();
-
-    assert_eq!(1, alpha.notifications_count().await.unwrap());
-    let notifications = alpha.notifications_list().await.unwrap();
-    assert_eq!(1, notifications.len());
-    let Notification::ArticleApprovalRequired(notif) = &notifications[0] else {
-        panic!()
-    };
-    assert_eq!(create_res.article.id, notif.id);
+    admin.login(login_params).await.unwrap();
+    for domain in ["%", "localhost/u", "user@localhost", ""] {
+        let params = AddFederationListParams {
+            domain: domain.to_string(),
+            ..params.clone()
+        };
+        assert!(admin.add_to_federation_list(&params).await.is_err());
+    }
+    admin.add_to_federation_list(&params).await.unwrap();
+    let list = admin.federation_list().await.unwrap();
+    assert_eq!(1, list.len());
+    assert_eq!(beta.hostname, list[0].domain);
+    assert!(list[0].blocked);
 
-    alpha.approve_article(notif.id, true).await.unwrap();
-    let params = GetArticleParams {
-        id: Some(create_res.article.id),
+    let get_params = GetArticleParams {
+        title: Some(beta_article.article.title.clone()),
+        domain: Some(beta.hostname.clone()),
         ..Default::default()
     };
-    let approved = alpha.get_article(params).await.unwrap();
-    assert_eq!(create_res.article.id, approved.article.id);
-    assert!(approved.article.approved);
+    assert!(alpha.get_article(get_params.clone()).await.is_err());
+    let instances = alpha
+        .list_instances(&ListInstancesParams::default())
+        .await
+        .unwrap();
+    assert!(!instances.iter().any(|i| i.instance.domain == beta.hostname));
 
-    let list_all = alpha.list_articles(Default::default()).await.unwrap();
-    assert_eq!(2, list_all.len());
-    assert!(list_all.iter().any(|a| a.id == create_res.article.id));
+    // federation with beta is rejected without restart
+    assert!(alpha.resolve_article(ap_id.clone()).await.is_err());
+
+    // after removing beta from the blocklist, federation works again
+    admin
+        .remove_from_federation_list(beta.hostname.clone())
+        .await
+        .unwrap();
+    assert!(admin.federation_list().await.unwrap().is_empty());
+    alpha.resolve_article(ap_id).await.unwrap();
+    alpha.get_article(get_params).await.unwrap();
 
     TestData::stop(alpha, beta, gamma)
 }
 
 #[tokio::test]
-async fn test_comment_create_edit() -> Result<()> {
-    let TestData(alpha, beta, gamma) = TestData::start(true).await;
+async fn test_user_avatar() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
 
     beta.follow_instance_with_resolve(&alpha.hostname)
         .await
         .unwrap();
-    gamma
-        .follow_instance_with_resolve(&alpha.hostname)
+
+    // invalid image is rejected
+    let res = alpha.upload_avatar(b"not an image".to_vec()).await;
+    assert!(res.is_err());
+
+    let mut png = vec![];
+    RgbImage::new(400, 300)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    let person = alpha.upload_avatar(png.clone()).await.unwrap();
+    let avatar = person.avatar.unwrap();
+    assert!(avatar.starts_with(&format!("http://{}/media/", alpha.hostname)));
+    let person = alpha.upload_banner(png).await.unwrap();
+    assert!(person.banner.is_some());
+
+    // avatar is resized
+    let data = reqwest::get(&avatar).await?.bytes().await?;
+    let avatar_image = image::load_from_memory(&data)?;
+    assert_eq!((256, 256), (avatar_image.width(), avatar_image.height()));
+
+    // beta follows alpha, so it receives the profile update and stores a copy of the avatar
+    let params = GetUserParams {
+        name: "alpha".to_string(),
+        domain: Some(extract_domain(&person.ap_id)),
+    };
+    let alpha_user = beta.get_user(params).await.unwrap();
+    let beta_avatar = alpha_user.avatar.unwrap();
+    assert!(beta_avatar.starts_with(&format!("http://{}/media/", beta.hostname)));
+    assert!(alpha_user.banner.is_some());
+    let res = reqwest::get(&beta_avatar).await?;
+    assert!(res.status().is_success());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_article_image() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
         .await
         .unwrap();
 
-    // create article
+    let mut png = vec![];
+    RgbImage::new(1200, 900)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    let upload = alpha.upload_image(png).await.unwrap();
+    let filename = upload.path.strip_prefix("/media/").unwrap();
+
+    // thumbnail is generated
+    let thumbnail_url = format!("http://{}/media/thumbnail/{filename}", alpha.hostname);
+    let data = reqwest::get(thumbnail_url).await?.bytes().await?;
+    let thumbnail = image::load_from_memory(&data)?;
+    assert_eq!((800, 600), (thumbnail.width(), thumbnail.height()));
+
+    // create article with image, which is federated to beta together with the image
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: format!("![Ibis]({})\n", upload.path),
+        summary: "create article".to_string(),
+    };
+    alpha.create_article(&create_params).await.unwrap();
+    let get_params = GetArticleParams {
+        title: Some(create_params.title.clone()),
+        domain: Some(alpha.hostname.clone()),
+        id: None,
+    };
+    let get_res = beta.get_article(get_params).await.unwrap();
+    assert_eq!(create_params.text, get_res.article.text);
+    // images are downloaded in the background, wait until the thumbnail is available
+    let thumbnail_url = format!("http://{}/media/thumbnail/{filename}", beta.hostname);
+    for _ in 0..20 {
+        if reqwest::get(&thumbnail_url).await?.status().is_success() {
+            break;
+        }
+        sleep(Duration::from_millis(500)).await;
+    }
+    let res = reqwest::get(format!("http://{}{}", beta.hostname, upload.path)).await?;
+    assert!(res.status().is_success());
+    let res = reqwest::get(thumbnail_url).await?;
+    assert!(res.status().is_success());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_delete_account() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article on alpha, and comment on it from beta
     let params = CreateArticleParams {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
     };
     let alpha_article = alpha.create_article(&params).await.unwrap();
-
-    // fetch article on beta and create comment
     let beta_article = beta
         .resolve_article(alpha_article.article.ap_id.inner().clone())
         .await
         .unwrap();
     let params = CreateCommentParams {
-        content: "top comment".to_string(),
+        content: "my comment".to_string(),
         article_id: beta_article.article.id,
         parent_id: None,
     };
-    let top_comment = beta.create_comment(&params).await.unwrap().comment;
-    assert_eq!(top_comment.content, params.content);
-    assert_eq!(top_comment.article_id, beta_article.article.id);
-    assert_eq!(top_comment.depth, 0);
-    assert!(top_comment.parent_id.is_none());
-    assert!(top_comment.local);
-    assert!(!top_comment.deleted);
-    assert!(top_comment.updated.is_none());
-    sleep(Duration::from_secs(1)).await;
+    beta.create_comment(&params).await.unwrap();
+    let beta_person = beta.site().await.unwrap().my_profile.unwrap().person;
 
-    // now create child comment on alpha
-    let get_params = GetArticleParams {
-        title: Some(alpha_article.article.title),
-        domain: Some(alpha.hostname.clone()),
+    // wrong password is rejected
+    let params = DeleteAccountParams {
+        password: "wrong".to_string(),
+    };
+    assert!(beta.delete_account(params).await.is_err());
+
+    let params = DeleteAccountParams {
+        password: "hunter2".to_string(),
+    };
+    beta.delete_account(params).await.unwrap();
+    assert!(beta.site().await.unwrap().my_profile.is_none());
+    let params = LoginUserParams {
+        username: "beta".to_string(),
+        password: "hunter2".to_string(),
         ..Default::default()
     };
-    let article = alpha.get_article(get_params.clone()).await.unwrap();
-    assert_eq!(1, article.comments.len());
-    let params = CreateCommentParams {
-        content: "child comment".to_string(),
-        article_id: article.article.id,
-        parent_id: Some(article.comments[0].comment.id),
+    assert!(beta.login(params).await.is_err());
+
+    // comment is kept on both instances, but anonymised
+    for (instance, article_id) in [
+        (&beta, beta_article.article.id),
+        (&alpha, alpha_article.article.id),
+    ] {
+        let get_params = GetArticleParams {
+            id: Some(article_id),
+            ..Default::default()
+        };
+        let article = instance.get_article(get_params).await.unwrap();
+        assert_eq!(1, article.comments.len());
+        assert_eq!("ghost", article.comments[0].creator.username);
+        assert_eq!("", article.comments[0].comment.content);
+        assert!(article.comments[0].comment.deleted);
+    }
+
+    // user is also deleted on alpha
+    let params = GetUserParams {
+        name: "beta".to_string(),
+        domain: Some(extract_domain(&beta_person.ap_id)),
     };
-    let child_comment = alpha.create_comment(&params).await.unwrap().comment;
-    assert_eq!(child_comment.parent_id, Some(top_comment.id));
-    assert_eq!(child_comment.depth, 1);
+    assert!(alpha.get_user(params).await.is_err());
 
-    // edit comment text
-    let edit_params = EditCommentParams {
-        id: child_comment.id,
-        content: Some("edited comment".to_string()),
-        deleted: None,
+    // admin can delete other users, but not admin accounts
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
     };
-    let edited_comment = alpha.edit_comment(&edit_params).await.unwrap().comment;
-    assert_eq!(edited_comment.article_id, article.article.id);
-    assert_eq!(Some(&edited_comment.content), edit_params.content.as_ref());
+    let admin_user = admin.login(login_params).await.unwrap();
+    let params = AdminDeleteUserParams {
+        person_id: admin_user.person.id,
+    };
+    assert!(admin.admin_delete_user(params).await.is_err());
+    let alpha_person = alpha.site().await.unwrap().my_profile.unwrap().person;
+    let params = AdminDeleteUserParams {
+        person_id: alpha_person.id,
+    };
+    admin.admin_delete_user(params).await.unwrap();
+    assert!(alpha.site().await.unwrap().my_profile.is_none());
+    let mod_log = alpha.mod_log(&ListModLogParams::default()).await.unwrap();
+    assert_eq!(1, mod_log.len());
+    assert_eq!(ModAction::DeleteUser, mod_log[0].mod_log.action);
+    assert_eq!(
+        Some(admin_user.person.id),
+        mod_log[0].moderator.as_ref().map(|m| m.id)
+    );
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_ban_user() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article on alpha, and comment on it from beta so that the user is known on alpha
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let alpha_article = alpha.create_article(&params).await.unwrap();
+    let beta_article = beta
+        .resolve_article(alpha_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let params = CreateCommentParams {
+        content: "my comment".to_string(),
+        article_id: beta_article.article.id,
+        parent_id: None,
+    };
+    beta.create_comment(&params).await.unwrap();
+
+    // normal user cant ban
+    let alpha_person = alpha.site().await.unwrap().my_profile.unwrap().person;
+    let params = BanUserParams {
+        person_id: alpha_person.id,
+        ban: true,
+        reason: Some("spam".to_string()),
+        expires: None,
+    };
+    assert!(alpha.ban_user(&params).await.is_err());
+
+    // admin bans local user
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let banned = admin.ban_user(&params).await.unwrap();
+    assert!(banned.is_banned());
+
+    // banned user cant edit or comment
+    let edit_params = EditArticleParams {
+        article_id: alpha_article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: alpha_article.latest_version.clone(),
+        resolve_conflict_id: None,
+    };
+    assert!(alpha.edit_article(&edit_params).await.is_err());
+    let comment_params = CreateCommentParams {
+        content: "banned comment".to_string(),
+        article_id: alpha_article.article.id,
+        parent_id: None,
+    };
+    assert!(alpha.create_comment(&comment_params).await.is_err());
+
+    // banned user also cant move, fork or upload images
+    let move_params = MoveArticleParams {
+        article_id: alpha_article.article.id,
+        new_title: "Manu_Chao_Moved".to_string(),
+    };
+    assert!(alpha.move_article(&move_params).await.is_err());
+    let fork_params = ForkArticleParams {
+        article_id: alpha_article.article.id,
+        new_title: "Manu_Chao_Fork".to_string(),
+    };
+    assert!(alpha.fork_article(&fork_params).await.is_err());
+    let mut png = vec![];
+    RgbImage::new(100, 100)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    assert!(alpha.upload_image(png).await.is_err());
+
+    // but can still read
+    alpha
+        .get_article(GetArticleParams {
+            id: Some(alpha_article.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // after unban it works again
+    let params = BanUserParams {
+        ban: false,
+        ..params
+    };
+    let unbanned = admin.ban_user(&params).await.unwrap();
+    assert!(!unbanned.is_banned());
+    alpha.edit_article(&edit_params).await.unwrap();
+
+    // admin bans remote user temporarily
+    let beta_person = beta.site().await.unwrap().my_profile.unwrap().person;
+    let params = GetUserParams {
+        name: "beta".to_string(),
+        domain: Some(extract_domain(&beta_person.ap_id)),
+    };
+    let beta_person_on_alpha = alpha.get_user(params).await.unwrap();
+    let expires = Utc::now() + TimeDelta::days(1);
+    let params = BanUserParams {
+        person_id: beta_person_on_alpha.id,
+        ban: true,
+        reason: None,
+        expires: Some(expires),
+    };
+    admin.ban_user(&params).await.unwrap();
+
+    // activities from banned user are rejected by alpha. The result on beta is ignored, as it
+    // only stores the comment and edit locally.
+    let params = CreateCommentParams {
+        content: "another comment".to_string(),
+        article_id: beta_article.article.id,
+        parent_id: None,
+    };
+    let _ = beta.create_comment(&params).await;
+    let get_params = GetArticleParams {
+        id: Some(beta_article.article.id),
+        ..Default::default()
+    };
+    let beta_article = beta.get_article(get_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: beta_article.article.id,
+        new_text: "Lorem Ipsum 3\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: beta_article.latest_version,
+        resolve_conflict_id: None,
+    };
+    let _ = beta.edit_article(&edit_params).await;
+    let get_params = GetArticleParams {
+        id: Some(alpha_article.article.id),
+        ..Default::default()
+    };
+    let alpha_article = alpha.get_article(get_params).await.unwrap();
+    assert_eq!(1, alpha_article.comments.len());
+    assert_eq!("Lorem Ipsum 2\n", alpha_article.article.text);
+
+    // all actions are listed in mod log
+    let mod_log = alpha.mod_log(&ListModLogParams::default()).await.unwrap();
+    assert_eq!(3, mod_log.len());
+    assert_eq!(ModAction::BanUser, mod_log[0].mod_log.action);
+    assert_eq!(
+        Some("ibis"),
+        mod_log[0].moderator.as_ref().map(|m| m.username.as_str())
+    );
+    assert_eq!(
+        Some(beta_person_on_alpha.id),
+        mod_log[0].target_person.as_ref().map(|p| p.id)
+    );
+    assert!(mod_log[0].mod_log.expires.is_some());
+    assert_eq!(ModAction::UnbanUser, mod_log[1].mod_log.action);
+    assert_eq!(ModAction::BanUser, mod_log[2].mod_log.action);
+    assert_eq!(Some("spam".to_string()), mod_log[2].mod_log.reason);
+    assert_eq!(
+        Some(alpha_person.id),
+        mod_log[2].target_person.as_ref().map(|p| p.id)
+    );
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_report() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article and comment on alpha
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let alpha_article = alpha.create_article(&params).await.unwrap();
+    let beta_article = beta
+        .resolve_article(alpha_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let params = CreateCommentParams {
+        content: "spam comment".to_string(),
+        article_id: alpha_article.article.id,
+        parent_id: None,
+    };
+    let alpha_comment = alpha.create_comment(&params).await.unwrap().comment;
+
+    // report comment from beta
+    let get_params = GetArticleParams {
+        id: Some(beta_article.article.id),
+        ..Default::default()
+    };
+    let beta_comments = beta.get_article(get_params).await.unwrap().comments;
+    assert_eq!(1, beta_comments.len());
+    let params = CreateReportParams {
+        article_id: beta_article.article.id,
+        edit_id: None,
+        comment_id: Some(beta_comments[0].comment.id),
+        reason: "this is spam".to_string(),
+    };
+    let report = beta.create_report(&params).await.unwrap();
+    assert!(report.local);
+    assert!(!report.resolved);
+
+    // report for a comment of another article is rejected
+    let params = CreateArticleParams {
+        title: "Another_Article".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let beta_article2 = beta.create_article(&params).await.unwrap();
+    let params = CreateReportParams {
+        article_id: beta_article2.article.id,
+        edit_id: None,
+        comment_id: Some(beta_comments[0].comment.id),
+        reason: "this is spam".to_string(),
+    };
+    assert!(beta.create_report(&params).await.is_err());
+
+    // report is federated to alpha and shown to admin there
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let notifications = admin.notifications_list(&Default::default()).await.unwrap();
+    assert_eq!(1, notifications.len());
+    let Notification::Report(report_view) = &notifications[0].data else {
+        panic!()
+    };
+    assert_eq!("this is spam", report_view.report.reason);
+    assert!(!report_view.report.local);
+    assert_eq!(
+        Some(alpha_comment.id),
+        report_view.comment.as_ref().map(|c| c.id)
+    );
+    assert_eq!(
+        Some("alpha"),
+        report_view.creator.as_ref().map(|c| c.username.as_str())
+    );
+
+    // normal user cant see or resolve the report
+    assert!(alpha
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(alpha.resolve_report(report_view.report.id).await.is_err());
+
+    // admin resolves report, which removes the notification and writes to mod log
+    let resolved = admin.resolve_report(report_view.report.id).await.unwrap();
+    assert!(resolved.resolved);
+    assert!(admin.resolve_report(resolved.id).await.is_err());
+    assert!(admin
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .is_empty());
+    let mod_log = alpha.mod_log(&ListModLogParams::default()).await.unwrap();
+    assert_eq!(ModAction::ResolveReport, mod_log[0].mod_log.action);
+    assert_eq!(
+        Some(alpha_article.article.id),
+        mod_log[0].target_article.as_ref().map(|a| a.id)
+    );
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_email_verification_password_reset() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+    let email = "alpha@example.com".to_string();
+
+    // set email, which sends a verification mail
+    let params = UpdateEmailParams {
+        email: Some(email.clone()),
+        email_notifications: true,
+    };
+    alpha.update_email(params).await.unwrap();
+    assert_eq!(1, alpha.emails.lock().unwrap().len());
+    let my_profile = alpha.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(Some(&email), my_profile.local_user.email.as_ref());
+    assert!(!my_profile.local_user.email_verified);
+
+    // password reset is not possible with unverified email
+    let params = RequestPasswordResetParams {
+        email: email.clone(),
+    };
+    alpha.request_password_reset(params).await.unwrap();
+    assert_eq!(1, alpha.emails.lock().unwrap().len());
+
+    // verify email
+    let params = VerifyEmailParams {
+        token: alpha.last_email_token(),
+    };
+    alpha.verify_email(params.clone()).await.unwrap();
+    let my_profile = alpha.site().await.unwrap().my_profile.unwrap();
+    assert!(my_profile.local_user.email_verified);
+    assert!(alpha.verify_email(params).await.is_err());
+
+    // cant use an email address which belongs to another user
+    let params = UpdateEmailParams {
+        email: Some(email.clone()),
+        email_notifications: false,
+    };
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let err = admin.update_email(params).await.unwrap_err();
+    assert_eq!("Email address can't be used", err.message());
+
+    // request password reset
+    alpha.logout().await.unwrap();
+    let params = RequestPasswordResetParams {
+        email: email.clone(),
+    };
+    alpha.request_password_reset(params).await.unwrap();
+    // email is sent in background
+    RetryFuture::new(
+        || async {
+            match alpha.emails.lock().unwrap().len() {
+                2 => Ok(()),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+
+    // set new password, token can only be used once
+    let params = ResetPasswordParams {
+        token: alpha.last_email_token(),
+        new_password: "correct horse".to_string(),
+    };
+    alpha.reset_password(params.clone()).await.unwrap();
+    assert!(alpha.reset_password(params).await.is_err());
+
+    // login only works with new password
+    let params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+    assert!(alpha.login(params).await.is_err());
+    let params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "correct horse".to_string(),
+        ..Default::default()
+    };
+    alpha.login(params).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_change_password() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // login from a second client
+    let alpha_other = ApiClient::new(Some(alpha.hostname.clone()));
+    let params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+    alpha_other.login(params).await.unwrap();
+    assert!(alpha_other.site().await.unwrap().my_profile.is_some());
+
+    // change password fails with wrong old password
+    let params = ChangePasswordParams {
+        old_password: "hunter3".to_string(),
+        new_password: "correct horse".to_string(),
+    };
+    assert!(alpha.change_password(params).await.is_err());
+
+    let params = ChangePasswordParams {
+        old_password: "hunter2".to_string(),
+        new_password: "correct horse".to_string(),
+    };
+    alpha.change_password(params).await.unwrap();
+
+    // current session stays logged in, the other one is logged out
+    assert!(alpha.site().await.unwrap().my_profile.is_some());
+    assert!(alpha_other.site().await.unwrap().my_profile.is_none());
+
+    // login only works with new password
+    let params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+    assert!(alpha_other.login(params).await.is_err());
+    let params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "correct horse".to_string(),
+        ..Default::default()
+    };
+    alpha_other.login(params).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_admin_reset_password() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+    let person_id = alpha.site().await.unwrap().my_profile.unwrap().person.id;
+
+    // normal user cant reset password
+    let params = AdminResetPasswordParams { person_id };
+    assert!(alpha.admin_reset_password(params.clone()).await.is_err());
+
+    // admin resets password, which logs out the user
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let res = admin.admin_reset_password(params).await.unwrap();
+    assert!(alpha.site().await.unwrap().my_profile.is_none());
+    let login_params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+    assert!(alpha.login(login_params).await.is_err());
+
+    // user sets new password with the link
+    let token = res.reset_link.split("token=").nth(1).unwrap().to_string();
+    let params = ResetPasswordParams {
+        token,
+        new_password: "correct horse".to_string(),
+    };
+    alpha.reset_password(params).await.unwrap();
+    let login_params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "correct horse".to_string(),
+        ..Default::default()
+    };
+    alpha.login(login_params).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_api_permissions() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+    const LOGIN_REQUIRED: &str = "Login required";
+    const ADMIN_REQUIRED: &str = "Only admin can perform this action";
+    fn assert_rejected<T>(res: FrontendResult<T>, message: &str) {
+        assert_eq!(message, res.err().unwrap().message());
+    }
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    let article = create_res.article;
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+
+    // endpoints which require login
+    assert_rejected(
+        anonymous.create_article(&create_params).await,
+        LOGIN_REQUIRED,
+    );
+    let edit_params = EditArticleParams {
+        article_id: article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+    };
+    assert_rejected(anonymous.edit_article(&edit_params).await, LOGIN_REQUIRED);
+    let fork_params = ForkArticleParams {
+        article_id: article.id,
+        new_title: "Manu_Chao_Fork".to_string(),
+    };
+    assert_rejected(anonymous.fork_article(&fork_params).await, LOGIN_REQUIRED);
+    let move_params = MoveArticleParams {
+        article_id: article.id,
+        new_title: "Manu_Chao_Moved".to_string(),
+    };
+    assert_rejected(anonymous.move_article(&move_params).await, LOGIN_REQUIRED);
+    let revert_params = RevertArticleParams {
+        article_id: article.id,
+        version: create_res.latest_version.clone(),
+    };
+    assert_rejected(
+        anonymous.revert_article(&revert_params).await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(
+        anonymous.watch_article(article.id, true).await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(anonymous.get_conflict(ConflictId(1)).await, LOGIN_REQUIRED);
+    assert_rejected(
+        anonymous.delete_conflict(ConflictId(1)).await,
+        LOGIN_REQUIRED,
+    );
+    let comment_params = CreateCommentParams {
+        content: "my comment".to_string(),
+        article_id: article.id,
+        parent_id: None,
+    };
+    assert_rejected(
+        anonymous.create_comment(&comment_params).await,
+        LOGIN_REQUIRED,
+    );
+    let edit_comment_params = EditCommentParams {
+        id: CommentId(1),
+        content: Some("edited comment".to_string()),
+        deleted: None,
+    };
+    assert_rejected(
+        anonymous.edit_comment(&edit_comment_params).await,
+        LOGIN_REQUIRED,
+    );
+    let follow_params = FollowInstanceParams { id: InstanceId(1) };
+    assert_rejected(
+        anonymous.follow_instance(follow_params).await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(
+        anonymous
+            .notifications_list(&ListNotificationsParams::default())
+            .await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(
+        anonymous.mark_notification_as_read(NotificationId(1)).await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(
+        anonymous.mark_all_notifications_as_read().await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(
+        anonymous.delete_notification(NotificationId(1)).await,
+        LOGIN_REQUIRED,
+    );
+    let profile_params = UpdateUserParams {
+        display_name: Some("hacked".to_string()),
+        bio: Some("hacked".to_string()),
+    };
+    assert_rejected(
+        anonymous.update_user_profile(profile_params).await,
+        LOGIN_REQUIRED,
+    );
+    let email_params = UpdateEmailParams {
+        email: Some("hacked@example.com".to_string()),
+        email_notifications: false,
+    };
+    assert_rejected(anonymous.update_email(email_params).await, LOGIN_REQUIRED);
+    let password_params = ChangePasswordParams {
+        old_password: "hunter2".to_string(),
+        new_password: "hacked".to_string(),
+    };
+    assert_rejected(
+        anonymous.change_password(password_params).await,
+        LOGIN_REQUIRED,
+    );
+    let report_params = CreateReportParams {
+        article_id: article.id,
+        edit_id: None,
+        comment_id: None,
+        reason: "spam".to_string(),
+    };
+    assert_rejected(
+        anonymous.create_report(&report_params).await,
+        LOGIN_REQUIRED,
+    );
+    assert_rejected(anonymous.list_api_tokens().await, LOGIN_REQUIRED);
+    assert_rejected(anonymous.generate_totp().await, LOGIN_REQUIRED);
+
+    // endpoints which require admin, rejected for anonymous and normal users
+    let person_id = alpha.site().await.unwrap().my_profile.unwrap().person.id;
+    for (client, message) in [
+        (&anonymous, LOGIN_REQUIRED),
+        (&alpha.api_client, ADMIN_REQUIRED),
+    ] {
+        let protect_params = ProtectArticleParams {
+            article_id: article.id,
+            protected: true,
+        };
+        assert_rejected(client.protect_article(&protect_params).await, message);
+        assert_rejected(client.approve_article(article.id, true).await, message);
+        let delete_params = DeleteArticleParams {
+            article_id: article.id,
+            deleted: true,
+        };
+        assert_rejected(client.delete_article(&delete_params).await, message);
+        let instance_params = UpdateInstanceParams {
+            name: Some("hacked".to_string()),
+            topic: Some("hacked".to_string()),
+        };
+        assert_rejected(
+            client.update_local_instance(&instance_params).await,
+            message,
+        );
+        let reset_params = AdminResetPasswordParams { person_id };
+        assert_rejected(client.admin_reset_password(reset_params).await, message);
+        let ban_params = BanUserParams {
+            person_id,
+            ban: true,
+            reason: None,
+            expires: None,
+        };
+        assert_rejected(client.ban_user(&ban_params).await, message);
+        assert_rejected(client.rotate_jwt_secret().await, message);
+        assert_rejected(client.resolve_report(ReportId(1)).await, message);
+        assert_rejected(client.dismiss_report(ReportId(1)).await, message);
+        let application_id = RegistrationApplicationId(1);
+        assert_rejected(
+            client
+                .approve_registration(application_id, true, None)
+                .await,
+            message,
+        );
+    }
+
+    // nothing was changed
+    let site = alpha.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(None, site.person.display_name);
+    assert_eq!(None, site.local_user.email);
+    let instance = alpha.get_local_instance().await.unwrap().instance;
+    assert_eq!(None, instance.name);
+    let get_params = GetArticleParams {
+        title: Some(article.title.clone()),
+        ..Default::default()
+    };
+    let get_res = alpha.get_article(get_params).await.unwrap();
+    assert!(!get_res.article.protected);
+    assert!(!get_res.article.deleted);
+
+    // admin can use the endpoints
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(login_params).await.unwrap();
+    let instance_params = UpdateInstanceParams {
+        name: Some("Alpha".to_string()),
+        topic: None,
+    };
+    let instance = admin.update_local_instance(&instance_params).await.unwrap();
+    assert_eq!(Some("Alpha".to_string()), instance.name);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_api_token() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let article = alpha.create_article(&create_params).await.unwrap();
+
+    // normal user cant create token with admin scope
+    let mut params = CreateApiTokenParams {
+        name: "my bot".to_string(),
+        scope_read: true,
+        scope_comment: true,
+        scope_admin: true,
+        ..Default::default()
+    };
+    assert!(alpha.create_api_token(&params).await.is_err());
+    params.scope_admin = false;
+    let res = alpha.create_api_token(&params).await.unwrap();
+    assert!(res.token.starts_with("ibis_"));
+    let tokens = alpha.list_api_tokens().await.unwrap();
+    assert_eq!(1, tokens.len());
+    assert_eq!("my bot", tokens[0].name);
+    assert!(tokens[0].last_used.is_none());
+
+    // bot is authenticated as alpha, and can use the allowed scopes
+    let bot = ApiClient::with_api_token(Some(alpha.hostname.clone()), &res.token);
+    let my_profile = bot.site().await.unwrap().my_profile.unwrap();
+    assert_eq!("alpha", my_profile.person.username);
+    bot.notifications_list(&ListNotificationsParams::default())
+        .await
+        .unwrap();
+    let comment_params = CreateCommentParams {
+        content: "comment from bot".to_string(),
+        article_id: article.article.id,
+        parent_id: None,
+    };
+    bot.create_comment(&comment_params).await.unwrap();
+    let tokens = alpha.list_api_tokens().await.unwrap();
+    assert!(tokens[0].last_used.is_some());
+
+    // other scopes and account settings are rejected
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+    };
+    let err = bot.edit_article(&edit_params).await.err().unwrap();
+    assert!(err.message().contains("missing required scope"));
+    let err = bot.create_api_token(&params).await.err().unwrap();
+    assert!(err.message().contains("missing required scope"));
+
+    // revoked token cant be used anymore
+    alpha.delete_api_token(tokens[0].id).await.unwrap();
+    assert!(alpha.list_api_tokens().await.unwrap().is_empty());
+    assert!(bot.site().await.unwrap().my_profile.is_none());
+
+    // token without read scope is handled as anonymous for public read requests, and rejected
+    // for read requests which need login
+    let params = CreateApiTokenParams {
+        name: "comment bot".to_string(),
+        scope_comment: true,
+        ..Default::default()
+    };
+    let res = alpha.create_api_token(&params).await.unwrap();
+    let bot = ApiClient::with_api_token(Some(alpha.hostname.clone()), &res.token);
+    assert!(bot.site().await.unwrap().my_profile.is_none());
+    let get_params = GetArticleParams {
+        id: Some(article.article.id),
+        ..Default::default()
+    };
+    bot.get_article(get_params).await.unwrap();
+    let err = bot
+        .notifications_list(&ListNotificationsParams::default())
+        .await
+        .err()
+        .unwrap();
+    assert!(err.message().contains("missing required scope"));
+    bot.create_comment(&comment_params).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_login_sessions() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+    let login_params = || LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+
+    // login from another device
+    let other = ApiClient::new(Some(alpha.hostname.clone()));
+    other.login(login_params()).await.unwrap();
+    let sessions = alpha.list_sessions().await.unwrap();
+    assert_eq!(2, sessions.len());
+    assert_eq!(1, sessions.iter().filter(|s| s.current).count());
+
+    // revoked session is logged out
+    let other_session = sessions.iter().find(|s| !s.current).unwrap();
+    alpha
+        .revoke_session(other_session.session.id)
+        .await
+        .unwrap();
+    assert!(other.site().await.unwrap().my_profile.is_none());
+    assert_eq!(1, alpha.list_sessions().await.unwrap().len());
+
+    // logout removes the session
+    other.login(login_params()).await.unwrap();
+    assert_eq!(2, alpha.list_sessions().await.unwrap().len());
+    other.logout().await.unwrap();
+    assert_eq!(1, alpha.list_sessions().await.unwrap().len());
+
+    // rotating the secret logs out all users
+    let admin = ApiClient::new(Some(alpha.hostname.clone()));
+    let admin_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    admin.login(admin_params).await.unwrap();
+    admin.rotate_jwt_secret().await.unwrap();
+    assert!(alpha.site().await.unwrap().my_profile.is_none());
+    assert!(admin.site().await.unwrap().my_profile.is_none());
+    alpha.login(login_params()).await.unwrap();
+    assert!(alpha.site().await.unwrap().my_profile.is_some());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_totp() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+    let login_params = |totp_token: Option<String>| LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        totp_token,
+    };
+    let other = ApiClient::new(Some(alpha.hostname.clone()));
+
+    // generated secret needs to be confirmed before it is required for login
+    let generated = alpha.generate_totp().await.unwrap();
+    let totp = TOTP::from_url(&generated.totp_url).unwrap();
+    other.login(login_params(None)).await.unwrap();
+    assert!(alpha.enable_totp("invalid".to_string()).await.is_err());
+    let token = totp.generate_current().unwrap();
+    let enabled = alpha.enable_totp(token).await.unwrap();
+    assert_eq!(10, enabled.recovery_codes.len());
+    assert!(alpha.generate_totp().await.is_err());
+
+    // login requires a valid token now
+    let res = other.login(login_params(None)).await;
+    assert!(res.err().unwrap().message().contains(TOTP_REQUIRED));
+    let res = other.login(login_params(Some("invalid".to_string()))).await;
+    assert!(res.is_err());
+    let token = totp.generate_current().unwrap();
+    other
+        .login(login_params(Some(token.clone())))
+        .await
+        .unwrap();
+
+    // the same token can't be used again
+    let res = other.login(login_params(Some(token))).await;
+    assert!(res.is_err());
+
+    // recovery codes can be used instead of a token, but only once
+    let recovery_code = enabled.recovery_codes[0].clone();
+    other
+        .login(login_params(Some(recovery_code.clone())))
+        .await
+        .unwrap();
+    let res = other.login(login_params(Some(recovery_code))).await;
+    assert!(res.is_err());
+
+    // disabling requires the password and a token or recovery code
+    let recovery_code = enabled.recovery_codes[1].clone();
+    assert!(alpha
+        .disable_totp("wrong".to_string(), recovery_code.clone())
+        .await
+        .is_err());
+    assert!(alpha
+        .disable_totp("hunter2".to_string(), "invalid".to_string())
+        .await
+        .is_err());
+    alpha
+        .disable_totp("hunter2".to_string(), recovery_code)
+        .await
+        .unwrap();
+    other.login(login_params(None)).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_oauth_login() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+    let providers = alpha.site().await.unwrap().oauth_providers;
+    assert_eq!("mock", providers[0].name);
+
+    async fn oauth_login(client: &ApiClient, code: &str) -> FrontendResult<LocalUserView> {
+        let authorize = client.oauth_authorize("mock".to_string()).await?;
+        let url = Url::parse(&authorize.url).unwrap();
+        let query: HashMap<_, _> = url.query_pairs().collect();
+        assert_eq!("S256", query["code_challenge_method"]);
+        let state = &query["state"];
+        let params = OAuthLoginParams {
+            code: code.to_string(),
+            state: state.to_string(),
+        };
+        client.oauth_login(params).await
+    }
+
+    // first login creates a new account, the mock provider uses the code as username
+    let carol = ApiClient::new(Some(alpha.hostname.clone()));
+    let user = oauth_login(&carol, "carol").await.unwrap();
+    assert_eq!("carol", user.person.username);
+    assert!(carol.site().await.unwrap().my_profile.is_some());
+
+    // following logins use the same account
+    let other = ApiClient::new(Some(alpha.hostname.clone()));
+    let user2 = oauth_login(&other, "carol").await.unwrap();
+    assert_eq!(user.person.id, user2.person.id);
+
+    // state must match the one from authorize
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+    anonymous.oauth_authorize("mock".to_string()).await.unwrap();
+    let params = OAuthLoginParams {
+        code: "carol".to_string(),
+        state: "invalid".to_string(),
+    };
+    assert!(anonymous.oauth_login(params).await.is_err());
+
+    // existing local user can't be taken over
+    assert!(oauth_login(&anonymous, "alpha").await.is_err());
+
+    // logged in user can link the provider account, and then login with it
+    let linked = oauth_login(&alpha, "alpha_provider").await.unwrap();
+    assert_eq!("alpha", linked.person.username);
+    let user = oauth_login(&anonymous, "alpha_provider").await.unwrap();
+    assert_eq!("alpha", user.person.username);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_rate_limit() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // failed logins count towards the limit. use an unknown user so that the requests don't
+    // wait for password hashing, otherwise the bucket refills in between
+    let anonymous = ApiClient::new(Some(alpha.hostname.clone()));
+    let login_params = || LoginUserParams {
+        username: "unknown".to_string(),
+        password: "wrong".to_string(),
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        let res = anonymous.login(login_params()).await;
+        assert!(!res.err().unwrap().message().contains("Rate limit"));
+    }
+    let res = anonymous.login(login_params()).await;
+    assert!(res.err().unwrap().message().contains("Rate limit exceeded"));
+
+    // other instances use separate limits
+    let login_params = LoginUserParams {
+        username: "beta".to_string(),
+        password: "hunter2".to_string(),
+        ..Default::default()
+    };
+    beta.login(login_params).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_lock_article() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // create article
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    assert!(!create_res.article.protected);
+
+    // lock from normal user fails
+    let lock_params = ProtectArticleParams {
+        article_id: create_res.article.id,
+        protected: true,
+    };
+    let lock_res = alpha.protect_article(&lock_params).await;
+    assert!(lock_res.is_err());
+
+    // login as admin to lock article
+    let params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    alpha.login(params).await.unwrap();
+    let lock_res = alpha.protect_article(&lock_params).await.unwrap();
+    assert!(lock_res.protected);
+
+    let resolve_res: DbArticleView = gamma
+        .resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let edit_params = EditArticleParams {
+        article_id: resolve_res.article.id,
+        new_text: "test".to_string(),
+        summary: "test".to_string(),
+        previous_version_id: resolve_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    let edit_res = gamma.edit_article_without_conflict(&edit_params).await;
+    assert!(edit_res.is_none());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_delete_restore_article() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    assert!(!create_res.article.deleted);
+    let get_params = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        domain: Some(alpha.hostname.clone()),
+        id: None,
+    };
+    let search_params = SearchArticleParams {
+        query: create_params.title.clone(),
+        ..Default::default()
+    };
+
+    // delete from normal user fails
+    let mut delete_params = DeleteArticleParams {
+        article_id: create_res.article.id,
+        deleted: true,
+    };
+    let delete_res = alpha.delete_article(&delete_params).await;
+    assert!(delete_res.is_err());
+
+    // login as admin to delete article
+    let params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    alpha.login(params).await.unwrap();
+    let delete_res = alpha.delete_article(&delete_params).await.unwrap();
+    assert!(delete_res.deleted);
+
+    // article is hidden from listing and search, but can still be read
+    let list_res = alpha
+        .list_articles(ListArticlesParams::default())
+        .await
+        .unwrap();
+    assert!(!list_res.iter().any(|a| a.id == create_res.article.id));
+    assert!(alpha.search(&search_params).await.unwrap().is_empty());
+    let get_res = alpha.get_article(get_params.clone()).await.unwrap();
+    assert!(get_res.article.deleted);
+
+    // deleted article cant be edited
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: get_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    let err = alpha.edit_article(&edit_params).await.unwrap_err();
+    assert!(err.to_string().contains("Article is deleted"));
+
+    // and also not reverted or moved
+    let revert_params = RevertArticleParams {
+        article_id: create_res.article.id,
+        version: create_res.latest_version.clone(),
+    };
+    let err = alpha.revert_article(&revert_params).await.unwrap_err();
+    assert!(err.to_string().contains("Article is deleted"));
+    let move_params = MoveArticleParams {
+        article_id: create_res.article.id,
+        new_title: "Manu_Chao_Moved".to_string(),
+    };
+    let err = alpha.move_article(&move_params).await.unwrap_err();
+    assert!(err.to_string().contains("Article is deleted"));
+
+    // deletion is federated to beta
+    sleep(Duration::from_secs(1)).await;
+    let beta_res = beta.get_article(get_params.clone()).await.unwrap();
+    assert!(beta_res.article.deleted);
+    assert!(beta.search(&search_params).await.unwrap().is_empty());
+
+    // deleted article cant be forked
+    let fork_params = ForkArticleParams {
+        article_id: beta_res.article.id,
+        new_title: "Manu_Chao_Fork".to_string(),
+    };
+    let err = beta.fork_article(&fork_params).await.unwrap_err();
+    assert!(err.to_string().contains("Article is deleted"));
+
+    // federation endpoint returns tombstone for deleted article
+    let res = reqwest::Client::new()
+        .get(create_res.article.ap_id.inner().clone())
+        .header("Accept", "application/activity+json")
+        .send()
+        .await?;
+    assert_eq!(StatusCode::GONE, res.status());
+
+    // restore article
+    delete_params.deleted = false;
+    let restore_res = alpha.delete_article(&delete_params).await.unwrap();
+    assert!(!restore_res.deleted);
+    assert_eq!(1, alpha.search(&search_params).await.unwrap().len());
+
+    // restore is federated to beta
+    sleep(Duration::from_secs(1)).await;
+    let beta_res = beta.get_article(get_params).await.unwrap();
+    assert!(!beta_res.article.deleted);
+    assert_eq!(1, beta.search(&search_params).await.unwrap().len());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_move_article() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article
+    let create_params = CreateArticleParams {
+        title: "Manu_Chau".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+
+    // move to new title
+    let move_params = MoveArticleParams {
+        article_id: create_res.article.id,
+        new_title: "Manu Chao".to_string(),
+    };
+    let move_res = alpha.move_article(&move_params).await.unwrap();
+    assert_eq!("Manu_Chao", move_res.article.title);
+    assert_eq!(create_res.article.ap_id, move_res.article.ap_id);
+    assert_eq!(create_res.latest_version, move_res.latest_version);
+
+    // old title redirects to new one
+    let mut get_params = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        domain: None,
+        id: None,
+    };
+    let get_res = alpha.get_article(get_params.clone()).await.unwrap();
+    assert_eq!(move_res.article, get_res.article);
+
+    // rename is federated, and old title also redirects on beta
+    sleep(Duration::from_secs(1)).await;
+    get_params.domain = Some(alpha.hostname.clone());
+    let beta_res = beta.get_article(get_params.clone()).await.unwrap();
+    assert_eq!(move_res.article.title, beta_res.article.title);
+    get_params.title = Some(move_res.article.title.clone());
+    let beta_res = beta.get_article(get_params).await.unwrap();
+    assert_eq!(move_res.article.ap_id, beta_res.article.ap_id);
+
+    // article can still be fetched by ap_id which contains the old title
+    let gamma_res = gamma
+        .resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    assert_eq!(move_res.article.title, gamma_res.article.title);
+
+    // cant move to existing title
+    let create_params = CreateArticleParams {
+        title: "Bob_Marley".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    alpha.create_article(&create_params).await.unwrap();
+    let move_params = MoveArticleParams {
+        article_id: create_res.article.id,
+        new_title: create_params.title,
+    };
+    assert!(alpha.move_article(&move_params).await.is_err());
+
+    // can move another article to a title which is only a redirect
+    let create_params = CreateArticleParams {
+        title: "Peter_Tosh".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let other_res = alpha.create_article(&create_params).await.unwrap();
+    let move_params = MoveArticleParams {
+        article_id: other_res.article.id,
+        new_title: create_res.article.title.clone(),
+    };
+    let other_move_res = alpha.move_article(&move_params).await.unwrap();
+    let get_params = GetArticleParams {
+        title: Some(create_res.article.title.clone()),
+        domain: None,
+        id: None,
+    };
+    let get_res = alpha.get_article(get_params).await.unwrap();
+    assert_eq!(other_res.article.id, get_res.article.id);
+
+    // now the title is taken, so an article with the same title cant be created
+    let duplicate_params = CreateArticleParams {
+        title: create_res.article.title.clone(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let err = alpha.create_article(&duplicate_params).await.unwrap_err();
+    assert_eq!("Article with this title already exists", err.message());
+
+    // the original title of the moved article can be used again, but gets a different ap_id
+    let new_res = alpha.create_article(&create_params).await.unwrap();
+    assert_eq!(other_res.article.title, new_res.article.title);
+    assert_ne!(other_res.article.ap_id, new_res.article.ap_id);
+
+    // both articles can be fetched by their ap_id
+    let gamma_res = gamma
+        .resolve_article(other_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    assert_eq!(other_move_res.article.title, gamma_res.article.title);
+    let gamma_res = gamma
+        .resolve_article(new_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    assert_eq!(new_res.article.title, gamma_res.article.title);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_revert_article() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // create and edit article
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "vandalism\n".to_string(),
+        summary: "vandalism".to_string(),
+        previous_version_id: create_res.latest_version.clone(),
+        resolve_conflict_id: None,
+    };
+    alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+
+    // revert to first version
+    let revert_params = RevertArticleParams {
+        article_id: create_res.article.id,
+        version: create_res.latest_version.clone(),
+    };
+    let revert_res = alpha.revert_article(&revert_params).await.unwrap();
+    assert_eq!(create_res.article.text, revert_res.article.text);
+    let edits = alpha
+        .get_article_edits(create_res.article.id)
+        .await
+        .unwrap();
+    assert_eq!(3, edits.len());
+    assert!(edits[2].edit.summary.starts_with("Revert to version from"));
+    assert!(edits[2].edit.summary.ends_with(&create_params.summary));
+
+    // reverting again fails because there are no changes
+    assert!(alpha.revert_article(&revert_params).await.is_err());
+
+    // revert remote article from beta
+    let edit_params = EditArticleParams {
+        previous_version_id: revert_res.latest_version,
+        ..edit_params
+    };
+    let edit_res = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    let beta_article = beta
+        .resolve_article(create_res.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    assert_eq!(edit_res.article.text, beta_article.article.text);
+    let revert_params = RevertArticleParams {
+        article_id: beta_article.article.id,
+        version: create_res.latest_version,
+    };
+    beta.revert_article(&revert_params).await.unwrap();
+
+    // revert is applied on origin instance
+    sleep(Duration::from_secs(1)).await;
+    let alpha_article = alpha
+        .get_article(GetArticleParams {
+            id: Some(create_res.article.id),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(create_res.article.text, alpha_article.article.text);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_revert_article_with_snapshots() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: "version 0\n".to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+
+    // enough edits so that snapshots are stored, while staying below the edit rate limit
+    let mut latest_version = create_res.latest_version;
+    let mut versions = vec![];
+    for i in 1..55 {
+        let edit_params = EditArticleParams {
+            article_id: create_res.article.id,
+            new_text: format!("version {i}\n"),
+            summary: format!("edit {i}"),
+            previous_version_id: latest_version,
+            resolve_conflict_id: None,
+        };
+        let edit_res = alpha
+            .edit_article_without_conflict(&edit_params)
+            .await
+            .unwrap();
+        latest_version = edit_res.latest_version;
+        versions.push(latest_version.clone());
+    }
+
+    // versions before and after snapshots can be restored
+    for i in [3, 30, 52] {
+        let revert_params = RevertArticleParams {
+            article_id: create_res.article.id,
+            version: versions[i - 1].clone(),
+        };
+        let revert_res = alpha.revert_article(&revert_params).await.unwrap();
+        assert_eq!(format!("version {i}\n"), revert_res.article.text);
+    }
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_search_article() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create articles on alpha and beta
+    for (title, text) in [
+        ("Manu_Chao", "Manu Chao is a musician from France.\n"),
+        ("Clandestino", "Clandestino is an album by Manu Chao.\n"),
+        (
+            "Mano_Negra",
+            "Mano Negra was a <b>band</b> with musician Manu Chao.\n",
+        ),
+    ] {
+        let params = CreateArticleParams {
+            title: title.to_string(),
+            text: text.to_string(),
+            summary: "create article".to_string(),
+        };
+        alpha.create_article(&params).await.unwrap();
+    }
+    let params = CreateArticleParams {
+        title: "Bob_Marley".to_string(),
+        text: "Bob Marley was a musician from Jamaica.\n".to_string(),
+        summary: "create article".to_string(),
+    };
+    beta.create_article(&params).await.unwrap();
+
+    // title match is ranked highest, text is stemmed
+    let mut search_params = SearchArticleParams {
+        query: "manu chao".to_string(),
+        ..Default::default()
+    };
+    let search_res = alpha.search(&search_params).await.unwrap();
+    assert_eq!(3, search_res.len());
+    assert_eq!("Manu_Chao", search_res[0].title);
+    assert!(search_res[0].snippet.contains("<mark>Manu</mark>"));
+    assert!(search_res.iter().all(|a| !a.snippet.contains("<b>")));
+
+    // paging
+    search_params.limit = Some(2);
+    let first_page = alpha.search(&search_params).await.unwrap();
+    assert_eq!(search_res[..2], first_page[..]);
+    search_params.cursor = first_page.last().map(|a| a.id);
+    let second_page = alpha.search(&search_params).await.unwrap();
+    assert_eq!(search_res[2..], second_page[..]);
+
+    // filter by local and instance
+    let search_params = SearchArticleParams {
+        query: "musicians".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(3, beta.search(&search_params).await.unwrap().len());
+    let search_params = SearchArticleParams {
+        local: Some(true),
+        ..search_params
+    };
+    let search_res = beta.search(&search_params).await.unwrap();
+    assert_eq!(1, search_res.len());
+    assert_eq!("Bob_Marley", search_res[0].title);
+    let alpha_instance = alpha.get_local_instance().await.unwrap().instance;
+    let alpha_instance = beta
+        .resolve_instance(alpha_instance.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let search_params = SearchArticleParams {
+        local: None,
+        instance_id: Some(alpha_instance.id),
+        ..search_params
+    };
+    let search_res = beta.search(&search_params).await.unwrap();
+    assert_eq!(2, search_res.len());
+    assert!(search_res.iter().all(|a| !a.local));
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_article_pagination() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // create enough articles that the federated collection needs more than one page
+    let mut titles = vec![];
+    for i in 0..55 {
+        let params = CreateArticleParams {
+            title: format!("Article_{i:02}"),
+            text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+            summary: "create article".to_string(),
+        };
+        titles.push(alpha.create_article(&params).await.unwrap().article.title);
+    }
+
+    // alphabetical order with cursor
+    let mut params = ListArticlesParams {
+        only_local: Some(true),
+        sort: Some(ArticleSort::Alphabetical),
+        limit: Some(20),
+        ..Default::default()
+    };
+    let mut listed = vec![];
+    loop {
+        let page = alpha.list_articles(params.clone()).await.unwrap();
+        params.cursor = page.last().map(|a| a.id);
+        listed.extend(page.iter().map(|a| a.title.clone()));
+        if page.len() < 20 {
+            break;
+        }
+    }
+    // includes main page
+    assert_eq!(56, listed.len());
+    assert_eq!(titles, listed[..55]);
+
+    // newest first
+    params.sort = Some(ArticleSort::Newest);
+    params.cursor = None;
+    let page = alpha.list_articles(params.clone()).await.unwrap();
+    assert_eq!(titles[54], page[0].title);
+
+    // edit something so it becomes most recently edited
+    let get_res = alpha
+        .get_article(GetArticleParams {
+            title: Some(titles[10].clone()),
+            domain: None,
+            id: None,
+        })
+        .await
+        .unwrap();
+    let edit_params = EditArticleParams {
+        article_id: get_res.article.id,
+        new_text: "Updated text\n".to_string(),
+        summary: "update".to_string(),
+        previous_version_id: get_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    params.sort = Some(ArticleSort::RecentlyEdited);
+    let page = alpha.list_articles(params.clone()).await.unwrap();
+    assert_eq!(titles[10], page[0].title);
+    assert_eq!(titles[54], page[1].title);
+    params.cursor = Some(page[0].id);
+    let page = alpha.list_articles(params.clone()).await.unwrap();
+    assert_eq!(titles[54], page[0].title);
+
+    // edits newest first with cursor
+    let mut edit_list = GetEditList {
+        article_id: Some(get_res.article.id),
+        sort: Some(EditSort::Newest),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let edits = alpha.list_edits(&edit_list).await.unwrap();
+    assert_eq!(1, edits.len());
+    assert_eq!("update", edits[0].edit.summary);
+    edit_list.cursor = Some(edits[0].edit.id);
+    let edits = alpha.list_edits(&edit_list).await.unwrap();
+    assert_eq!("create article", edits[0].edit.summary);
+
+    // beta fetches all pages of the articles collection
+    let alpha_instance = beta
+        .resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
+        .await
+        .unwrap();
+    let beta_articles = RetryFuture::new(
+        || async {
+            let params = ListArticlesParams {
+                instance_id: Some(alpha_instance.id),
+                limit: Some(100),
+                ..Default::default()
+            };
+            match beta.list_articles(params).await {
+                Ok(a) if a.len() == 56 => Ok(a),
+                _ => Err(RetryPolicy::<String>::Retry(None)),
+            }
+        },
+        LinearRetryStrategy::new().max_attempts(20),
+    )
+    .await?;
+    assert_eq!(titles[10], beta_articles[0].title);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_watch_article() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let beta_id_on_alpha = alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+
+    // create article on beta and watch it from both instances
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let beta_article = beta.create_article(&create_params).await.unwrap();
+    assert!(!beta_article.watched);
+    let get_params = GetArticleParams {
+        title: Some(beta_article.article.title.clone()),
+        domain: Some(beta_id_on_alpha.domain),
+        id: None,
+    };
+    let alpha_article = alpha.get_article(get_params.clone()).await.unwrap();
+    assert!(!alpha_article.watched);
+    alpha
+        .watch_article(alpha_article.article.id, true)
+        .await
+        .unwrap();
+    beta.watch_article(beta_article.article.id, true)
+        .await
+        .unwrap();
+    let alpha_article = alpha.get_article(get_params.clone()).await.unwrap();
+    assert!(alpha_article.watched);
+    assert_eq!(0, alpha.notifications_count().await.unwrap());
+    assert_eq!(0, beta.notifications_count().await.unwrap());
+
+    // edit on alpha is sent to beta, only beta gets notified
+    let edit_params = EditArticleParams {
+        article_id: alpha_article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "edit from alpha".to_string(),
+        previous_version_id: alpha_article.latest_version,
+        resolve_conflict_id: None,
+    };
+    let alpha_article = alpha
+        .edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    assert_eq!(0, alpha.notifications_count().await.unwrap());
+    assert_eq!(1, beta.notifications_count().await.unwrap());
+    let notifications = beta.notifications_list(&Default::default()).await.unwrap();
+    let Notification::ArticleEdited(edit) = &notifications[0].data else {
+        panic!()
+    };
+    assert_eq!(edit_params.summary, edit.edit.summary);
+    beta.mark_notification_as_read(notifications[0].id)
+        .await
+        .unwrap();
+    assert_eq!(0, beta.notifications_count().await.unwrap());
+    let notifications = beta.notifications_list(&Default::default()).await.unwrap();
+    assert_eq!(1, notifications.len());
+    assert!(notifications[0].read);
+    let params = ListNotificationsParams {
+        unread_only: Some(true),
+        ..Default::default()
+    };
+    assert!(beta.notifications_list(&params).await.unwrap().is_empty());
+    beta.delete_notification(notifications[0].id).await.unwrap();
+    let notifications = beta.notifications_list(&Default::default()).await.unwrap();
+    assert!(notifications.is_empty());
+
+    // edit on beta is federated to alpha, which gets notified
+    let edit_params = EditArticleParams {
+        article_id: beta_article.article.id,
+        new_text: "Lorem Ipsum 3\n".to_string(),
+        summary: "edit from beta".to_string(),
+        previous_version_id: alpha_article.latest_version,
+        resolve_conflict_id: None,
+    };
+    beta.edit_article_without_conflict(&edit_params)
+        .await
+        .unwrap();
+    assert_eq!(0, beta.notifications_count().await.unwrap());
+    assert_eq!(1, alpha.notifications_count().await.unwrap());
+    let notifications = alpha.notifications_list(&Default::default()).await.unwrap();
+    let Notification::ArticleEdited(edit) = &notifications[0].data else {
+        panic!()
+    };
+    assert_eq!(edit_params.summary, edit.edit.summary);
+
+    // no more notifications after unwatching
+    alpha
+        .watch_article(alpha_article.article.id, false)
+        .await
+        .unwrap();
+    assert_eq!(0, alpha.notifications_count().await.unwrap());
+    let alpha_article = alpha.get_article(get_params).await.unwrap();
+    assert!(!alpha_article.watched);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_synchronize_instances() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // fetch alpha instance on beta
+    beta.resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
+        .await
+        .unwrap();
+    let beta_instances = beta.list_instances(&Default::default()).await.unwrap();
+    assert_eq!(2, beta_instances.len());
+
+    // fetch beta instance on gamma
+    gamma
+        .resolve_instance(Url::parse(&format!("http://{}", &beta.hostname))?)
+        .await
+        .unwrap();
+
+    // wait until instance collection is fetched
+    let gamma_instances = RetryFuture::new(
+        || async {
+            let res = gamma.list_instances(&Default::default()).await;
+            match res {
+                Err(_) => Err(RetryPolicy::<String>::Retry(None)),
+                Ok(i) if i.len() < 3 => Err(RetryPolicy::Retry(None)),
+                Ok(i) => Ok(i),
+            }
+        },
+        LinearRetryStrategy::new(),
+    )
+    .await?;
+
+    // now gamma also knows about alpha
+    assert_eq!(3, gamma_instances.len());
+    assert!(gamma_instances
+        .iter()
+        .any(|i| i.instance.domain == alpha.hostname));
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_article_approval_required() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(true).await;
+
+    // create article
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    assert!(!create_res.article.approved);
+
+    let list_all = alpha.list_articles(Default::default()).await.unwrap();
+    assert_eq!(1, list_all.len());
+    assert!(list_all.iter().all(|a| a.id != create_res.article.id));
+
+    // login as admin to handle approvals
+    let params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        ..Default::default()
+    };
+    alpha.login(params).await.unwrap();
+
+    assert_eq!(1, alpha.notifications_count().await.unwrap());
+    let notifications = alpha.notifications_list(&Default::default()).await.unwrap();
+    assert_eq!(1, notifications.len());
+    let Notification::ArticleApprovalRequired(notif) = &notifications[0].data else {
+        panic!()
+    };
+    assert_eq!(create_res.article.id, notif.id);
+
+    alpha.approve_article(notif.id, true).await.unwrap();
+    assert_eq!(0, alpha.notifications_count().await.unwrap());
+    let params = GetArticleParams {
+        id: Some(create_res.article.id),
+        ..Default::default()
+    };
+    let approved = alpha.get_article(params).await.unwrap();
+    assert_eq!(create_res.article.id, approved.article.id);
+    assert!(approved.article.approved);
+
+    let list_all = alpha.list_articles(Default::default()).await.unwrap();
+    assert_eq!(2, list_all.len());
+    assert!(list_all.iter().any(|a| a.id == create_res.article.id));
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_comment_create_edit() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(true).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+    gamma
+        .follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+    };
+    let alpha_article = alpha.create_article(&params).await.unwrap();
+
+    // fetch article on beta and create comment
+    let beta_article = beta
+        .resolve_article(alpha_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let params = CreateCommentParams {
+        content: "top comment".to_string(),
+        article_id: beta_article.article.id,
+        parent_id: None,
+    };
+    let top_comment = beta.create_comment(&params).await.unwrap().comment;
+    assert_eq!(top_comment.content, params.content);
+    assert_eq!(top_comment.article_id, beta_article.article.id);
+    assert_eq!(top_comment.depth, 0);
+    assert!(top_comment.parent_id.is_none());
+    assert!(top_comment.local);
+    assert!(!top_comment.deleted);
+    assert!(top_comment.updated.is_none());
+    sleep(Duration::from_secs(1)).await;
+
+    // now create child comment on alpha
+    let get_params = GetArticleParams {
+        title: Some(alpha_article.article.title),
+        domain: Some(alpha.hostname.clone()),
+        ..Default::default()
+    };
+    let article = alpha.get_article(get_params.clone()).await.unwrap();
+    assert_eq!(1, article.comments.len());
+    let params = CreateCommentParams {
+        content: "child comment".to_string(),
+        article_id: article.article.id,
+        parent_id: Some(article.comments[0].comment.id),
+    };
+    let child_comment = alpha.create_comment(&params).await.unwrap().comment;
+    assert_eq!(child_comment.parent_id, Some(top_comment.id));
+    assert_eq!(child_comment.depth, 1);
+
+    // edit comment text
+    let edit_params = EditCommentParams {
+        id: child_comment.id,
+        content: Some("edited comment".to_string()),
+        deleted: None,
+    };
+    let edited_comment = alpha.edit_comment(&edit_params).await.unwrap().comment;
+    assert_eq!(edited_comment.article_id, article.article.id);
+    assert_eq!(Some(&edited_comment.content), edit_params.content.as_ref());
 
     let beta_comments = beta.get_article(get_params.clone()).await.unwrap().comments;
     assert_eq!(2, beta_comments.len());
+
+    // reply is federated to beta and creates a notification there
+    assert_eq!(1, beta.notifications_count().await.unwrap());
+    let notifications = beta.notifications_list(&Default::default()).await.unwrap();
+    let Notification::Reply(reply) = &notifications[0].data else {
+        panic!()
+    };
+    assert_eq!(edited_comment.ap_id, reply.comment.ap_id);
+    // deprecated endpoint still works
+    beta.mark_comment_as_read(reply.comment.id).await.unwrap();
+    assert_eq!(0, beta.notifications_count().await.unwrap());
+    beta.mark_all_notifications_as_read().await.unwrap();
     assert_eq!(beta_comments[1].comment.content, top_comment.content);
     assert_eq!(
         Some(&beta_comments[0].comment.content),